@@ -0,0 +1,7 @@
+/*
+ Index for the account listing API.
+ - created_at range filter
+ - keyset pagination ordered by (created_at, id)
+*/
+CREATE INDEX IF NOT EXISTS idx_pjsip_accounts_created_at_id
+          ON pjsip_realtime_accounts(created_at, id);
@@ -0,0 +1,7 @@
+/*
+ MD5 digest credentials support.
+ - Accounts created with auth_type = md5 store only ps_auths.md5_cred / realm,
+   so pjsip_realtime_accounts.password is NULL for them.
+*/
+ALTER TABLE pjsip_realtime_accounts
+      ALTER COLUMN password DROP NOT NULL;
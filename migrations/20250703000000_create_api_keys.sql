@@ -0,0 +1,25 @@
+/*
+ API keys for machine clients (e.g. dialer bots) that cannot use OIDC.
+ - id: ULID of the key
+ - name: label given by the administrator
+ - key_prefix: first characters of the key, shown in listings to identify it
+ - key_hash: SHA-256 (hex) of the key. The key itself is returned only once, when issued.
+ - scopes: roles granted to the key (same names as the JWT roles, e.g. pjsip:read)
+ - expires_at: NULL means the key does not expire
+ - revoked_at: set when the key is revoked; revoked keys are kept for auditing
+*/
+CREATE TABLE IF NOT EXISTS api_keys (
+    id VARCHAR(26) PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    key_prefix VARCHAR(16) NOT NULL,
+    key_hash CHAR(64) NOT NULL,
+    scopes TEXT[] NOT NULL DEFAULT '{}',
+    expires_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    last_used_at TIMESTAMPTZ,
+    created_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_api_keys_key_hash_unique
+                 ON api_keys(key_hash);
@@ -0,0 +1,25 @@
+/*
+ SIP trunks (carrier registrations) managed by the API.
+ Each trunk owns ps_registrations / ps_auths / ps_aors / ps_endpoints rows with the same id,
+ and its ps_endpoint_id_ips rows reference it by endpoint.
+ - id: ULID of the trunk
+ - name: label given by the administrator (unique)
+ - transport: transport type (UDP, TCP, TLS)
+ - server_uri: registrar of the carrier (ps_registrations.server_uri)
+ - context: dialplan context for calls from the carrier
+*/
+CREATE TABLE IF NOT EXISTS pjsip_realtime_trunks (
+    id VARCHAR(255) PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    transport VARCHAR(10) NOT NULL
+                          CHECK (transport IN ('udp', 'tcp', 'tls')),
+    server_uri VARCHAR(255) NOT NULL,
+    context VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_pjsip_trunks_name_unique
+                 ON pjsip_realtime_trunks(name);
+CREATE INDEX IF NOT EXISTS idx_pjsip_trunks_created_at_id
+          ON pjsip_realtime_trunks(created_at, id);
@@ -67,4 +67,8 @@ GRANT SELECT ON information_schema.columns TO api_user_rw;
 GRANT SELECT, INSERT, UPDATE, DELETE ON TABLE ps_auths TO api_user_rw;
 GRANT SELECT, INSERT, UPDATE, DELETE ON TABLE ps_aors TO api_user_rw;
 GRANT SELECT, INSERT, UPDATE, DELETE ON TABLE ps_endpoints TO api_user_rw;
-GRANT SELECT, INSERT, UPDATE, DELETE ON TABLE ps_registrations TO api_user_rw;
\ No newline at end of file
+GRANT SELECT, INSERT, UPDATE, DELETE ON TABLE ps_registrations TO api_user_rw;
+-- asterisk pjsip realtime indexes used by the API
+-- ps_contacts.id is "<aor>^3B@<hash>"; the contacts and registration summary APIs look up
+-- the contacts of an AOR by split_part(id, '^3B@', 1).
+CREATE INDEX IF NOT EXISTS idx_ps_contacts_aor ON ps_contacts (split_part(id, '^3B@', 1));
@@ -0,0 +1,44 @@
+use crate::AppState;
+use crate::infrastructure::models::authentication::authorization_policy::Permission;
+use crate::infrastructure::models::authentication::principal::Principal;
+use crate::infrastructure::models::errors::api_error::{ApiError, ErrorCode};
+
+// 呼び出し元のロール (JWT のロール / API キーの scopes) がポリシーで許可されているかを確認します。
+// 認証が無効 (AUTH_DISABLED=true) の場合は確認しません。
+pub fn authorize(
+    state: &AppState,
+    principal: Option<&Principal>,
+    permission: Permission,
+) -> Result<(), ApiError> {
+    if state.authenticator.is_disabled() {
+        return Ok(());
+    }
+
+    let required_roles: &[String] = state.config.authorization_policy.allowed_roles(permission);
+    let audiences: &[String] = state
+        .config
+        .oidc
+        .as_ref()
+        .map(|oidc| oidc.audiences.as_slice())
+        .unwrap_or_default();
+    let granted: bool = principal.is_some_and(|principal| {
+        let roles: Vec<&str> = principal.roles(audiences);
+        required_roles
+            .iter()
+            .any(|role| roles.contains(&role.as_str()))
+    });
+    if granted {
+        return Ok(());
+    }
+
+    tracing::info!(
+        "Denied {:?} for {}",
+        permission,
+        principal.map_or_else(|| String::from("anonymous"), Principal::subject)
+    );
+    Err(
+        ApiError::new(ErrorCode::MissingRole, "Missing required role")
+            .with_extension("permission", permission)
+            .with_extension("required_roles", required_roles),
+    )
+}
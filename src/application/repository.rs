@@ -1 +1,5 @@
+pub mod api_keys;
+pub mod pjsip_contacts;
+pub mod pjsip_identifies;
 pub mod pjsip_realtime;
+pub mod pjsip_trunks;
@@ -0,0 +1,109 @@
+use crate::AppState;
+use crate::infrastructure::models::authentication::api_key::{
+    ApiKey, IssueApiKeyRequest, IssuedApiKey, api_key_display_prefix, generate_api_key,
+    hash_api_key,
+};
+use crate::infrastructure::models::errors::{
+    api_error::{ApiError, ErrorCode},
+    authentication_error::AuthenticationError,
+};
+use crate::infrastructure::repository::api_key_repository::{
+    exec_authenticate_api_key, exec_insert_api_key, exec_revoke_api_key, get_api_keys,
+};
+use axum::{extract::State, http::StatusCode};
+use ulid::Ulid;
+
+const MAX_API_KEY_NAME_LENGTH: usize = 100;
+
+pub async fn issue_api_key(
+    state: State<AppState>,
+    request: IssueApiKeyRequest,
+    created_by: Option<String>,
+) -> Result<IssuedApiKey, ApiError> {
+    // validation
+    let name: &str = request.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_API_KEY_NAME_LENGTH {
+        let error_message = format!(
+            "name must be between 1 and {} characters",
+            MAX_API_KEY_NAME_LENGTH
+        );
+        return Err(ApiError::new(ErrorCode::ValidationFailed, error_message));
+    }
+    if request.scopes.is_empty()
+        || request
+            .scopes
+            .iter()
+            .any(|scope| scope.is_empty() || scope.chars().any(char::is_whitespace))
+    {
+        return Err(ApiError::new(
+            ErrorCode::ValidationFailed,
+            "scopes must be a non-empty list of role names",
+        ));
+    }
+    let now = chrono::Utc::now();
+    if request
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= now)
+    {
+        return Err(ApiError::new(
+            ErrorCode::ValidationFailed,
+            "expires_at must be in the future",
+        ));
+    }
+
+    let mut scopes: Vec<String> = request.scopes;
+    scopes.sort();
+    scopes.dedup();
+
+    let plaintext: String = generate_api_key();
+    let key = ApiKey {
+        id: Ulid::new().to_string(),
+        name: name.to_string(),
+        key_prefix: api_key_display_prefix(&plaintext),
+        scopes,
+        expires_at: request.expires_at,
+        revoked_at: None,
+        last_used_at: None,
+        created_by,
+        created_at: now,
+    };
+
+    exec_insert_api_key(&state.pjsip_db, &key, &hash_api_key(&plaintext))
+        .await
+        .map_err(|e| ApiError::from(e).context("Failed to issue API key"))?;
+    tracing::info!(
+        "Issued API key {} ({}) with scopes {:?}",
+        key.id,
+        key.name,
+        key.scopes
+    );
+    Ok(IssuedApiKey {
+        key,
+        api_key: plaintext,
+    })
+}
+
+pub async fn list_api_keys(state: State<AppState>) -> Result<Vec<ApiKey>, ApiError> {
+    get_api_keys(&state.pjsip_db)
+        .await
+        .map_err(|e| ApiError::from(e).context("Failed to get API keys"))
+}
+
+pub async fn revoke_api_key(
+    state: State<AppState>,
+    key_id: String,
+) -> Result<StatusCode, ApiError> {
+    exec_revoke_api_key(&state.pjsip_db, &key_id)
+        .await
+        .map_err(|e| ApiError::from(e).context("Failed to revoke API key"))?;
+    tracing::info!("Revoked API key {}", key_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// X-API-Key ヘッダーのキーを検証します
+pub async fn authenticate_api_key(
+    state: &AppState,
+    api_key: &str,
+) -> Result<ApiKey, AuthenticationError> {
+    exec_authenticate_api_key(&state.pjsip_db, &hash_api_key(api_key)).await
+}
@@ -0,0 +1,84 @@
+use crate::AppState;
+use crate::infrastructure::models::errors::api_error::{ApiError, ErrorCode};
+use crate::infrastructure::models::pjsip_realtime::{
+    account_list::MAX_PAGE_LIMIT,
+    contact::PsContactResponse,
+    registration_status::{
+        AccountRegistration, MAX_EXPIRING_WITHIN, RegistrationCounts, RegistrationGroup,
+        RegistrationSummary, RegistrationSummaryParams,
+    },
+};
+use crate::infrastructure::repository::pjsip_contact_repository::{
+    get_pjsip_account_registrations, get_pjsip_aor_contacts, get_pjsip_registration_groups,
+};
+use crate::infrastructure::repository::pjsip_realtime_repository::get_pjsip_account_by_id;
+use axum::extract::State;
+
+// アカウントの AOR に登録中の contact (ps_contacts)
+pub async fn get_pjsip_account_contacts(
+    state: State<AppState>,
+    account_id: String,
+) -> Result<Vec<PsContactResponse>, ApiError> {
+    let account = get_pjsip_account_by_id(&state.pjsip_db, &account_id)
+        .await
+        .map_err(|e| ApiError::from(e).context("Failed to get account"))?;
+    let aor_id: String = account.aor.map_or(account_id, |aor| aor.id);
+    get_pjsip_aor_contacts(&state.pjsip_db, &aor_id)
+        .await
+        .map_err(|e| ApiError::from(e).context("Failed to get contacts"))
+}
+
+// 全アカウントの登録状況の集計 (transport / context ごと)
+pub async fn get_pjsip_registration_summary(
+    state: State<AppState>,
+    params: &RegistrationSummaryParams,
+) -> Result<RegistrationSummary, ApiError> {
+    // validation
+    let expiring_within: i64 = params.expiring_within();
+    if !(0..=MAX_EXPIRING_WITHIN).contains(&expiring_within) {
+        let error_message = format!(
+            "expiring_within must be between 0 and {}",
+            MAX_EXPIRING_WITHIN
+        );
+        return Err(ApiError::new(ErrorCode::InvalidQuery, error_message));
+    }
+
+    let limit: i64 = params.page_limit();
+    if !(1..=MAX_PAGE_LIMIT).contains(&limit) {
+        let error_message = format!("limit must be between 1 and {}", MAX_PAGE_LIMIT);
+        return Err(ApiError::new(ErrorCode::InvalidQuery, error_message));
+    }
+
+    let groups: Vec<RegistrationGroup> =
+        get_pjsip_registration_groups(&state.pjsip_db, expiring_within)
+            .await
+            .map_err(|e| ApiError::from(e).context("Failed to get registrations"))?;
+    let mut counts = RegistrationCounts::default();
+    for group in &groups {
+        counts.add(&group.counts);
+    }
+
+    let (accounts, next_cursor): (Option<Vec<AccountRegistration>>, Option<String>) =
+        match params.status {
+            Some(status) => {
+                let (accounts, next_cursor) = get_pjsip_account_registrations(
+                    &state.pjsip_db,
+                    status,
+                    expiring_within,
+                    limit,
+                    params.cursor.as_deref(),
+                )
+                .await
+                .map_err(|e| ApiError::from(e).context("Failed to get registrations"))?;
+                (Some(accounts), next_cursor)
+            }
+            None => (None, None),
+        };
+    Ok(RegistrationSummary {
+        expiring_within,
+        counts,
+        groups,
+        accounts,
+        next_cursor,
+    })
+}
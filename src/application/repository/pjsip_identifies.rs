@@ -0,0 +1,126 @@
+use crate::AppState;
+use crate::infrastructure::models::errors::api_error::{ApiError, ErrorCode};
+use crate::infrastructure::models::pjsip_realtime::{
+    identify_validation::validate_identify,
+    sip_identify::{
+        PjsipEndpointIdentify, PjsipEndpointIdentifyResponse, PsEndpointIdIp, matches_overlap,
+    },
+};
+use crate::infrastructure::repository::pjsip_identify_repository::{
+    exec_delete_pjsip_endpoint_id_ip, exec_insert_pjsip_endpoint_id_ip, exists_pjsip_endpoint,
+    get_other_endpoint_matches, get_pjsip_endpoint_id_ip_by_id, get_pjsip_endpoint_id_ips,
+    lock_pjsip_endpoint_id_ips,
+};
+use axum::{extract::State, http::StatusCode};
+use sqlx::{PgPool, Postgres, Transaction};
+use ulid::Ulid;
+
+pub async fn list_pjsip_endpoint_identifies(
+    state: State<AppState>,
+    endpoint_id: String,
+) -> Result<Vec<PjsipEndpointIdentifyResponse>, ApiError> {
+    ensure_pjsip_endpoint_exists(&state.pjsip_db, &endpoint_id).await?;
+    get_pjsip_endpoint_id_ips(&state.pjsip_db, &endpoint_id)
+        .await
+        .map_err(|e| ApiError::from(e).context("Failed to get identifies"))
+}
+
+// エンドポイント (アカウントまたはトランク) に送信元 IP / SIP ヘッダーによる識別を追加します
+// 他のエンドポイントの match と重なる場合は、どちらに着信するか決まらないため 409 を返します。
+pub async fn create_pjsip_endpoint_identify(
+    state: State<AppState>,
+    endpoint_id: String,
+    identify: PjsipEndpointIdentify,
+) -> Result<PjsipEndpointIdentifyResponse, ApiError> {
+    validate_identify(&identify)?;
+    ensure_pjsip_endpoint_exists(&state.pjsip_db, &endpoint_id).await?;
+
+    let row = PsEndpointIdIp {
+        id: Ulid::new().to_string(),
+        endpoint: endpoint_id.clone(),
+        r#match: (!identify.matches.is_empty()).then(|| identify.matches.join(",")),
+        match_header: identify.match_header.clone(),
+    };
+    let mut transaction = state.pjsip_db.begin().await?;
+    if let Err(e) = check_match_overlaps(&mut transaction, &endpoint_id, &identify.matches).await {
+        let _ = transaction.rollback().await;
+        return Err(e);
+    }
+    match exec_insert_pjsip_endpoint_id_ip(&mut transaction, &row).await {
+        Ok(_) => transaction.commit().await?,
+        Err(e) => {
+            let _ = transaction.rollback().await;
+            return Err(ApiError::from(e).context("Failed to create identify"));
+        }
+    }
+    tracing::info!("Created identify {} for endpoint {}", row.id, endpoint_id);
+    get_pjsip_endpoint_id_ip_by_id(&state.pjsip_db, &endpoint_id, &row.id)
+        .await
+        .map_err(|e| ApiError::from(e).context("Failed to get identify"))
+}
+
+pub async fn delete_pjsip_endpoint_identify(
+    state: State<AppState>,
+    endpoint_id: String,
+    identify_id: String,
+) -> Result<StatusCode, ApiError> {
+    let mut transaction = state.pjsip_db.begin().await?;
+    match exec_delete_pjsip_endpoint_id_ip(&mut transaction, &endpoint_id, &identify_id).await {
+        Ok(_) => {
+            transaction.commit().await?;
+            tracing::info!(
+                "Deleted identify {} for endpoint {}",
+                identify_id,
+                endpoint_id
+            );
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            let _ = transaction.rollback().await;
+            Err(ApiError::from(e).context("Failed to delete identify"))
+        }
+    }
+}
+
+// 他のエンドポイントに登録済みの match と重なるものがあれば 409 を返します
+// (同じエンドポイントの match 同士の重なりは着信先が変わらないため許容します)
+// 同時に登録されるリクエストと競合しないよう、登録と同じトランザクション内で
+// ps_endpoint_id_ips をロックしてから判定します。
+pub async fn check_match_overlaps(
+    transaction: &mut Transaction<'_, Postgres>,
+    endpoint_id: &str,
+    matches: &[String],
+) -> Result<(), ApiError> {
+    if matches.is_empty() {
+        return Ok(());
+    }
+    lock_pjsip_endpoint_id_ips(transaction).await?;
+    let existing: Vec<(String, String)> =
+        get_other_endpoint_matches(transaction, endpoint_id).await?;
+    for (i, value) in matches.iter().enumerate() {
+        if let Some((endpoint, existing_match)) = existing
+            .iter()
+            .find(|(_, existing_match)| matches_overlap(existing_match, value))
+        {
+            let error_message = format!(
+                "match {} overlaps {} of endpoint {}",
+                value, existing_match, endpoint
+            );
+            return Err(ApiError::new(ErrorCode::MatchOverlap, error_message)
+                .with_extension("field", format!("match[{}]", i))
+                .with_extension("endpoint", endpoint)
+                .with_extension("overlapping_match", existing_match));
+        }
+    }
+    Ok(())
+}
+
+async fn ensure_pjsip_endpoint_exists(pool: &PgPool, endpoint_id: &str) -> Result<(), ApiError> {
+    if !exists_pjsip_endpoint(pool, endpoint_id).await? {
+        return Err(ApiError::new(
+            ErrorCode::NotFound,
+            format!("Endpoint {} not found", endpoint_id),
+        ));
+    }
+    Ok(())
+}
@@ -1,307 +1,1079 @@
 use crate::AppState;
-use crate::infrastructure::models::errors::registration_error::RegistrationError;
+use crate::config::AppConfig;
+use crate::infrastructure::models::errors::{
+    api_error::{ApiError, ErrorCode},
+    deletion_error::DeletionError,
+    registration_error::RegistrationError,
+    update_error::UpdateError,
+    validation_error::ValidationErrors,
+};
 use crate::infrastructure::models::pjsip_realtime::enums::{
     pjsip_auth_enums::AuthType,
-    pjsip_endpoint_enums::{DtmfMode, MediaEncryption, RtpTimeout, TransportType},
+    pjsip_endpoint_enums::{
+        DtlsFingerprint, DtlsSetup, DtmfMode, MediaEncryption, RtpTimeout, TransportType,
+    },
     pjsip_realtime_common_enums::TurnOnOff,
 };
 use crate::infrastructure::models::pjsip_realtime::{
-    account::{PjsipRealtimeAccount, PjsipRealtimeAccountWithId},
-    sip_udp::{PsAorForUdp, PsAuthForUdp, PsEndpointForUdp},
-    sip_ws::{PsAorForWs, PsAuthForWs, PsEndpointForWs},
+    account::{
+        PjsipRealtimeAccount, PjsipRealtimeAccountCredentials, PjsipRealtimeAccountPatch,
+        PjsipRealtimeAccountResponse, PjsipRealtimeAccountWithId,
+    },
+    account_bulk::{
+        BulkCreateMode, BulkCreateResponse, BulkCreateResult, BulkPjsipRealtimeAccount,
+        MAX_BULK_ACCOUNTS,
+    },
+    account_csv::{
+        CSV_COLUMNS, CsvRowError, ExportFormat, ImportReport, ImportedAccount, check_csv_headers,
+        parse_csv_record,
+    },
+    account_detail::PjsipRealtimeAccountDetail,
+    account_list::{AccountListParams, AccountPage, MAX_PAGE_LIMIT},
+    account_validation::{
+        validate_account, validate_account_patch, validate_imported_account,
+        validate_updated_account,
+    },
+    auth::AuthCredentials,
+    pjsip_conf::{
+        PjsipConf, PjsipConfEndpoint, PjsipConfImportParams, PjsipConfImportReport,
+        PjsipConfImportedEndpoint, PjsipConfIssue, read_pjsip_conf, render_pjsip_conf,
+        render_pjsip_conf_account,
+    },
+    sip_account::{PjsipAccountRows, PsAorForAccount, PsAuthForAccount, PsEndpointForAccount},
+    sip_tls::{DEFAULT_TLS_TRANSPORT_NAME, TlsAccountOptions},
+    sip_ws::DtlsAccountOptions,
 };
+use crate::infrastructure::repository::pjsip_identify_repository::exec_delete_pjsip_endpoint_id_ips;
 use crate::infrastructure::repository::pjsip_realtime_repository::{
-    exec_delete_pjsip_account, exec_insert_udp_pjsip_account, exec_insert_ws_pjsip_account,
-    get_all_pjsip_accounts,
+    exec_delete_pjsip_account, exec_insert_pjsip_account, exec_update_pjsip_account,
+    exec_update_pjsip_endpoint_transport, get_existing_account_keys, get_pjsip_account_by_id,
+    get_pjsip_account_credentials, get_pjsip_account_details, get_pjsip_account_for_update,
+    get_pjsip_accounts_credentials, get_pjsip_accounts_for_export, get_pjsip_accounts_page,
+    get_pjsip_auth_for_update,
 };
 use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
-use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 use ulid::Ulid;
 
-pub async fn create_udp_pjsip_account(
+// アカウントを 1 件登録し、登録した ID を返します。
+// account_id が指定されていない場合は ULID を採番します。
+pub async fn create_pjsip_account(
     state: State<AppState>,
     account_id: Option<String>,
     account: &PjsipRealtimeAccount,
-) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
-    println!("TODO create_udp_account validation here.");
+) -> Result<String, ApiError> {
+    validate_account(&state.config, account_id.as_deref(), account)?;
+
+    let mut transaction: sqlx::Transaction<'static, sqlx::Postgres> =
+        state.pjsip_db.begin().await?;
+    match insert_pjsip_account(&mut transaction, &state.config, account_id, account).await {
+        Ok(new_account_id) => {
+            transaction.commit().await?;
+            Ok(new_account_id)
+        }
+        Err(e) => {
+            let _ = transaction.rollback().await;
+            Err(e.context("Failed to create account"))
+        }
+    }
+}
+
+// if_exists が true の場合、存在しないアカウントの削除も成功 (204) として扱います。
+// 送信元 IP による識別 (ps_endpoint_id_ips) もアカウントと一緒に削除します。
+pub async fn delete_pjsip_account(
+    state: State<AppState>,
+    account_id: String,
+    if_exists: bool,
+) -> Result<impl IntoResponse, ApiError> {
+    // repository delete
+    let mut transaction = state.pjsip_db.begin().await?;
+
+    let result = exec_delete_pjsip_account(&mut transaction, account_id.clone()).await;
+    match result {
+        Ok(_) => {
+            if let Err(e) = exec_delete_pjsip_endpoint_id_ips(&mut transaction, &account_id).await {
+                let _ = transaction.rollback().await;
+                return Err(ApiError::from(e).context("Failed to delete account"));
+            }
+            transaction.commit().await?;
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(DeletionError::NotFoundRecord) if if_exists => {
+            let _ = transaction.rollback().await;
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            let _ = transaction.rollback().await;
+            Err(ApiError::from(e).context("Failed to delete account"))
+        }
+    }
+}
 
+pub async fn get_accounts(_: State<AppState>) -> impl IntoResponse {
+    // Here you would typically query the database using `state.pjsip_db`
+    let users = vec!["user1", "user2", "user3"];
+    (StatusCode::OK, Json(users))
+}
+
+pub async fn get_pjsip_accounts(
+    state: State<AppState>,
+    params: &AccountListParams,
+) -> Result<AccountPage<PjsipRealtimeAccountResponse>, ApiError> {
     // validation
+    let limit: i64 = params.page_limit();
+    if !(1..=MAX_PAGE_LIMIT).contains(&limit) {
+        let error_message = format!("limit must be between 1 and {}", MAX_PAGE_LIMIT);
+        return Err(ApiError::new(ErrorCode::InvalidQuery, error_message));
+    }
+    if matches!((params.created_from, params.created_to), (Some(from), Some(to)) if from >= to) {
+        return Err(ApiError::new(
+            ErrorCode::InvalidQuery,
+            "created_from must be earlier than created_to",
+        ));
+    }
 
-    // common
-    // If account_id is provided, use it; otherwise, generate a new ULID
-    let new_account_id: String = match account_id {
-        Some(id) => id,
-        None => Ulid::new().to_string(),
+    get_pjsip_accounts_page(&state.pjsip_db, params)
+        .await
+        .map_err(|e| ApiError::from(e).context("Failed to get accounts"))
+}
+
+pub async fn get_pjsip_account(
+    state: State<AppState>,
+    account_id: String,
+) -> Result<PjsipRealtimeAccountDetail, ApiError> {
+    get_pjsip_account_by_id(&state.pjsip_db, &account_id)
+        .await
+        .map_err(|e| ApiError::from(e).context("Failed to get account"))
+}
+
+// 認証情報 (パスワード) の参照
+// 通常の参照系とは別に、設定された reveal トークンを持つクライアントのみ許可します。
+pub async fn reveal_pjsip_account_credentials(
+    state: State<AppState>,
+    account_id: String,
+    reveal_token: Option<&str>,
+    requested_by: Option<&str>,
+) -> Result<PjsipRealtimeAccountCredentials, ApiError> {
+    check_reveal_token(&state.config, reveal_token)?;
+
+    let credentials = get_pjsip_account_credentials(&state.pjsip_db, &account_id)
+        .await
+        .map_err(|e| ApiError::from(e).context("Failed to get credentials"))?;
+    tracing::warn!(
+        "Revealed credentials of account {} (requested by {})",
+        account_id,
+        requested_by.unwrap_or("anonymous")
+    );
+    Ok(credentials)
+}
+
+// 認証情報を返す処理 (credentials / include_secrets のエクスポート) で共通の reveal トークンの確認
+fn check_reveal_token(config: &AppConfig, reveal_token: Option<&str>) -> Result<(), ApiError> {
+    let Some(expected_token) = config.credentials_reveal_token.as_deref() else {
+        return Err(ApiError::new(
+            ErrorCode::CredentialsRevealDisabled,
+            "Revealing credentials is disabled",
+        ));
     };
+    if !reveal_token
+        .is_some_and(|token| constant_time_eq(token.as_bytes(), expected_token.as_bytes()))
+    {
+        return Err(ApiError::new(
+            ErrorCode::InvalidRevealToken,
+            "Not allowed to reveal credentials",
+        ));
+    }
+    Ok(())
+}
 
-    // let account_ulid: String = new_account_id;
-    let pjsip_account: PjsipRealtimeAccountWithId = PjsipRealtimeAccountWithId {
-        id: new_account_id.clone(),
+// トークン比較で一致した長さが推測されないよう、全バイトを比較します。
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+pub async fn update_pjsip_account(
+    state: State<AppState>,
+    account_id: String,
+    patch: &PjsipRealtimeAccountPatch,
+    allow_transport_change: bool,
+) -> Result<PjsipRealtimeAccountDetail, ApiError> {
+    validate_account_patch(patch)?;
+
+    let mut transaction: sqlx::Transaction<'static, sqlx::Postgres> =
+        state.pjsip_db.begin().await?;
+
+    let current: PjsipRealtimeAccountWithId =
+        match get_pjsip_account_for_update(&mut transaction, &account_id).await {
+            Ok(account) => account,
+            Err(e) => {
+                let _ = transaction.rollback().await;
+                return Err(update_error(e));
+            }
+        };
+    let current_auth: AuthCredentials =
+        match get_pjsip_auth_for_update(&mut transaction, &account_id).await {
+            Ok(auth) => auth,
+            Err(e) => {
+                let _ = transaction.rollback().await;
+                return Err(update_error(e));
+            }
+        };
+    let mut updated: PjsipRealtimeAccountWithId = patch.apply_to(&current);
+    if let Err(e) = validate_updated_account(patch, &updated) {
+        let _ = transaction.rollback().await;
+        return Err(e.into());
+    }
+    let auth: AuthCredentials = match resolve_updated_auth_credentials(
+        &state.config,
+        patch,
+        &current,
+        &current_auth,
+        &updated,
+    ) {
+        Ok(auth) => auth,
+        Err(e) => {
+            let _ = transaction.rollback().await;
+            return Err(e);
+        }
+    };
+    updated.password = auth.password.clone();
+
+    // トランスポートの変更は端末の接続方法が変わるため、明示的に要求された場合のみ許可します。
+    let transport_changed: bool = updated.transport != current.transport;
+    if transport_changed && !allow_transport_change {
+        let _ = transaction.rollback().await;
+        let error_message = format!(
+            "Changing transport from {} to {} changes the endpoint's transport settings; set allow_transport_change=true to proceed",
+            current.transport, updated.transport
+        );
+        return Err(ApiError::new(
+            ErrorCode::TransportChangeNotAllowed,
+            error_message,
+        ));
+    }
+    // TLS / DTLS のオプションは ps_endpoints の複数のカラムに展開されるため、
+    // トランスポートの変更時と同様にトランスポート固有のカラムをまとめて更新します。
+    let updates_transport: bool =
+        transport_changed || patch.changes_transport_options(&updated.transport);
+    let result =
+        update_pjsip_account_rows(&mut transaction, &updated, &auth, updates_transport).await;
+
+    match result {
+        Ok(_) => {
+            transaction.commit().await?;
+            get_pjsip_account(state, account_id).await
+        }
+        Err(e) => {
+            let _ = transaction.rollback().await;
+            Err(e)
+        }
+    }
+}
+
+// pjsip_realtime_accounts / ps_auths / ps_endpoints を更新します。
+// updates_transport が true の場合は、新しいトランスポートの既定値と更新後の TLS / DTLS のオプション
+// (未指定の項目はデフォルト値) で、ps_endpoints のトランスポート固有のカラムも更新します。
+async fn update_pjsip_account_rows(
+    transaction: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    account: &PjsipRealtimeAccountWithId,
+    auth: &AuthCredentials,
+    updates_transport: bool,
+) -> Result<(), ApiError> {
+    exec_update_pjsip_account(transaction, account, auth)
+        .await
+        .map_err(update_error)?;
+    if !updates_transport {
+        return Ok(());
+    }
+
+    let new_account: PjsipRealtimeAccount = PjsipRealtimeAccount {
         username: account.username.clone(),
-        password: account.password.clone(),
-        context: account.context.clone(),
+        password: account.password.clone().unwrap_or_default(),
         transport: account.transport.clone(),
+        context: account.context.clone(),
         from_domain: account.from_domain.clone(),
         from_user: account.from_user.clone(),
         rtp_timeout: account.rtp_timeout,
         rtp_timeout_hold: account.rtp_timeout_hold,
-        created_at: chrono::Utc::now(),
-        updated_at: chrono::Utc::now(),
+        auth_type: Some(auth.auth_type.clone()),
+        realm: auth.realm.clone(),
+        tls: account.tls.clone(),
+        dtls: account.dtls.clone(),
     };
+    let rows: PjsipAccountRows = build_pjsip_account_rows(&account.id, &new_account, auth);
+    exec_update_pjsip_endpoint_transport(transaction, &rows.endpoint)
+        .await
+        .map(|_| ())
+        .map_err(update_error)
+}
 
-    // ps_auth
-    let ps_auth: PsAuthForUdp = PsAuthForUdp {
-        id: new_account_id.clone(),
-        auth_type: AuthType::Userpass, // ここは必ず AuthType の値
-        username: account.username.clone(),
-        password: account.password.clone(),
-    };
+// ps_* 行を組み立てて登録します。
+// create / 一括登録から利用します。
+async fn insert_pjsip_account_rows(
+    transaction: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    account_id: &str,
+    account: &PjsipRealtimeAccount,
+    auth: &AuthCredentials,
+) -> Result<StatusCode, RegistrationError> {
+    let rows: PjsipAccountRows = build_pjsip_account_rows(account_id, account, auth);
+    exec_insert_pjsip_account(transaction, &rows).await
+}
 
-    // ps_aor
-    let ps_aor: PsAorForUdp = PsAorForUdp {
-        id: new_account_id.clone(),
-        max_contacts: 5,
-        remove_existing: TurnOnOff::Yes,
-        remove_unavailable: TurnOnOff::Yes,
-        default_expiration: 60,
-        minimum_expiration: 60,
-        maximum_expiration: 90,
-        // qualify_frequency: 10,
-        // qualify_timeout: 9,
-        qualify_frequency: 0,  // Disable qualify by setting to 0
-        qualify_timeout: 0,    // Disable qualify by setting to 0
-    };
+// 作成時の認証情報を決定します。
+// auth_type / realm はリクエストの指定を優先し、未指定の場合はサーバー設定の既定値を使用します。
+// md5_cred は realm を含めて計算するため、md5 の場合は realm が必須です。
+// 作成時は validate_account で確認済みのため、エラーになるのは更新時のみです。
+fn resolve_auth_credentials(
+    config: &AppConfig,
+    auth_type: Option<&AuthType>,
+    realm: Option<&str>,
+    username: &str,
+    password: &str,
+) -> Result<AuthCredentials, ApiError> {
+    if realm.is_some_and(|realm| realm.trim().is_empty()) {
+        return Err(invalid_field("realm", "must not be empty"));
+    }
+    let realm: Option<&str> = realm.or(config.default_realm.as_deref());
 
-    // ps_endpoint
-    let ps_endpoint: PsEndpointForUdp = PsEndpointForUdp {
-        id: new_account_id.clone(),
-        transport: TransportType::Udp,
-        aors: new_account_id.clone(),
-        auth: new_account_id.clone(),
-        context: account.context.clone(),
-        disallow: String::from("all"),
-        allow: String::from("ulaw,opus"),
-        direct_media: TurnOnOff::No,
-        dtmf_mode: DtmfMode::Auto,
-        force_rport: TurnOnOff::Yes,
-        rewrite_contact: TurnOnOff::Yes,
-        rtp_ipv6: TurnOnOff::Yes,
-        rtp_symmetric: TurnOnOff::Yes,
-        media_encryption: MediaEncryption::No,
-        from_domain: account.from_domain.clone(),
-        from_user: account.from_user.clone(),
+    match auth_type.unwrap_or(&config.default_auth_type) {
+        AuthType::Userpass => Ok(AuthCredentials::userpass(password, realm)),
+        AuthType::Md5 => match realm {
+            Some(realm) => Ok(AuthCredentials::md5(username, realm, password)),
+            None => Err(invalid_field("realm", "is required for md5 credentials")),
+        },
+        AuthType::GoogleOauth => Err(invalid_field("auth_type", "must be userpass or md5")),
+    }
+}
+
+// 更新後の認証情報を決定します。
+// md5_cred は username / realm / password から計算するため、md5 のアカウントで
+// username / realm や認証方式を変更する場合は password の指定が必要です。
+fn resolve_updated_auth_credentials(
+    config: &AppConfig,
+    patch: &PjsipRealtimeAccountPatch,
+    current: &PjsipRealtimeAccountWithId,
+    current_auth: &AuthCredentials,
+    updated: &PjsipRealtimeAccountWithId,
+) -> Result<AuthCredentials, ApiError> {
+    let auth_type: &AuthType = patch.auth_type.as_ref().unwrap_or(&current_auth.auth_type);
+    let realm: Option<&str> = patch.realm.as_deref().or(current_auth.realm.as_deref());
+    if patch.password.is_none()
+        && *auth_type == current_auth.auth_type
+        && realm == current_auth.realm.as_deref()
+        && updated.username == current.username
+    {
+        return Ok(current_auth.clone());
+    }
+
+    let Some(password) = patch
+        .password
+        .as_deref()
+        .or(current_auth.password.as_deref())
+    else {
+        return Err(invalid_field(
+            "password",
+            "is required to recompute the md5 credentials",
+        ));
     };
+    resolve_auth_credentials(config, Some(auth_type), realm, &updated.username, password)
+}
+
+// 1 項目の検証エラー (validate_account と同じ 422 の形式で返します)
+fn invalid_field(field: &str, message: &str) -> ApiError {
+    let mut errors = ValidationErrors::new();
+    errors.add(field, message);
+    errors.into()
+}
+
+fn update_error(e: UpdateError) -> ApiError {
+    ApiError::from(e).context("Failed to update account")
+}
+
+// 一括登録
+// atomic: すべての項目を検証してから 1 トランザクションで登録します (201 / 失敗時はエラー)
+// best_effort: 項目ごとにトランザクションを分けて登録し、結果をまとめて返します (207)
+pub async fn create_pjsip_accounts_bulk(
+    state: State<AppState>,
+    accounts: Vec<BulkPjsipRealtimeAccount>,
+    mode: BulkCreateMode,
+) -> Result<(StatusCode, BulkCreateResponse), ApiError> {
+    if accounts.is_empty() || accounts.len() > MAX_BULK_ACCOUNTS {
+        let error_message = format!(
+            "accounts must contain between 1 and {} items",
+            MAX_BULK_ACCOUNTS
+        );
+        return Err(ApiError::new(ErrorCode::ValidationFailed, error_message));
+    }
+    match mode {
+        BulkCreateMode::Atomic => create_pjsip_accounts_atomic(state, accounts).await,
+        BulkCreateMode::BestEffort => create_pjsip_accounts_best_effort(state, accounts).await,
+    }
+}
+
+async fn create_pjsip_accounts_atomic(
+    state: State<AppState>,
+    accounts: Vec<BulkPjsipRealtimeAccount>,
+) -> Result<(StatusCode, BulkCreateResponse), ApiError> {
+    // 登録前にすべての項目の検証エラーをまとめて返します
+    let mut errors = ValidationErrors::new();
+    for (index, item) in accounts.iter().enumerate() {
+        if let Err(e) = validate_account(&state.config, item.id.as_deref(), &item.account) {
+            errors.append_prefixed(&format!("[{}]", index), e);
+        }
+    }
+    errors.into_result()?;
 
-    // register account in database
     let mut transaction: sqlx::Transaction<'static, sqlx::Postgres> =
-        state.pjsip_db.begin().await.map_err(|e: sqlx::Error| {
-            let error_message: String = format!("Failed to begin transaction: {}", e);
-            let value: Value = serde_json::json!({ "error": error_message });
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(value))
-        })?;
-
-    let result: Result<StatusCode, RegistrationError> = exec_insert_udp_pjsip_account(
-        &mut transaction,
-        &pjsip_account,
-        &ps_auth,
-        &ps_aor,
-        &ps_endpoint,
-    )
-    .await;
-    match result {
-        Ok(_) => {
-            transaction.commit().await.map_err(|e| {
-                let error_message = format!("Failed to commit transaction: {}", e);
-                let value: Value = serde_json::json!({ "error": error_message });
-                (StatusCode::INTERNAL_SERVER_ERROR, Json(value))
-            })?;
-            let value: Value = serde_json::json!({"id": new_account_id});
-            Ok((StatusCode::CREATED, Json(value)))
+        state.pjsip_db.begin().await?;
+    let mut results: Vec<BulkCreateResult> = Vec::with_capacity(accounts.len());
+    for (index, item) in accounts.into_iter().enumerate() {
+        match insert_pjsip_account(&mut transaction, &state.config, item.id, &item.account).await {
+            Ok(account_id) => results.push(BulkCreateResult {
+                index,
+                status: StatusCode::CREATED.as_u16(),
+                username: item.account.username,
+                id: Some(account_id),
+                error: None,
+            }),
+            Err(e) => {
+                let _ = transaction.rollback().await;
+                let context = format!("Failed to create account at index {}", index);
+                return Err(e.context(&context).with_extension("index", index));
+            }
         }
-        Err(e) => {
-            let _ = transaction.rollback().await;
-            let error_message = format!("Failed to create UDP account: {}", e);
-            let value: Value = serde_json::json!({ "error": error_message });
-            match e {
-                RegistrationError::DuplicateError => {
-                    // Handle duplicate error specifically
-                    Err((StatusCode::CONFLICT, Json(value)))
-                }
-                _ => {
-                    // Handle other errors {
-                    tracing::error!("Failed to create UDP account: {}", e);
-                    Err((StatusCode::INTERNAL_SERVER_ERROR, Json(value)))
+    }
+    transaction.commit().await?;
+    Ok((
+        StatusCode::CREATED,
+        BulkCreateResponse::new(BulkCreateMode::Atomic, results),
+    ))
+}
+
+async fn create_pjsip_accounts_best_effort(
+    state: State<AppState>,
+    accounts: Vec<BulkPjsipRealtimeAccount>,
+) -> Result<(StatusCode, BulkCreateResponse), ApiError> {
+    let mut results: Vec<BulkCreateResult> = Vec::with_capacity(accounts.len());
+    for (index, item) in accounts.into_iter().enumerate() {
+        // 項目ごとにトランザクションを分けます
+        let result: Result<String, ApiError> =
+            create_pjsip_account(state.clone(), item.id.clone(), &item.account).await;
+        results.push(match result {
+            Ok(account_id) => BulkCreateResult {
+                index,
+                status: StatusCode::CREATED.as_u16(),
+                username: item.account.username,
+                id: Some(account_id),
+                error: None,
+            },
+            Err(e) => {
+                let (status, problem) = e.into_problem();
+                BulkCreateResult {
+                    index,
+                    status: status.as_u16(),
+                    username: item.account.username,
+                    id: None,
+                    error: Some(problem),
                 }
             }
+        });
+    }
+    Ok((
+        StatusCode::MULTI_STATUS,
+        BulkCreateResponse::new(BulkCreateMode::BestEffort, results),
+    ))
+}
+
+// アカウントのエクスポート (CSV / pjsip.conf)
+// ids を指定した場合はそのアカウントのみ出力します (存在しない ID は無視します)。
+// パスワードは出力しないため、再インポートする場合は password 列を記入してください。
+// include_secrets の場合は、credentials の参照と同じ reveal トークンを確認して
+// pjsip.conf に password / md5_cred を出力します。
+pub async fn export_pjsip_accounts(
+    state: State<AppState>,
+    format: ExportFormat,
+    ids: Option<Vec<String>>,
+    include_secrets: bool,
+    reveal_token: Option<&str>,
+    requested_by: Option<&str>,
+) -> Result<Vec<u8>, ApiError> {
+    let export_error = |e| ApiError::from(e).context("Failed to export accounts");
+    match format {
+        ExportFormat::Csv if include_secrets => Err(ApiError::new(
+            ErrorCode::ValidationFailed,
+            "include_secrets is only supported for format=pjsip_conf",
+        )),
+        ExportFormat::Csv => {
+            let accounts = get_pjsip_accounts_for_export(&state.pjsip_db, ids.as_deref())
+                .await
+                .map_err(export_error)?;
+            let csv_error = |e: String| ApiError::new(ErrorCode::InternalError, e);
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer
+                .write_record(CSV_COLUMNS)
+                .map_err(|e| csv_error(e.to_string()))?;
+            for account in &accounts {
+                writer
+                    .write_record(account.to_csv_record())
+                    .map_err(|e| csv_error(e.to_string()))?;
+            }
+            writer.into_inner().map_err(|e| csv_error(e.to_string()))
+        }
+        ExportFormat::PjsipConf => {
+            let secrets = if include_secrets {
+                check_reveal_token(&state.config, reveal_token)?;
+                let secrets = get_pjsip_accounts_credentials(&state.pjsip_db, ids.as_deref())
+                    .await
+                    .map_err(export_error)?;
+                tracing::warn!(
+                    "Exported credentials of {} accounts (requested by {})",
+                    secrets.len(),
+                    requested_by.unwrap_or("anonymous")
+                );
+                Some(secrets)
+            } else {
+                None
+            };
+            let accounts = get_pjsip_account_details(&state.pjsip_db, ids.as_deref())
+                .await
+                .map_err(export_error)?;
+            Ok(render_pjsip_conf(&accounts, secrets.as_deref()).into_bytes())
         }
     }
 }
 
-pub async fn delete_pjsip_account(
+// 1 アカウント分の pjsip.conf (endpoint / auth / aor セクション)
+// include_secrets の扱いは export_pjsip_accounts と同じです。
+pub async fn export_pjsip_account_conf(
     state: State<AppState>,
     account_id: String,
-) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
-    // repository delete
-    let mut transaction = state.pjsip_db.begin().await.map_err(|e| {
-        let error_message = format!("Failed to begin transaction: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({ "error": error_message })),
-        )
-    })?;
+    include_secrets: bool,
+    reveal_token: Option<&str>,
+    requested_by: Option<&str>,
+) -> Result<String, ApiError> {
+    let export_error = |e| ApiError::from(e).context("Failed to export account");
+    let account = get_pjsip_account_by_id(&state.pjsip_db, &account_id)
+        .await
+        .map_err(export_error)?;
+    let secret = if include_secrets {
+        check_reveal_token(&state.config, reveal_token)?;
+        let secret = get_pjsip_account_credentials(&state.pjsip_db, &account_id)
+            .await
+            .map_err(export_error)?;
+        tracing::warn!(
+            "Exported credentials of account {} (requested by {})",
+            account_id,
+            requested_by.unwrap_or("anonymous")
+        );
+        Some(secret)
+    } else {
+        None
+    };
+    Ok(render_pjsip_conf_account(&account, secret.as_ref()))
+}
 
-    let result = exec_delete_pjsip_account(&mut transaction, account_id).await;
-    match result {
-        Ok(_) => {
-            transaction.commit().await.map_err(|e| {
-                let error_message = format!("Failed to commit transaction: {}", e);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(serde_json::json!({ "error": error_message })),
-                )
-            })?;
-            Ok(StatusCode::NO_CONTENT)
+// CSV インポート
+// すべての行を検証し、ファイル内・登録済みアカウントとの重複を確認してから
+// atomic の一括登録と同じく 1 トランザクションで登録します。
+// dry_run の場合は検証結果のみ返し、登録しません。
+pub async fn import_pjsip_accounts(
+    state: State<AppState>,
+    data: &[u8],
+    dry_run: bool,
+) -> Result<(StatusCode, ImportReport), ApiError> {
+    let data: &[u8] = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(data);
+    let headers: csv::StringRecord = reader
+        .headers()
+        .map_err(|e| ApiError::new(ErrorCode::InvalidRequestBody, e.to_string()))?
+        .clone();
+    check_csv_headers(&headers).map_err(|e| ApiError::new(ErrorCode::ValidationFailed, e))?;
+
+    // 行番号はヘッダーを 1 行目とした CSV の行番号です
+    let mut rows: Vec<(usize, Option<BulkPjsipRealtimeAccount>)> = Vec::new();
+    let mut errors: Vec<CsvRowError> = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        if rows.len() >= MAX_BULK_ACCOUNTS {
+            let error_message = format!("CSV must contain at most {} rows", MAX_BULK_ACCOUNTS);
+            return Err(ApiError::new(ErrorCode::ValidationFailed, error_message));
         }
-        Err(e) => {
-            let _ = transaction.rollback().await;
-            let error_message = format!("Failed to delete account: {}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({ "error": error_message })),
-            ))
+        let record: csv::StringRecord = match record {
+            Ok(record) => record,
+            Err(e) => {
+                let row: usize = e.position().map_or(index + 2, |p| p.line() as usize);
+                errors.push(CsvRowError {
+                    row,
+                    field: "row".to_string(),
+                    message: e.to_string(),
+                });
+                rows.push((row, None));
+                continue;
+            }
+        };
+        let row: usize = record.position().map_or(index + 2, |p| p.line() as usize);
+        let item = parse_csv_record(&headers, &record).and_then(|item| {
+            validate_account(&state.config, item.id.as_deref(), &item.account)?;
+            Ok(item)
+        });
+        match item {
+            Ok(item) => rows.push((row, Some(item))),
+            Err(e) => {
+                errors.extend(e.errors().iter().cloned().map(|e| CsvRowError::new(row, e)));
+                rows.push((row, None));
+            }
         }
     }
+    if rows.is_empty() {
+        return Err(ApiError::new(
+            ErrorCode::ValidationFailed,
+            "CSV must contain at least one row",
+        ));
+    }
+    errors.extend(find_duplicate_rows(&state, &rows).await?);
+    errors.sort_by_key(|e| e.row);
+
+    let invalid_rows: HashSet<usize> = errors.iter().map(|e| e.row).collect();
+    let report = ImportReport {
+        dry_run,
+        total: rows.len(),
+        valid: rows.len() - invalid_rows.len(),
+        invalid: invalid_rows.len(),
+        errors,
+        created: Vec::new(),
+    };
+    if dry_run {
+        return Ok((StatusCode::OK, report));
+    }
+    if !report.errors.is_empty() {
+        return Err(
+            ApiError::new(ErrorCode::InvalidFields, "One or more rows are invalid")
+                .with_extension("errors", report.errors),
+        );
+    }
+
+    let (row_numbers, accounts): (Vec<usize>, Vec<BulkPjsipRealtimeAccount>) = rows
+        .into_iter()
+        .filter_map(|(row, item)| item.map(|item| (row, item)))
+        .unzip();
+    let (_, response) = create_pjsip_accounts_bulk(state, accounts, BulkCreateMode::Atomic).await?;
+    let created: Vec<ImportedAccount> = response
+        .results
+        .into_iter()
+        .filter_map(|result| {
+            Some(ImportedAccount {
+                row: row_numbers[result.index],
+                id: result.id?,
+                username: result.username,
+            })
+        })
+        .collect();
+    Ok((StatusCode::CREATED, ImportReport { created, ..report }))
 }
 
-pub async fn get_accounts(_: State<AppState>) -> impl IntoResponse {
-    // Here you would typically query the database using `state.pjsip_db`
-    let users = vec!["user1", "user2", "user3"];
-    (StatusCode::OK, Json(users))
+// ファイル内の重複と登録済みアカウントとの重複 (id / username) を確認します。
+async fn find_duplicate_rows(
+    state: &State<AppState>,
+    rows: &[(usize, Option<BulkPjsipRealtimeAccount>)],
+) -> Result<Vec<CsvRowError>, ApiError> {
+    let items: Vec<(usize, &BulkPjsipRealtimeAccount)> = rows
+        .iter()
+        .filter_map(|(row, item)| item.as_ref().map(|item| (*row, item)))
+        .collect();
+    let ids: Vec<String> = items.iter().filter_map(|(_, i)| i.id.clone()).collect();
+    let usernames: Vec<String> = items
+        .iter()
+        .map(|(_, i)| i.account.username.clone())
+        .collect();
+    let existing: Vec<(String, String)> =
+        get_existing_account_keys(&state.pjsip_db, &ids, &usernames)
+            .await
+            .map_err(|e| ApiError::from(e).context("Failed to check existing accounts"))?;
+    let existing_ids: HashSet<&str> = existing.iter().map(|(id, _)| id.as_str()).collect();
+    let existing_usernames: HashSet<&str> = existing.iter().map(|(_, u)| u.as_str()).collect();
+
+    let mut errors: Vec<CsvRowError> = Vec::new();
+    let mut seen_ids: HashMap<&str, usize> = HashMap::new();
+    let mut seen_usernames: HashMap<&str, usize> = HashMap::new();
+    for (row, item) in items {
+        if let Some(id) = item.id.as_deref() {
+            if existing_ids.contains(id) {
+                errors.push(duplicate_row_error(row, "id", None));
+            } else if let Some(first) = seen_ids.get(id) {
+                errors.push(duplicate_row_error(row, "id", Some(*first)));
+            } else {
+                seen_ids.insert(id, row);
+            }
+        }
+        let username: &str = &item.account.username;
+        if existing_usernames.contains(username) {
+            errors.push(duplicate_row_error(row, "username", None));
+        } else if let Some(first) = seen_usernames.get(username) {
+            errors.push(duplicate_row_error(row, "username", Some(*first)));
+        } else {
+            seen_usernames.insert(username, row);
+        }
+    }
+    Ok(errors)
 }
 
-pub async fn get_pjsip_accounts(
-    state: State<AppState>,
-) -> Result<Vec<PjsipRealtimeAccountWithId>, sqlx::Error> {
-    get_all_pjsip_accounts(&state.pjsip_db).await
+fn duplicate_row_error(row: usize, field: &str, first_row: Option<usize>) -> CsvRowError {
+    let message: String = match first_row {
+        Some(first_row) => format!("duplicates the {} in row {}", field, first_row),
+        None => format!("an account with this {} already exists", field),
+    };
+    CsvRowError {
+        row,
+        field: field.to_string(),
+        message,
+    }
 }
 
-pub async fn create_ws_pjsip_account(
+// pjsip.conf の取り込み
+// エンドポイントごとに検証と重複の確認を行い、すべて問題がなければ 1 トランザクションで登録します。
+// dry_run の場合は検証結果のみ返し、登録しません。
+pub async fn import_pjsip_conf(
     state: State<AppState>,
-    account_id: Option<String>,
-    account: &PjsipRealtimeAccount,
-) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
-    println!("TODO create_ws_account validation here.");
+    text: &str,
+    params: &PjsipConfImportParams,
+) -> Result<(StatusCode, PjsipConfImportReport), ApiError> {
+    let conf: PjsipConf = read_pjsip_conf(text, params.from_domain.as_deref());
+    if conf.endpoint_sections == 0 {
+        return Err(ApiError::new(
+            ErrorCode::ValidationFailed,
+            "pjsip.conf contains no type=endpoint sections",
+        ));
+    }
+    if conf.endpoint_sections > MAX_BULK_ACCOUNTS {
+        let error_message = format!(
+            "pjsip.conf must contain at most {} endpoints",
+            MAX_BULK_ACCOUNTS
+        );
+        return Err(ApiError::new(ErrorCode::ValidationFailed, error_message));
+    }
 
-    // validation
+    let mut errors: Vec<PjsipConfIssue> = conf.errors;
+    let mut invalid_endpoints: HashSet<String> = HashSet::new();
+    for endpoint in &conf.endpoints {
+        // エンドポイント名をそのままアカウント ID にします (ULID でなくても可)
+        let mut endpoint_errors: Vec<PjsipConfIssue> = Vec::new();
+        if endpoint.id.len() > 255 || endpoint.id.chars().any(|c| c.is_whitespace()) {
+            endpoint_errors.push(endpoint_issue(
+                endpoint,
+                "id",
+                "section name must be at most 255 characters without whitespace".to_string(),
+            ));
+        }
+        if let Err(e) = validate_imported_account(
+            &state.config,
+            &endpoint.account,
+            endpoint.md5_cred.is_some(),
+        ) {
+            endpoint_errors.extend(
+                e.errors()
+                    .iter()
+                    .map(|e| endpoint_issue(endpoint, &e.field, e.message.clone())),
+            );
+        }
+        if !endpoint_errors.is_empty() {
+            invalid_endpoints.insert(endpoint.id.clone());
+            errors.extend(endpoint_errors);
+        }
+    }
+    for issue in find_duplicate_endpoints(&state, &conf.endpoints).await? {
+        invalid_endpoints.insert(issue.section.clone().unwrap_or_default());
+        errors.push(issue);
+    }
+    errors.sort_by_key(|e| e.line);
+
+    let valid: usize = conf.endpoints.len() - invalid_endpoints.len();
+    let report = PjsipConfImportReport {
+        dry_run: params.dry_run,
+        endpoints: conf.endpoint_sections,
+        valid,
+        invalid: conf.endpoint_sections - valid,
+        errors,
+        unsupported_options: conf.unsupported_options,
+        skipped_sections: conf.skipped_sections,
+        created: Vec::new(),
+    };
+    if params.dry_run {
+        return Ok((StatusCode::OK, report));
+    }
+    if !report.errors.is_empty() {
+        return Err(ApiError::new(
+            ErrorCode::InvalidFields,
+            "pjsip.conf contains invalid sections",
+        )
+        .with_extension("errors", report.errors));
+    }
+
+    let mut transaction: sqlx::Transaction<'static, sqlx::Postgres> =
+        state.pjsip_db.begin().await?;
+    let mut created: Vec<PjsipConfImportedEndpoint> = Vec::with_capacity(conf.endpoints.len());
+    for endpoint in conf.endpoints {
+        if let Err(e) = insert_pjsip_conf_endpoint(&mut transaction, &state.config, &endpoint).await
+        {
+            let _ = transaction.rollback().await;
+            let context = format!("Failed to import endpoint {}", endpoint.id);
+            return Err(e.context(&context).with_extension("section", endpoint.id));
+        }
+        created.push(PjsipConfImportedEndpoint {
+            id: endpoint.id,
+            username: endpoint.account.username,
+            transport: endpoint.account.transport,
+        });
+    }
+    transaction.commit().await?;
+    Ok((
+        StatusCode::CREATED,
+        PjsipConfImportReport { created, ..report },
+    ))
+}
+
+fn endpoint_issue(endpoint: &PjsipConfEndpoint, field: &str, message: String) -> PjsipConfIssue {
+    PjsipConfIssue {
+        section: Some(endpoint.id.clone()),
+        line: endpoint.line,
+        option: Some(field.to_string()),
+        message,
+    }
+}
+
+// ファイル内の重複と登録済みアカウントとの重複 (id / username) を確認します。
+async fn find_duplicate_endpoints(
+    state: &State<AppState>,
+    endpoints: &[PjsipConfEndpoint],
+) -> Result<Vec<PjsipConfIssue>, ApiError> {
+    let ids: Vec<String> = endpoints.iter().map(|e| e.id.clone()).collect();
+    let usernames: Vec<String> = endpoints
+        .iter()
+        .map(|e| e.account.username.clone())
+        .collect();
+    let existing: Vec<(String, String)> =
+        get_existing_account_keys(&state.pjsip_db, &ids, &usernames)
+            .await
+            .map_err(|e| ApiError::from(e).context("Failed to check existing accounts"))?;
+    let existing_ids: HashSet<&str> = existing.iter().map(|(id, _)| id.as_str()).collect();
+    let existing_usernames: HashSet<&str> = existing.iter().map(|(_, u)| u.as_str()).collect();
+
+    let mut errors: Vec<PjsipConfIssue> = Vec::new();
+    let mut seen_ids: HashSet<&str> = HashSet::new();
+    let mut seen_usernames: HashMap<&str, &str> = HashMap::new();
+    for endpoint in endpoints {
+        if existing_ids.contains(endpoint.id.as_str()) {
+            let message = "an account with this id already exists".to_string();
+            errors.push(endpoint_issue(endpoint, "id", message));
+        } else if !seen_ids.insert(endpoint.id.as_str()) {
+            let message = "endpoint is defined more than once".to_string();
+            errors.push(endpoint_issue(endpoint, "id", message));
+        }
+        let username: &str = &endpoint.account.username;
+        if existing_usernames.contains(username) {
+            let message = "an account with this username already exists".to_string();
+            errors.push(endpoint_issue(endpoint, "username", message));
+        } else if let Some(first) = seen_usernames.get(username) {
+            let message = format!("duplicates the username of endpoint {}", first);
+            errors.push(endpoint_issue(endpoint, "username", message));
+        } else {
+            seen_usernames.insert(username, endpoint.id.as_str());
+        }
+    }
+    Ok(errors)
+}
 
-    // common
-    // If account_id is provided, use it; otherwise, generate a new ULID
-    let new_account_id: String = match account_id {
-        Some(id) => id,
-        None => Ulid::new().to_string(),
+// ps_* 行を組み立て、pjsip.conf のオプションで既定値を上書きして登録します。
+async fn insert_pjsip_conf_endpoint(
+    transaction: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    config: &AppConfig,
+    endpoint: &PjsipConfEndpoint,
+) -> Result<(), ApiError> {
+    let account: &PjsipRealtimeAccount = &endpoint.account;
+    let credentials: AuthCredentials = match &endpoint.md5_cred {
+        // md5_cred は username / realm から計算済みのため、realm はそのまま使います
+        Some(md5_cred) => match account.realm.as_deref().or(config.default_realm.as_deref()) {
+            Some(realm) => AuthCredentials::md5_from_cred(md5_cred, realm),
+            None => return Err(invalid_field("realm", "is required for md5 credentials")),
+        },
+        None => resolve_auth_credentials(
+            config,
+            account.auth_type.as_ref(),
+            account.realm.as_deref(),
+            &account.username,
+            &account.password,
+        )?,
     };
+    let mut rows: PjsipAccountRows = build_pjsip_account_rows(&endpoint.id, account, &credentials);
+    endpoint.overrides.apply_to(&mut rows);
+    exec_insert_pjsip_account(transaction, &rows).await?;
+    Ok(())
+}
 
+// 既存のトランザクション内で 1 件のアカウントを登録し、登録した ID を返します。
+// 項目の検証 (validate_account) は呼び出し元で行います。
+async fn insert_pjsip_account(
+    transaction: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    config: &AppConfig,
+    account_id: Option<String>,
+    account: &PjsipRealtimeAccount,
+) -> Result<String, ApiError> {
+    let new_account_id: String = account_id.unwrap_or_else(|| Ulid::new().to_string());
+    let credentials: AuthCredentials = resolve_auth_credentials(
+        config,
+        account.auth_type.as_ref(),
+        account.realm.as_deref(),
+        &account.username,
+        &account.password,
+    )?;
+    insert_pjsip_account_rows(transaction, &new_account_id, account, &credentials).await?;
+    Ok(new_account_id)
+}
+
+// ps_* row builder
+// 共通の既定値で行を組み立て、transport ごとの既定値とオプションで上書きします。
+// create と transport 変更を伴う update の両方から利用します。
+fn build_pjsip_account_rows(
+    account_id: &str,
+    account: &PjsipRealtimeAccount,
+    credentials: &AuthCredentials,
+) -> PjsipAccountRows {
     let pjsip_account: PjsipRealtimeAccountWithId = PjsipRealtimeAccountWithId {
-        id: new_account_id.clone(),
+        id: account_id.to_string(),
         username: account.username.clone(),
-        password: account.password.clone(),
+        password: credentials.password.clone(), // md5 の場合は平文を保存しない
         context: account.context.clone(),
         transport: account.transport.clone(),
         from_domain: account.from_domain.clone(),
         from_user: account.from_user.clone(),
         rtp_timeout: account.rtp_timeout,
         rtp_timeout_hold: account.rtp_timeout_hold,
+        tls: account.tls.clone(),
+        dtls: account.dtls.clone(),
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
     };
 
     // ps_auth
-    let ps_auth: PsAuthForWs = PsAuthForWs {
-        id: new_account_id.clone(),
-        auth_type: AuthType::Userpass,
+    let auth: PsAuthForAccount = PsAuthForAccount {
+        id: account_id.to_string(),
+        auth_type: credentials.auth_type.clone(),
+        md5_cred: credentials.md5_cred.clone(),
+        password: credentials.password.clone(),
+        realm: credentials.realm.clone(),
         username: account.username.clone(),
-        password: account.password.clone(),
     };
 
-    // ps_aor - WebSocket specific settings
-    let ps_aor: PsAorForWs = PsAorForWs {
-        id: new_account_id.clone(),
-        max_contacts: 1, // WebSocket typically uses 1 contact
+    // ps_aor
+    let mut aor: PsAorForAccount = PsAorForAccount {
+        id: account_id.to_string(),
+        max_contacts: 5,
         remove_existing: TurnOnOff::Yes,
         remove_unavailable: TurnOnOff::Yes,
-        default_expiration: 3600, // 1 hour for WebSocket
+        default_expiration: 60, // short re-registration keeps the NAT binding open
         minimum_expiration: 60,
-        maximum_expiration: 7200,
+        maximum_expiration: 90,
+        qualify_frequency: Some(0), // Disable qualify by setting to 0
+        qualify_timeout: Some(0),   // Disable qualify by setting to 0
     };
 
-    // ps_endpoint - WebSocket specific settings
-    let ps_endpoint: PsEndpointForWs = PsEndpointForWs {
-        id: new_account_id.clone(),
-        transport: TransportType::Ws,
-        aors: new_account_id.clone(),
-        auth: new_account_id.clone(),
+    // ps_endpoint
+    let mut endpoint: PsEndpointForAccount = PsEndpointForAccount {
+        id: account_id.to_string(),
+        transport: account.transport.to_string(),
+        aors: account_id.to_string(),
+        auth: account_id.to_string(),
         context: account.context.clone(),
         disallow: String::from("all"),
-        allow: String::from("opus,ulaw,alaw,vp8,h264"), // WebSocket typically supports these codecs
+        allow: String::from("ulaw,opus"),
         direct_media: TurnOnOff::No,
-        dtmf_mode: DtmfMode::Rfc4733,
+        dtmf_mode: DtmfMode::Auto,
         force_rport: TurnOnOff::Yes,
         rewrite_contact: TurnOnOff::Yes,
         rtp_ipv6: TurnOnOff::Yes,
         rtp_symmetric: TurnOnOff::Yes,
-        media_encryption: MediaEncryption::Dtls,
+        media_encryption: MediaEncryption::No,
+        media_encryption_optimistic: None,
         from_domain: account.from_domain.clone(),
         from_user: account.from_user.clone(),
-        rtp_timeout: Some(account.rtp_timeout.unwrap_or(RtpTimeout::Thirty)),        // Use provided value or default to 30 seconds
-        rtp_timeout_hold: Some(account.rtp_timeout_hold.unwrap_or(RtpTimeout::ThreeHundred)),  // Use provided value or default to 300 seconds
-        ice_support: Some(TurnOnOff::Yes), // ICE is typically required for WebRTC
-        use_avpf: Some(TurnOnOff::Yes),    // AVPF is recommended for WebRTC
-        webrtc: Some(TurnOnOff::Yes),      // Enable WebRTC
-        max_audio_streams: Some(1),
-        max_video_streams: Some(1),
+        rtp_timeout: Some(account.rtp_timeout.unwrap_or(RtpTimeout::Thirty)), // Use provided value or default to 30 seconds
+        rtp_timeout_hold: Some(account.rtp_timeout_hold.unwrap_or(RtpTimeout::ThreeHundred)), // Use provided value or default to 300 seconds
+        ice_support: None,
+        use_avpf: None,
+        webrtc: None,
+        max_audio_streams: None,
+        max_video_streams: None,
+        dtls_verify: None,
+        dtls_setup: None,
+        dtls_fingerprint: None,
+        dtls_auto_generate_cert: None,
+        rtcp_mux: None,
+        bundle: None,
     };
 
-    // register account in database
-    let mut transaction: sqlx::Transaction<'static, sqlx::Postgres> =
-        state.pjsip_db.begin().await.map_err(|e: sqlx::Error| {
-            let error_message: String = format!("Failed to begin transaction: {}", e);
-            let value: Value = serde_json::json!({ "error": error_message });
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(value))
-        })?;
-
-    let result: Result<StatusCode, RegistrationError> = exec_insert_ws_pjsip_account(
-        &mut transaction,
-        &pjsip_account,
-        &ps_auth,
-        &ps_aor,
-        &ps_endpoint,
-    )
-    .await;
-    match result {
-        Ok(_) => {
-            transaction.commit().await.map_err(|e| {
-                let error_message = format!("Failed to commit transaction: {}", e);
-                let value: Value = serde_json::json!({ "error": error_message });
-                (StatusCode::INTERNAL_SERVER_ERROR, Json(value))
-            })?;
-            let value: Value = serde_json::json!({"id": new_account_id});
-            Ok((StatusCode::CREATED, Json(value)))
+    match account.transport {
+        TransportType::Udp => {}
+        TransportType::Tcp => {
+            aor.max_contacts = 1; // one contact per TCP connection
         }
-        Err(e) => {
-            let _ = transaction.rollback().await;
-            let error_message = format!("Failed to create WS account: {}", e);
-            let value: Value = serde_json::json!({ "error": error_message });
-            match e {
-                RegistrationError::DuplicateError => {
-                    // Handle duplicate error specifically
-                    Err((StatusCode::CONFLICT, Json(value)))
-                }
-                _ => {
-                    // Handle other errors
-                    tracing::error!("Failed to create WS account: {}", e);
-                    Err((StatusCode::INTERNAL_SERVER_ERROR, Json(value)))
-                }
-            }
+        TransportType::Tls => {
+            aor.max_contacts = 1; // one contact per TLS connection
+            let tls_options: TlsAccountOptions = account.tls.clone().unwrap_or_default();
+            endpoint.transport = tls_options
+                .transport_name
+                .unwrap_or_else(|| String::from(DEFAULT_TLS_TRANSPORT_NAME));
+            endpoint.media_encryption = tls_options
+                .media_encryption
+                .unwrap_or(MediaEncryption::Sdes); // SRTP (SDES) by default
+            endpoint.media_encryption_optimistic = tls_options.media_encryption_optimistic;
         }
+        TransportType::Ws | TransportType::Wss => {
+            // ps_aor - WebSocket specific settings (qualify は登録しません)
+            aor.max_contacts = 1; // WebSocket typically uses 1 contact
+            aor.default_expiration = 3600; // 1 hour for WebSocket
+            aor.maximum_expiration = 7200;
+            aor.qualify_frequency = None;
+            aor.qualify_timeout = None;
+
+            // ps_endpoint - WebSocket specific settings
+            let dtls_options: DtlsAccountOptions = account.dtls.clone().unwrap_or_default();
+            endpoint.allow = String::from("opus,ulaw,alaw,vp8,h264"); // WebSocket typically supports these codecs
+            endpoint.dtmf_mode = DtmfMode::Rfc4733;
+            endpoint.media_encryption = MediaEncryption::Dtls;
+            endpoint.ice_support = Some(TurnOnOff::Yes); // ICE is typically required for WebRTC
+            endpoint.use_avpf = Some(TurnOnOff::Yes); // AVPF is recommended for WebRTC
+            endpoint.webrtc = Some(TurnOnOff::Yes); // Enable WebRTC
+            endpoint.max_audio_streams = Some(1);
+            endpoint.max_video_streams = Some(1);
+            // DTLS-SRTP settings for WebRTC clients
+            endpoint.dtls_verify = Some(
+                dtls_options
+                    .dtls_verify
+                    .unwrap_or_else(|| String::from("fingerprint")),
+            );
+            endpoint.dtls_setup = Some(dtls_options.dtls_setup.unwrap_or(DtlsSetup::Actpass));
+            endpoint.dtls_fingerprint = Some(
+                dtls_options
+                    .dtls_fingerprint
+                    .unwrap_or(DtlsFingerprint::Sha256),
+            );
+            endpoint.dtls_auto_generate_cert = Some(
+                dtls_options
+                    .dtls_auto_generate_cert
+                    .unwrap_or(TurnOnOff::Yes),
+            );
+            endpoint.rtcp_mux = Some(dtls_options.rtcp_mux.unwrap_or(TurnOnOff::Yes));
+            endpoint.bundle = Some(dtls_options.bundle.unwrap_or(TurnOnOff::Yes));
+        }
+    }
+
+    PjsipAccountRows {
+        account: pjsip_account,
+        auth,
+        aor,
+        endpoint,
     }
 }
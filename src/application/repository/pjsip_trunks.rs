@@ -0,0 +1,197 @@
+use crate::AppState;
+use crate::application::repository::pjsip_identifies::check_match_overlaps;
+use crate::infrastructure::models::errors::api_error::ApiError;
+use crate::infrastructure::models::pjsip_realtime::{
+    enums::{
+        pjsip_auth_enums::AuthType,
+        pjsip_endpoint_enums::{DtmfMode, TransportType},
+        pjsip_realtime_common_enums::TurnOnOff,
+    },
+    sip_identify::PsEndpointIdIp,
+    sip_tls::DEFAULT_TLS_TRANSPORT_NAME,
+    sip_trunk::{
+        DEFAULT_TRUNK_EXPIRATION, DEFAULT_TRUNK_FORBIDDEN_RETRY_INTERVAL,
+        DEFAULT_TRUNK_MAX_RETRIES, DEFAULT_TRUNK_QUALIFY_FREQUENCY, DEFAULT_TRUNK_RETRY_INTERVAL,
+        PjsipRealtimeTrunk, PjsipRealtimeTrunkResponse, PjsipTrunkRows, PsAorForTrunk,
+        PsAuthForTrunk, PsEndpointForTrunk, PsRegistrationForTrunk,
+    },
+    trunk_validation::{parse_sip_uri, validate_trunk},
+};
+use crate::infrastructure::repository::pjsip_trunk_repository::{
+    exec_delete_pjsip_trunk, exec_insert_pjsip_trunk, exec_update_pjsip_trunk,
+    get_pjsip_trunk_by_id, get_pjsip_trunks,
+};
+use axum::{extract::State, http::StatusCode};
+use ulid::{Generator, Ulid};
+
+pub async fn create_pjsip_trunk(
+    state: State<AppState>,
+    trunk: PjsipRealtimeTrunk,
+) -> Result<PjsipRealtimeTrunkResponse, ApiError> {
+    validate_trunk(&trunk)?;
+    let trunk_id: String = Ulid::new().to_string();
+    let rows: PjsipTrunkRows = build_pjsip_trunk_rows(&trunk_id, &trunk);
+
+    let mut transaction = state.pjsip_db.begin().await?;
+    if let Err(e) = check_match_overlaps(&mut transaction, &trunk_id, &trunk.matches).await {
+        let _ = transaction.rollback().await;
+        return Err(e);
+    }
+    match exec_insert_pjsip_trunk(&mut transaction, &trunk_id, &trunk, &rows).await {
+        Ok(_) => transaction.commit().await?,
+        Err(e) => {
+            let _ = transaction.rollback().await;
+            return Err(ApiError::from(e).context("Failed to create trunk"));
+        }
+    }
+    tracing::info!("Created trunk {} ({})", trunk_id, trunk.name);
+    get_pjsip_trunk(state, trunk_id).await
+}
+
+pub async fn list_pjsip_trunks(
+    state: State<AppState>,
+) -> Result<Vec<PjsipRealtimeTrunkResponse>, ApiError> {
+    get_pjsip_trunks(&state.pjsip_db, None)
+        .await
+        .map_err(|e| ApiError::from(e).context("Failed to get trunks"))
+}
+
+pub async fn get_pjsip_trunk(
+    state: State<AppState>,
+    trunk_id: String,
+) -> Result<PjsipRealtimeTrunkResponse, ApiError> {
+    get_pjsip_trunk_by_id(&state.pjsip_db, &trunk_id)
+        .await
+        .map_err(|e| ApiError::from(e).context("Failed to get trunk"))
+}
+
+// トランクの置き換え (PUT)
+// ps_* の行はリクエストの内容で登録し直します。created_at は維持します。
+pub async fn replace_pjsip_trunk(
+    state: State<AppState>,
+    trunk_id: String,
+    trunk: PjsipRealtimeTrunk,
+) -> Result<PjsipRealtimeTrunkResponse, ApiError> {
+    validate_trunk(&trunk)?;
+    let rows: PjsipTrunkRows = build_pjsip_trunk_rows(&trunk_id, &trunk);
+
+    let mut transaction = state.pjsip_db.begin().await?;
+    if let Err(e) = check_match_overlaps(&mut transaction, &trunk_id, &trunk.matches).await {
+        let _ = transaction.rollback().await;
+        return Err(e);
+    }
+    match exec_update_pjsip_trunk(&mut transaction, &trunk_id, &trunk, &rows).await {
+        Ok(_) => transaction.commit().await?,
+        Err(e) => {
+            let _ = transaction.rollback().await;
+            return Err(ApiError::from(e).context("Failed to update trunk"));
+        }
+    }
+    tracing::info!("Updated trunk {} ({})", trunk_id, trunk.name);
+    get_pjsip_trunk(state, trunk_id).await
+}
+
+pub async fn delete_pjsip_trunk(
+    state: State<AppState>,
+    trunk_id: String,
+) -> Result<StatusCode, ApiError> {
+    let mut transaction = state.pjsip_db.begin().await?;
+    match exec_delete_pjsip_trunk(&mut transaction, &trunk_id).await {
+        Ok(_) => {
+            transaction.commit().await?;
+            tracing::info!("Deleted trunk {}", trunk_id);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            let _ = transaction.rollback().await;
+            Err(ApiError::from(e).context("Failed to delete trunk"))
+        }
+    }
+}
+
+// 検証済みのリクエストから ps_* の行を作成します
+fn build_pjsip_trunk_rows(trunk_id: &str, trunk: &PjsipRealtimeTrunk) -> PjsipTrunkRows {
+    let transport_name: String = match trunk.transport {
+        TransportType::Tls => String::from(DEFAULT_TLS_TRANSPORT_NAME),
+        _ => trunk.transport.to_string(),
+    };
+    // client_uri を省略した場合は sip:{username}@{server_uri のホスト}
+    let client_uri: String = trunk.client_uri.clone().unwrap_or_else(|| {
+        let host: &str = parse_sip_uri(&trunk.server_uri).unwrap_or_default();
+        format!("sip:{}@{}", trunk.username, host)
+    });
+
+    let registration = PsRegistrationForTrunk {
+        id: trunk_id.to_string(),
+        auth_rejection_permanent: TurnOnOff::No,
+        client_uri,
+        contact_user: trunk.contact_user.clone(),
+        expiration: trunk.expiration.unwrap_or(DEFAULT_TRUNK_EXPIRATION),
+        max_retries: trunk.max_retries.unwrap_or(DEFAULT_TRUNK_MAX_RETRIES),
+        outbound_auth: trunk_id.to_string(),
+        outbound_proxy: trunk.outbound_proxy.clone(),
+        retry_interval: trunk.retry_interval.unwrap_or(DEFAULT_TRUNK_RETRY_INTERVAL),
+        forbidden_retry_interval: DEFAULT_TRUNK_FORBIDDEN_RETRY_INTERVAL,
+        server_uri: trunk.server_uri.clone(),
+        transport: transport_name.clone(),
+        line: TurnOnOff::Yes, // 着信をこのトランクのエンドポイントに紐付けます
+        endpoint: trunk_id.to_string(),
+    };
+
+    let auth = PsAuthForTrunk {
+        id: trunk_id.to_string(),
+        auth_type: AuthType::Userpass,
+        password: trunk.password.clone(),
+        realm: trunk.realm.clone(),
+        username: trunk.username.clone(),
+    };
+
+    let aor = PsAorForTrunk {
+        id: trunk_id.to_string(),
+        contact: trunk.server_uri.clone(),
+        max_contacts: 1,
+        qualify_frequency: DEFAULT_TRUNK_QUALIFY_FREQUENCY,
+    };
+
+    let endpoint = PsEndpointForTrunk {
+        id: trunk_id.to_string(),
+        transport: transport_name,
+        aors: trunk_id.to_string(),
+        outbound_auth: trunk_id.to_string(),
+        context: trunk.context.clone(),
+        disallow: String::from("all"),
+        allow: String::from("ulaw,alaw"),
+        direct_media: TurnOnOff::No,
+        dtmf_mode: DtmfMode::Rfc4733,
+        force_rport: TurnOnOff::Yes,
+        rewrite_contact: TurnOnOff::Yes,
+        rtp_symmetric: TurnOnOff::Yes,
+        from_domain: trunk.from_domain.clone(),
+        from_user: trunk.from_user.clone(),
+        outbound_proxy: trunk.outbound_proxy.clone(),
+    };
+
+    // 登録順に並ぶよう、単調増加の ULID を使います
+    let mut generator: Generator = Generator::new();
+    let identifies: Vec<PsEndpointIdIp> = trunk
+        .matches
+        .iter()
+        .map(|value| PsEndpointIdIp {
+            id: generator
+                .generate()
+                .unwrap_or_else(|_| Ulid::new())
+                .to_string(),
+            endpoint: trunk_id.to_string(),
+            r#match: Some(value.clone()),
+            match_header: None,
+        })
+        .collect();
+
+    PjsipTrunkRows {
+        registration,
+        auth,
+        aor,
+        endpoint,
+        identifies,
+    }
+}
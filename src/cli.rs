@@ -0,0 +1,77 @@
+use axum::extract::State;
+
+use crate::application::repository::pjsip_realtime::import_pjsip_conf;
+use crate::infrastructure::models::pjsip_realtime::pjsip_conf::PjsipConfImportParams;
+use crate::{AppState, create_pjsip_pool};
+
+// コマンドラインのサブコマンド
+// 引数がない場合は API サーバーを起動します (main.rs)。
+
+pub const IMPORT_PJSIP_CONF_COMMAND: &str = "import-pjsip-conf";
+pub const IMPORT_PJSIP_CONF_USAGE: &str =
+    "usage: ai-talker-api import-pjsip-conf <path> [--dry-run] [--from-domain <domain>]";
+
+// exit code: 0 = 成功, 1 = 取り込みエラー (dry_run でエラーがある場合を含む), 2 = 引数の誤り
+pub async fn run_import_pjsip_conf(args: &[String]) -> i32 {
+    let (path, params) = match parse_import_pjsip_conf_args(args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}\n{}", e, IMPORT_PJSIP_CONF_USAGE);
+            return 2;
+        }
+    };
+    let text: String = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", path, e);
+            return 1;
+        }
+    };
+    let pool = match create_pjsip_pool().await {
+        Ok(pool) => pool,
+        Err(e) => {
+            eprintln!("Failed to create database connection pool: {}", e);
+            return 1;
+        }
+    };
+
+    match import_pjsip_conf(State(AppState::new(pool)), &text, &params).await {
+        Ok((_, report)) => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report).unwrap_or_default()
+            );
+            if report.errors.is_empty() { 0 } else { 1 }
+        }
+        Err(e) => {
+            let (_, problem) = e.into_problem();
+            eprintln!(
+                "{}",
+                serde_json::to_string_pretty(&problem).unwrap_or_default()
+            );
+            1
+        }
+    }
+}
+
+pub fn parse_import_pjsip_conf_args(
+    args: &[String],
+) -> Result<(String, PjsipConfImportParams), String> {
+    let mut path: Option<String> = None;
+    let mut params: PjsipConfImportParams = PjsipConfImportParams::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dry-run" => params.dry_run = true,
+            "--from-domain" => {
+                let domain = args.next().ok_or("--from-domain requires a value")?;
+                params.from_domain = Some(domain.clone());
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown option: {}", arg)),
+            _ if path.is_none() => path = Some(arg.clone()),
+            _ => return Err(format!("unexpected argument: {}", arg)),
+        }
+    }
+    let path: String = path.ok_or("path to pjsip.conf is required")?;
+    Ok((path, params))
+}
@@ -0,0 +1,153 @@
+use std::env;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::infrastructure::models::authentication::authorization_policy::AuthorizationPolicy;
+use crate::infrastructure::models::pjsip_realtime::enums::pjsip_auth_enums::AuthType;
+
+// アプリケーション設定
+// DB 接続以外の設定を環境変数から読み込み、AppState 経由でハンドラへ渡します。
+#[derive(Clone, Debug)]
+pub struct AppConfig {
+    // GET /accounts/{account_id}/credentials に必要なトークン
+    // 未設定の場合、認証情報の参照エンドポイントは無効 (403) になります。
+    pub credentials_reveal_token: Option<String>,
+    // リクエストで auth_type が指定されなかった場合の認証方式 (userpass / md5)
+    pub default_auth_type: AuthType,
+    // リクエストで realm が指定されなかった場合に ps_auths.realm へ設定する値
+    // 未設定の場合、md5 のアカウントは realm の指定が必須になります。
+    pub default_realm: Option<String>,
+    // true の場合、/api/v1 の Bearer トークン検証を行いません (ローカル開発・テスト用)
+    pub auth_disabled: bool,
+    // OIDC (Keycloak など) の JWT 検証設定
+    // 未設定かつ auth_disabled=false の場合、/api/v1 へのリクエストはすべて 401 になります。
+    pub oidc: Option<OidcConfig>,
+    // 操作ごとに必要なロール (AUTHZ_POLICY_FILE で上書き可能)
+    pub authorization_policy: AuthorizationPolicy,
+}
+
+// JWT の署名検証に使う JWKS の取得元
+#[derive(Clone, Debug, PartialEq)]
+pub enum JwksSource {
+    File(PathBuf),
+    Url(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct OidcConfig {
+    // iss クレームと一致する必要がある値
+    pub issuer: String,
+    // aud クレームにいずれかが含まれる必要がある値
+    pub audiences: Vec<String>,
+    pub jwks_source: JwksSource,
+    // exp / nbf の検証で許容する時計のずれ (秒)
+    pub leeway_secs: u64,
+}
+
+impl OidcConfig {
+    const DEFAULT_LEEWAY_SECS: u64 = 30;
+
+    // OIDC_ISSUER が未設定の場合は None
+    // OIDC_JWKS_FILE が優先され、OIDC_JWKS_URL も未設定の場合は Keycloak の certs エンドポイントを使います。
+    fn from_env() -> Option<Self> {
+        let issuer: String = non_empty_var("OIDC_ISSUER")?;
+        let audiences: Vec<String> = non_empty_var("OIDC_AUDIENCE")
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(|aud| aud.trim().to_string())
+                    .filter(|aud| !aud.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if audiences.is_empty() {
+            tracing::warn!("OIDC_ISSUER is set but OIDC_AUDIENCE is not, rejecting all tokens");
+            return None;
+        }
+
+        let jwks_source: JwksSource = match non_empty_var("OIDC_JWKS_FILE") {
+            Some(path) => JwksSource::File(PathBuf::from(path)),
+            None => JwksSource::Url(non_empty_var("OIDC_JWKS_URL").unwrap_or_else(|| {
+                format!(
+                    "{}/protocol/openid-connect/certs",
+                    issuer.trim_end_matches('/')
+                )
+            })),
+        };
+
+        let leeway_secs: u64 = match non_empty_var("OIDC_LEEWAY_SECS") {
+            Some(value) => value.parse().unwrap_or_else(|_| {
+                tracing::warn!(
+                    "Invalid OIDC_LEEWAY_SECS: {}, using {}",
+                    value,
+                    Self::DEFAULT_LEEWAY_SECS
+                );
+                Self::DEFAULT_LEEWAY_SECS
+            }),
+            None => Self::DEFAULT_LEEWAY_SECS,
+        };
+
+        Some(OidcConfig {
+            issuer,
+            audiences,
+            jwks_source,
+            leeway_secs,
+        })
+    }
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            credentials_reveal_token: None,
+            default_auth_type: AuthType::Userpass,
+            default_realm: None,
+            auth_disabled: false,
+            oidc: None,
+            authorization_policy: AuthorizationPolicy::default(),
+        }
+    }
+}
+
+impl AppConfig {
+    pub fn from_env() -> Self {
+        let default_auth_type: AuthType = match env::var("PJSIP_DEFAULT_AUTH_TYPE") {
+            Ok(value) if !value.is_empty() => match AuthType::from_str(&value) {
+                Ok(auth_type @ (AuthType::Userpass | AuthType::Md5)) => auth_type,
+                _ => {
+                    tracing::warn!(
+                        "Unsupported PJSIP_DEFAULT_AUTH_TYPE: {} (expected userpass or md5), using userpass",
+                        value
+                    );
+                    AuthType::Userpass
+                }
+            },
+            _ => AuthType::Userpass,
+        };
+
+        // ポリシーファイルを読み込めない場合は起動しません (意図しない権限の付与を防ぐため)
+        let authorization_policy: AuthorizationPolicy = match non_empty_var("AUTHZ_POLICY_FILE") {
+            Some(path) => AuthorizationPolicy::from_file(&PathBuf::from(path))
+                .unwrap_or_else(|e| panic!("Failed to load AUTHZ_POLICY_FILE: {}", e)),
+            None => AuthorizationPolicy::default(),
+        };
+
+        AppConfig {
+            credentials_reveal_token: env::var("CREDENTIALS_REVEAL_TOKEN")
+                .ok()
+                .filter(|token| !token.is_empty()),
+            default_auth_type,
+            default_realm: env::var("PJSIP_DEFAULT_REALM")
+                .ok()
+                .filter(|realm| !realm.is_empty()),
+            auth_disabled: env::var("AUTH_DISABLED")
+                .is_ok_and(|value| value.eq_ignore_ascii_case("true")),
+            oidc: OidcConfig::from_env(),
+            authorization_policy,
+        }
+    }
+}
+
+fn non_empty_var(key: &str) -> Option<String> {
+    env::var(key).ok().filter(|value| !value.is_empty())
+}
@@ -0,0 +1,157 @@
+use std::time::{Duration, Instant};
+
+use jsonwebtoken::jwk::{AlgorithmParameters, Jwk, JwkSet};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header};
+use tokio::sync::RwLock;
+
+use crate::config::{AppConfig, JwksSource, OidcConfig};
+use crate::infrastructure::models::authentication::claims::JwtClaims;
+use crate::infrastructure::models::errors::authentication_error::AuthenticationError;
+
+// 未知の kid を受け取った際に JWKS を再取得する最短間隔
+// (鍵のローテーションには追従しつつ、不正なトークンで IdP へ問い合わせが集中しないようにします)
+const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Default)]
+struct JwksCache {
+    keys: Option<JwkSet>,
+    fetched_at: Option<Instant>,
+}
+
+// Bearer JWT の検証
+// JWKS は最初の検証時に読み込み、キャッシュします。
+pub struct JwtAuthenticator {
+    disabled: bool,
+    oidc: Option<OidcConfig>,
+    http: reqwest::Client,
+    cache: RwLock<JwksCache>,
+}
+
+impl JwtAuthenticator {
+    pub fn new(config: &AppConfig) -> Self {
+        if config.auth_disabled {
+            tracing::warn!("Authentication is disabled (AUTH_DISABLED=true)");
+        } else if config.oidc.is_none() {
+            tracing::warn!("OIDC is not configured, all API requests will be rejected");
+        }
+        JwtAuthenticator {
+            disabled: config.auth_disabled,
+            oidc: config.oidc.clone(),
+            http: reqwest::Client::new(),
+            cache: RwLock::new(JwksCache::default()),
+        }
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+
+    pub async fn verify(&self, token: &str) -> Result<JwtClaims, AuthenticationError> {
+        let Some(oidc) = self.oidc.as_ref() else {
+            return Err(AuthenticationError::NotConfigured);
+        };
+
+        let header = decode_header(token)?;
+        let jwk: Jwk = self.find_key(oidc, header.kid.as_deref()).await?;
+        if !is_compatible_algorithm(&jwk, header.alg) {
+            return Err(AuthenticationError::UnsupportedAlgorithm(header.alg));
+        }
+        let decoding_key: DecodingKey = DecodingKey::from_jwk(&jwk)?;
+
+        let mut validation: Validation = Validation::new(header.alg);
+        validation.set_issuer(&[oidc.issuer.as_str()]);
+        validation.set_audience(&oidc.audiences);
+        validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+        validation.leeway = oidc.leeway_secs;
+
+        let token_data = decode::<JwtClaims>(token, &decoding_key, &validation)?;
+        Ok(token_data.claims)
+    }
+
+    async fn find_key(
+        &self,
+        oidc: &OidcConfig,
+        kid: Option<&str>,
+    ) -> Result<Jwk, AuthenticationError> {
+        {
+            let cache = self.cache.read().await;
+            if let Some(jwk) = cache.keys.as_ref().and_then(|keys| select_key(keys, kid)) {
+                return Ok(jwk);
+            }
+        }
+
+        let mut cache = self.cache.write().await;
+        // 他のリクエストが先に読み込んでいる場合
+        if let Some(jwk) = cache.keys.as_ref().and_then(|keys| select_key(keys, kid)) {
+            return Ok(jwk);
+        }
+        let refreshable: bool = cache.keys.is_none()
+            || (matches!(oidc.jwks_source, JwksSource::Url(_))
+                && cache
+                    .fetched_at
+                    .is_none_or(|fetched_at| fetched_at.elapsed() >= JWKS_REFRESH_INTERVAL));
+        if !refreshable {
+            return Err(AuthenticationError::UnknownKey(kid.map(str::to_string)));
+        }
+
+        let keys: JwkSet = self.load_jwks(&oidc.jwks_source).await?;
+        let jwk: Option<Jwk> = select_key(&keys, kid);
+        cache.keys = Some(keys);
+        cache.fetched_at = Some(Instant::now());
+        jwk.ok_or_else(|| AuthenticationError::UnknownKey(kid.map(str::to_string)))
+    }
+
+    async fn load_jwks(&self, source: &JwksSource) -> Result<JwkSet, AuthenticationError> {
+        let keys: JwkSet = match source {
+            JwksSource::File(path) => {
+                let contents: String = tokio::fs::read_to_string(path).await.map_err(|e| {
+                    AuthenticationError::JwksUnavailable(format!("{}: {}", path.display(), e))
+                })?;
+                serde_json::from_str(&contents).map_err(|e| {
+                    AuthenticationError::JwksUnavailable(format!("{}: {}", path.display(), e))
+                })?
+            }
+            JwksSource::Url(url) => self
+                .http
+                .get(url)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|e| AuthenticationError::JwksUnavailable(format!("{}: {}", url, e)))?
+                .json()
+                .await
+                .map_err(|e| AuthenticationError::JwksUnavailable(format!("{}: {}", url, e)))?,
+        };
+        tracing::info!("Loaded {} signing keys from JWKS", keys.keys.len());
+        Ok(keys)
+    }
+}
+
+// kid が指定されていない場合は、鍵が 1 つだけのときに限りその鍵を使います。
+fn select_key(keys: &JwkSet, kid: Option<&str>) -> Option<Jwk> {
+    match kid {
+        Some(kid) => keys.find(kid).cloned(),
+        None if keys.keys.len() == 1 => keys.keys.first().cloned(),
+        None => None,
+    }
+}
+
+// 公開鍵の種類とトークンの alg が一致するかを確認します (HS* などの共通鍵方式は受け付けません)。
+fn is_compatible_algorithm(jwk: &Jwk, alg: Algorithm) -> bool {
+    match &jwk.algorithm {
+        AlgorithmParameters::RSA(_) => matches!(
+            alg,
+            Algorithm::RS256
+                | Algorithm::RS384
+                | Algorithm::RS512
+                | Algorithm::PS256
+                | Algorithm::PS384
+                | Algorithm::PS512
+        ),
+        AlgorithmParameters::EllipticCurve(_) => {
+            matches!(alg, Algorithm::ES256 | Algorithm::ES384)
+        }
+        AlgorithmParameters::OctetKeyPair(_) => alg == Algorithm::EdDSA,
+        AlgorithmParameters::OctetKey(_) => false,
+    }
+}
@@ -0,0 +1,4 @@
+pub mod api_key;
+pub mod authorization_policy;
+pub mod claims;
+pub mod principal;
@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use rand::distr::Alphanumeric;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+// 発行する API キーの形式: "pjk_" + 英数字 40 文字
+pub const API_KEY_PREFIX: &str = "pjk_";
+const API_KEY_SECRET_LENGTH: usize = 40;
+// 一覧で表示するキーの先頭部分の長さ ("pjk_" を含む)
+const API_KEY_DISPLAY_PREFIX_LENGTH: usize = 12;
+
+// api_keys テーブル (key_hash は返却しません)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: String,
+    pub name: String,
+    pub key_prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// POST /api/v1/api_keys
+#[derive(Clone, Debug, Deserialize)]
+pub struct IssueApiKeyRequest {
+    pub name: String,
+    pub scopes: Vec<String>,
+    // 未指定の場合は無期限
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+// 発行時のレスポンス (api_key はこのときだけ返却します)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IssuedApiKey {
+    #[serde(flatten)]
+    pub key: ApiKey,
+    pub api_key: String,
+}
+
+// 新しい API キー (平文) を生成します
+pub fn generate_api_key() -> String {
+    let secret: String = rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(API_KEY_SECRET_LENGTH)
+        .map(char::from)
+        .collect();
+    format!("{}{}", API_KEY_PREFIX, secret)
+}
+
+pub fn api_key_display_prefix(api_key: &str) -> String {
+    api_key
+        .chars()
+        .take(API_KEY_DISPLAY_PREFIX_LENGTH)
+        .collect()
+}
+
+// 十分な長さの乱数のため、ソルトなしの SHA-256 で保存します
+pub fn hash_api_key(api_key: &str) -> String {
+    format!("{:x}", Sha256::digest(api_key.as_bytes()))
+}
@@ -0,0 +1,107 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+// 認可の対象となる操作
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    ListAccounts,
+    GetAccount,
+    CreateAccount,
+    UpdateAccount,
+    DeleteAccount,
+    RevealCredentials,
+    ManageApiKeys,
+    ReadTrunks,
+    ManageTrunks,
+    ReadIdentifies,
+    ManageIdentifies,
+}
+
+pub const ROLE_READ: &str = "pjsip:read";
+pub const ROLE_WRITE: &str = "pjsip:write";
+pub const ROLE_ADMIN: &str = "pjsip:admin";
+
+fn read_roles() -> Vec<String> {
+    vec![ROLE_READ.into(), ROLE_WRITE.into(), ROLE_ADMIN.into()]
+}
+
+fn write_roles() -> Vec<String> {
+    vec![ROLE_WRITE.into(), ROLE_ADMIN.into()]
+}
+
+fn admin_roles() -> Vec<String> {
+    vec![ROLE_ADMIN.into()]
+}
+
+// 操作ごとに許可するロール (いずれか 1 つを持っていれば許可)
+// ポリシーファイル (JSON) で指定しなかった操作はデフォルトのロールを使います。
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuthorizationPolicy {
+    #[serde(default = "read_roles")]
+    pub list_accounts: Vec<String>,
+    #[serde(default = "read_roles")]
+    pub get_account: Vec<String>,
+    #[serde(default = "write_roles")]
+    pub create_account: Vec<String>,
+    #[serde(default = "write_roles")]
+    pub update_account: Vec<String>,
+    #[serde(default = "admin_roles")]
+    pub delete_account: Vec<String>,
+    #[serde(default = "admin_roles")]
+    pub reveal_credentials: Vec<String>,
+    #[serde(default = "admin_roles")]
+    pub manage_api_keys: Vec<String>,
+    #[serde(default = "read_roles")]
+    pub read_trunks: Vec<String>,
+    #[serde(default = "admin_roles")]
+    pub manage_trunks: Vec<String>,
+    #[serde(default = "read_roles")]
+    pub read_identifies: Vec<String>,
+    #[serde(default = "admin_roles")]
+    pub manage_identifies: Vec<String>,
+}
+
+impl Default for AuthorizationPolicy {
+    fn default() -> Self {
+        AuthorizationPolicy {
+            list_accounts: read_roles(),
+            get_account: read_roles(),
+            create_account: write_roles(),
+            update_account: write_roles(),
+            delete_account: admin_roles(),
+            reveal_credentials: admin_roles(),
+            manage_api_keys: admin_roles(),
+            read_trunks: read_roles(),
+            manage_trunks: admin_roles(),
+            read_identifies: read_roles(),
+            manage_identifies: admin_roles(),
+        }
+    }
+}
+
+impl AuthorizationPolicy {
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let contents: String =
+            std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        serde_json::from_str(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn allowed_roles(&self, permission: Permission) -> &[String] {
+        match permission {
+            Permission::ListAccounts => &self.list_accounts,
+            Permission::GetAccount => &self.get_account,
+            Permission::CreateAccount => &self.create_account,
+            Permission::UpdateAccount => &self.update_account,
+            Permission::DeleteAccount => &self.delete_account,
+            Permission::RevealCredentials => &self.reveal_credentials,
+            Permission::ManageApiKeys => &self.manage_api_keys,
+            Permission::ReadTrunks => &self.read_trunks,
+            Permission::ManageTrunks => &self.manage_trunks,
+            Permission::ReadIdentifies => &self.read_identifies,
+            Permission::ManageIdentifies => &self.manage_identifies,
+        }
+    }
+}
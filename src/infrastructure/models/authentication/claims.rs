@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// aud は単一の文字列または配列のどちらでも発行されます
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum Audience {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct RealmAccess {
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+// 検証済みの JWT クレーム
+// 認証ミドルウェアがリクエストの extensions に格納し、ハンドラは Extension<JwtClaims> で受け取ります。
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JwtClaims {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    pub iss: String,
+    pub aud: Audience,
+    pub exp: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_username: Option<String>,
+    // スペース区切りのスコープ
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    // Keycloak の realm ロール
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub realm_access: Option<RealmAccess>,
+    // Keycloak のクライアントロール (クライアント ID ごと)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub resource_access: HashMap<String, RealmAccess>,
+    // 上記以外のクレーム
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl JwtClaims {
+    // ログなどで利用者を示す名前 (preferred_username → sub の順)
+    pub fn subject(&self) -> &str {
+        self.preferred_username
+            .as_deref()
+            .or(self.sub.as_deref())
+            .unwrap_or("unknown")
+    }
+
+    // realm ロールと、指定したクライアント (aud) のクライアントロール
+    // 他のクライアント向けのロールは対象外です。
+    pub fn roles<'a>(&'a self, clients: &[String]) -> Vec<&'a str> {
+        let realm_roles = self.realm_access.iter().flat_map(|access| &access.roles);
+        let client_roles = clients
+            .iter()
+            .filter_map(|client| self.resource_access.get(client))
+            .flat_map(|access| &access.roles);
+        realm_roles
+            .chain(client_roles)
+            .map(String::as_str)
+            .collect()
+    }
+}
@@ -0,0 +1,30 @@
+use crate::infrastructure::models::authentication::api_key::ApiKey;
+use crate::infrastructure::models::authentication::claims::JwtClaims;
+
+// 認証済みの呼び出し元
+// 認証ミドルウェアがリクエストの extensions に格納し、ハンドラは Extension<Principal> で受け取ります。
+#[derive(Clone, Debug)]
+pub enum Principal {
+    // OIDC の Bearer トークン
+    User(JwtClaims),
+    // X-API-Key ヘッダーの API キー
+    ApiKey(ApiKey),
+}
+
+impl Principal {
+    // ログなどで呼び出し元を示す名前
+    pub fn subject(&self) -> String {
+        match self {
+            Principal::User(claims) => claims.subject().to_string(),
+            Principal::ApiKey(key) => format!("api_key:{}", key.id),
+        }
+    }
+
+    // 認可に使うロール (API キーの場合は scopes)
+    pub fn roles<'a>(&'a self, clients: &[String]) -> Vec<&'a str> {
+        match self {
+            Principal::User(claims) => claims.roles(clients),
+            Principal::ApiKey(key) => key.scopes.iter().map(String::as_str).collect(),
+        }
+    }
+}
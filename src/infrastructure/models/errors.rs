@@ -1,2 +1,8 @@
+pub mod api_error;
+pub mod authentication_error;
 pub mod deletion_error;
 pub mod registration_error;
+pub mod retrieval_error;
+pub mod unique_violation;
+pub mod update_error;
+pub mod validation_error;
@@ -0,0 +1,311 @@
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::infrastructure::models::errors::{
+    authentication_error::AuthenticationError, deletion_error::DeletionError,
+    registration_error::RegistrationError, retrieval_error::RetrievalError,
+    update_error::UpdateError, validation_error::ValidationErrors,
+};
+
+pub const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+
+// クライアントが判定に使うエラーコード (レスポンスの "code")
+// 値は API の互換性の一部のため、変更・削除しないでください。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    // 400
+    ValidationFailed,
+    InvalidRequestBody,
+    InvalidQuery,
+    InvalidCursor,
+    // 401
+    Unauthenticated,
+    InvalidToken,
+    InvalidApiKey,
+    // 403
+    MissingRole,
+    CredentialsRevealDisabled,
+    InvalidRevealToken,
+    // 404
+    NotFound,
+    // 409
+    Duplicate,
+    TransportChangeNotAllowed,
+    MatchOverlap,
+    // 422
+    InvalidFields,
+    // 500
+    DatabaseError,
+    InternalError,
+    // 503
+    AuthenticationUnavailable,
+}
+
+impl ErrorCode {
+    pub fn status(self) -> StatusCode {
+        match self {
+            ErrorCode::ValidationFailed
+            | ErrorCode::InvalidRequestBody
+            | ErrorCode::InvalidQuery
+            | ErrorCode::InvalidCursor => StatusCode::BAD_REQUEST,
+            ErrorCode::Unauthenticated | ErrorCode::InvalidToken | ErrorCode::InvalidApiKey => {
+                StatusCode::UNAUTHORIZED
+            }
+            ErrorCode::MissingRole
+            | ErrorCode::CredentialsRevealDisabled
+            | ErrorCode::InvalidRevealToken => StatusCode::FORBIDDEN,
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::Duplicate
+            | ErrorCode::TransportChangeNotAllowed
+            | ErrorCode::MatchOverlap => StatusCode::CONFLICT,
+            ErrorCode::InvalidFields => StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCode::DatabaseError | ErrorCode::InternalError => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            ErrorCode::AuthenticationUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+}
+
+// すべてのハンドラが返すエラー
+// RFC 7807 (application/problem+json) の形式で返却します。
+// {"type": "about:blank", "title": "Not Found", "status": 404, "detail": "...", "code": "not_found"}
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    code: ErrorCode,
+    detail: String,
+    // problem+json の拡張メンバー
+    extensions: Map<String, Value>,
+    www_authenticate: Option<&'static str>,
+    // ログにのみ出力する内部エラー (5xx の原因など)
+    source: Option<String>,
+}
+
+impl ApiError {
+    pub fn new(code: ErrorCode, detail: impl Into<String>) -> Self {
+        ApiError {
+            status: code.status(),
+            code,
+            detail: detail.into(),
+            extensions: Map::new(),
+            www_authenticate: None,
+            source: None,
+        }
+    }
+
+    pub fn with_status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn with_extension(mut self, key: &str, value: impl Serialize) -> Self {
+        self.extensions.insert(
+            key.to_string(),
+            serde_json::to_value(value).unwrap_or(Value::Null),
+        );
+        self
+    }
+
+    pub fn with_www_authenticate(mut self, challenge: &'static str) -> Self {
+        self.www_authenticate = Some(challenge);
+        self
+    }
+
+    // detail の先頭に操作内容を付けます (例: "Failed to update account: ...")
+    pub fn context(mut self, context: &str) -> Self {
+        self.detail = format!("{}: {}", context, self.detail);
+        self
+    }
+
+    fn internal(code: ErrorCode, detail: &str, source: String) -> Self {
+        ApiError {
+            source: Some(source),
+            ..ApiError::new(code, detail)
+        }
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.code, self.detail)
+    }
+}
+
+impl ApiError {
+    // problem+json の本文に変換します (5xx の場合は原因をログに出力します)
+    // 一括処理の結果など、レスポンスの一部としてエラーを返す場合にも使用します。
+    pub fn into_problem(self) -> (StatusCode, Value) {
+        if self.status.is_server_error() {
+            tracing::error!(
+                "{} ({:?}): {}",
+                self.detail,
+                self.code,
+                self.source.as_deref().unwrap_or("-")
+            );
+        }
+
+        let mut body: Map<String, Value> = Map::new();
+        body.insert("type".into(), Value::from("about:blank"));
+        body.insert(
+            "title".into(),
+            Value::from(self.status.canonical_reason().unwrap_or("Error")),
+        );
+        body.insert("status".into(), Value::from(self.status.as_u16()));
+        body.insert("detail".into(), Value::from(self.detail));
+        body.insert(
+            "code".into(),
+            serde_json::to_value(self.code).unwrap_or(Value::Null),
+        );
+        for (key, value) in self.extensions {
+            body.entry(key).or_insert(value);
+        }
+        (self.status, Value::Object(body))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let www_authenticate: Option<&'static str> = self.www_authenticate;
+        let (status, body) = self.into_problem();
+        let mut response: Response = (status, body.to_string()).into_response();
+        let headers = response.headers_mut();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(PROBLEM_JSON_CONTENT_TYPE),
+        );
+        if let Some(challenge) = www_authenticate {
+            headers.insert(
+                header::WWW_AUTHENTICATE,
+                HeaderValue::from_static(challenge),
+            );
+        }
+        response
+    }
+}
+
+// 内部の詳細はレスポンスに含めず、ログにのみ出力します
+impl From<sqlx::Error> for ApiError {
+    fn from(err: sqlx::Error) -> Self {
+        ApiError::internal(
+            ErrorCode::DatabaseError,
+            "A database error occurred",
+            err.to_string(),
+        )
+    }
+}
+
+// 409 の problem+json に衝突した項目名を含めます (特定できない場合は含めません)。
+fn duplicate(field: Option<String>) -> ApiError {
+    match field {
+        Some(field) => ApiError::new(
+            ErrorCode::Duplicate,
+            format!("A resource with this {} already exists", field),
+        )
+        .with_extension("field", field),
+        None => ApiError::new(ErrorCode::Duplicate, "The resource already exists"),
+    }
+}
+
+impl From<RegistrationError> for ApiError {
+    fn from(err: RegistrationError) -> Self {
+        match err {
+            RegistrationError::DatabaseError(e) => e.into(),
+            RegistrationError::ValidationError(msg) => {
+                ApiError::new(ErrorCode::ValidationFailed, msg)
+            }
+            RegistrationError::DuplicateError(field) => duplicate(field),
+            RegistrationError::InsertionFailed => ApiError::internal(
+                ErrorCode::InternalError,
+                "Insertion failed",
+                err.to_string(),
+            ),
+        }
+    }
+}
+
+// 項目ごとのエラーは "errors": [{"field": "...", "message": "..."}] として返します。
+impl From<ValidationErrors> for ApiError {
+    fn from(err: ValidationErrors) -> Self {
+        ApiError::new(ErrorCode::InvalidFields, "One or more fields are invalid")
+            .with_extension("errors", err.errors())
+    }
+}
+
+impl From<DeletionError> for ApiError {
+    fn from(err: DeletionError) -> Self {
+        match err {
+            DeletionError::DatabaseError(e) => e.into(),
+            DeletionError::IdNotSpecified | DeletionError::InvalidId(_) => {
+                ApiError::new(ErrorCode::ValidationFailed, err.to_string())
+            }
+            DeletionError::NotFoundRecord => ApiError::new(ErrorCode::NotFound, err.to_string()),
+        }
+    }
+}
+
+impl From<RetrievalError> for ApiError {
+    fn from(err: RetrievalError) -> Self {
+        match err {
+            RetrievalError::DatabaseError(e) => e.into(),
+            RetrievalError::IdNotSpecified => {
+                ApiError::new(ErrorCode::ValidationFailed, err.to_string())
+            }
+            RetrievalError::NotFoundRecord => ApiError::new(ErrorCode::NotFound, err.to_string()),
+            RetrievalError::InvalidCursor(_) => {
+                ApiError::new(ErrorCode::InvalidCursor, err.to_string())
+            }
+            RetrievalError::InvalidRecord(_) => {
+                ApiError::internal(ErrorCode::InternalError, "Invalid record", err.to_string())
+            }
+        }
+    }
+}
+
+impl From<UpdateError> for ApiError {
+    fn from(err: UpdateError) -> Self {
+        match err {
+            UpdateError::DatabaseError(e) => e.into(),
+            UpdateError::IdNotSpecified => {
+                ApiError::new(ErrorCode::ValidationFailed, err.to_string())
+            }
+            UpdateError::NotFoundRecord => ApiError::new(ErrorCode::NotFound, err.to_string()),
+            UpdateError::DuplicateError(field) => duplicate(field),
+            UpdateError::UpdateFailed => {
+                ApiError::internal(ErrorCode::InternalError, "Update failed", err.to_string())
+            }
+        }
+    }
+}
+
+impl From<AuthenticationError> for ApiError {
+    fn from(err: AuthenticationError) -> Self {
+        match err {
+            AuthenticationError::MissingToken => {
+                ApiError::new(ErrorCode::Unauthenticated, err.to_string())
+                    .with_www_authenticate("Bearer")
+            }
+            AuthenticationError::InvalidApiKey => {
+                ApiError::new(ErrorCode::InvalidApiKey, err.to_string())
+                    .with_www_authenticate("Bearer")
+            }
+            AuthenticationError::NotConfigured
+            | AuthenticationError::InvalidToken(_)
+            | AuthenticationError::UnsupportedAlgorithm(_)
+            | AuthenticationError::UnknownKey(_) => {
+                ApiError::new(ErrorCode::InvalidToken, err.to_string())
+                    .with_www_authenticate("Bearer error=\"invalid_token\"")
+            }
+            // 署名鍵を取得できない場合はトークンの問題ではないため 503 を返します
+            AuthenticationError::JwksUnavailable(_) => ApiError::internal(
+                ErrorCode::AuthenticationUnavailable,
+                "Authentication service unavailable",
+                err.to_string(),
+            ),
+            AuthenticationError::DatabaseError(e) => e.into(),
+        }
+    }
+}
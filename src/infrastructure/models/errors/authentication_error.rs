@@ -0,0 +1,45 @@
+#[derive(Debug)]
+pub enum AuthenticationError {
+    // 認証が有効だが OIDC の設定がない
+    NotConfigured,
+    MissingToken,
+    InvalidToken(jsonwebtoken::errors::Error),
+    UnsupportedAlgorithm(jsonwebtoken::Algorithm),
+    UnknownKey(Option<String>),
+    JwksUnavailable(String),
+    // 未登録・失効・期限切れの API キー
+    InvalidApiKey,
+    DatabaseError(sqlx::Error),
+}
+
+impl From<jsonwebtoken::errors::Error> for AuthenticationError {
+    fn from(err: jsonwebtoken::errors::Error) -> Self {
+        AuthenticationError::InvalidToken(err)
+    }
+}
+
+impl From<sqlx::Error> for AuthenticationError {
+    fn from(err: sqlx::Error) -> Self {
+        AuthenticationError::DatabaseError(err)
+    }
+}
+
+impl std::fmt::Display for AuthenticationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthenticationError::NotConfigured => write!(f, "Authentication is not configured"),
+            AuthenticationError::MissingToken => write!(f, "Bearer token is required"),
+            AuthenticationError::InvalidToken(err) => write!(f, "Invalid token: {}", err),
+            AuthenticationError::UnsupportedAlgorithm(alg) => {
+                write!(f, "Unsupported token algorithm: {:?}", alg)
+            }
+            AuthenticationError::UnknownKey(Some(kid)) => {
+                write!(f, "No signing key found for kid: {}", kid)
+            }
+            AuthenticationError::UnknownKey(None) => write!(f, "No signing key found"),
+            AuthenticationError::JwksUnavailable(err) => write!(f, "JWKS unavailable: {}", err),
+            AuthenticationError::InvalidApiKey => write!(f, "Invalid API key"),
+            AuthenticationError::DatabaseError(err) => write!(f, "Database error: {}", err),
+        }
+    }
+}
@@ -2,6 +2,7 @@
 pub enum DeletionError {
     DatabaseError(sqlx::Error),
     IdNotSpecified,
+    InvalidId(String),
     NotFoundRecord,
 }
 
@@ -16,6 +17,7 @@ impl std::fmt::Display for DeletionError {
         match self {
             DeletionError::DatabaseError(err) => write!(f, "Database error: {}", err),
             DeletionError::IdNotSpecified => write!(f, "Account ID not specified"),
+            DeletionError::InvalidId(id) => write!(f, "Invalid account ID: {:?}", id),
             DeletionError::NotFoundRecord => write!(f, "No record found for the given ID"),
         }
     }
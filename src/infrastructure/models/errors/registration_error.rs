@@ -1,14 +1,23 @@
+use crate::infrastructure::models::errors::unique_violation::{
+    is_unique_violation, unique_violation_field,
+};
+
 #[derive(Debug)]
 pub enum RegistrationError {
     DatabaseError(sqlx::Error),
     ValidationError(String),
     InsertionFailed,
-    DuplicateError,
+    // 衝突した項目名 (id / username、既知の一意制約以外は None)
+    DuplicateError(Option<String>),
 }
 
 impl From<sqlx::Error> for RegistrationError {
     fn from(err: sqlx::Error) -> Self {
-        RegistrationError::DatabaseError(err)
+        if is_unique_violation(&err) {
+            RegistrationError::DuplicateError(unique_violation_field(&err))
+        } else {
+            RegistrationError::DatabaseError(err)
+        }
     }
 }
 
@@ -18,7 +27,8 @@ impl std::fmt::Display for RegistrationError {
             RegistrationError::DatabaseError(err) => write!(f, "Database error: {}", err),
             RegistrationError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
             RegistrationError::InsertionFailed => write!(f, "Insertion failed"),
-            RegistrationError::DuplicateError => write!(f, "Duplicate error"),
+            RegistrationError::DuplicateError(Some(field)) => write!(f, "Duplicate {}", field),
+            RegistrationError::DuplicateError(None) => write!(f, "Duplicate record"),
         }
     }
 }
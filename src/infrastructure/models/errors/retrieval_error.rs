@@ -0,0 +1,26 @@
+#[derive(Debug)]
+pub enum RetrievalError {
+    DatabaseError(sqlx::Error),
+    IdNotSpecified,
+    NotFoundRecord,
+    InvalidCursor(String),
+    InvalidRecord(String),
+}
+
+impl From<sqlx::Error> for RetrievalError {
+    fn from(err: sqlx::Error) -> Self {
+        RetrievalError::DatabaseError(err)
+    }
+}
+
+impl std::fmt::Display for RetrievalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RetrievalError::DatabaseError(err) => write!(f, "Database error: {}", err),
+            RetrievalError::IdNotSpecified => write!(f, "Account ID not specified"),
+            RetrievalError::NotFoundRecord => write!(f, "No record found for the given ID"),
+            RetrievalError::InvalidCursor(cursor) => write!(f, "Invalid cursor: {}", cursor),
+            RetrievalError::InvalidRecord(message) => write!(f, "Invalid record: {}", message),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+// 一意制約違反 (SQLSTATE 23505) から衝突した項目名を特定します。
+// 事前の SELECT EXISTS では同時に作成された場合を検出できないため、
+// 最終的な重複判定はデータベースの制約に任せます。
+
+// 一意制約と項目名の対応
+// ここにない制約の違反は項目名を特定できないため、field なしの 409 を返します。
+const UNIQUE_CONSTRAINT_FIELDS: &[(&str, &str)] = &[
+    ("idx_pjsip_accounts_username_unique", "username"),
+    ("idx_api_keys_key_hash_unique", "key_hash"),
+    ("idx_pjsip_trunks_name_unique", "name"),
+    ("pjsip_realtime_accounts_pkey", "id"),
+    ("pjsip_realtime_trunks_pkey", "id"),
+    ("api_keys_pkey", "id"),
+    ("ps_endpoints_id_key", "id"),
+    ("ps_aors_id_key", "id"),
+    ("ps_auths_id_key", "id"),
+    ("ps_registrations_id_key", "id"),
+    ("ps_endpoint_id_ips_id_key", "id"),
+];
+
+pub fn is_unique_violation(err: &sqlx::Error) -> bool {
+    matches!(err, sqlx::Error::Database(db_err) if db_err.is_unique_violation())
+}
+
+// 既知の一意制約の場合のみ項目名を返します
+pub fn unique_violation_field(err: &sqlx::Error) -> Option<String> {
+    let sqlx::Error::Database(db_err) = err else {
+        return None;
+    };
+    let constraint: &str = db_err.constraint()?;
+    UNIQUE_CONSTRAINT_FIELDS
+        .iter()
+        .find(|(name, _)| *name == constraint)
+        .map(|(_, field)| field.to_string())
+}
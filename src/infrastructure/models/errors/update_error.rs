@@ -0,0 +1,36 @@
+use crate::infrastructure::models::errors::unique_violation::{
+    is_unique_violation, unique_violation_field,
+};
+
+#[derive(Debug)]
+pub enum UpdateError {
+    DatabaseError(sqlx::Error),
+    IdNotSpecified,
+    NotFoundRecord,
+    // 衝突した項目名 (id / username、既知の一意制約以外は None)
+    DuplicateError(Option<String>),
+    UpdateFailed,
+}
+
+impl From<sqlx::Error> for UpdateError {
+    fn from(err: sqlx::Error) -> Self {
+        if is_unique_violation(&err) {
+            UpdateError::DuplicateError(unique_violation_field(&err))
+        } else {
+            UpdateError::DatabaseError(err)
+        }
+    }
+}
+
+impl std::fmt::Display for UpdateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdateError::DatabaseError(err) => write!(f, "Database error: {}", err),
+            UpdateError::IdNotSpecified => write!(f, "Account ID not specified"),
+            UpdateError::NotFoundRecord => write!(f, "No record found for the given ID"),
+            UpdateError::DuplicateError(Some(field)) => write!(f, "Duplicate {}", field),
+            UpdateError::DuplicateError(None) => write!(f, "Duplicate record"),
+            UpdateError::UpdateFailed => write!(f, "Update failed"),
+        }
+    }
+}
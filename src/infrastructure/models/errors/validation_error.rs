@@ -0,0 +1,59 @@
+use serde::Serialize;
+
+// 項目ごとの検証エラー
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+// リクエストの検証結果 (すべての項目のエラーをまとめて返します)
+#[derive(Debug, Default)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        ValidationErrors::default()
+    }
+
+    pub fn add(&mut self, field: &str, message: impl Into<String>) {
+        self.errors.push(FieldError {
+            field: field.to_string(),
+            message: message.into(),
+        });
+    }
+
+    // 一括登録などで項目名に位置を付けて他の結果にまとめます (例: "[3].username")
+    pub fn append_prefixed(&mut self, prefix: &str, other: ValidationErrors) {
+        self.errors
+            .extend(other.errors.into_iter().map(|e| FieldError {
+                field: format!("{}.{}", prefix, e.field),
+                message: e.message,
+            }));
+    }
+
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fields: Vec<String> = self
+            .errors
+            .iter()
+            .map(|e| format!("{}: {}", e.field, e.message))
+            .collect();
+        write!(f, "Validation error: {}", fields.join(", "))
+    }
+}
@@ -1,4 +1,18 @@
 pub mod account;
+pub mod account_bulk;
+pub mod account_csv;
+pub mod account_detail;
+pub mod account_list;
+pub mod account_validation;
+pub mod auth;
+pub mod contact;
 pub mod enums;
-pub mod sip_udp;
+pub mod identify_validation;
+pub mod pjsip_conf;
+pub mod registration_status;
+pub mod sip_account;
+pub mod sip_identify;
+pub mod sip_tls;
+pub mod sip_trunk;
 pub mod sip_ws;
+pub mod trunk_validation;
@@ -1,4 +1,7 @@
+use crate::infrastructure::models::pjsip_realtime::enums::pjsip_auth_enums::AuthType;
 use crate::infrastructure::models::pjsip_realtime::enums::pjsip_endpoint_enums::{RtpTimeout, TransportType};
+use crate::infrastructure::models::pjsip_realtime::sip_tls::TlsAccountOptions;
+use crate::infrastructure::models::pjsip_realtime::sip_ws::DtlsAccountOptions;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -7,7 +10,6 @@ pub struct CreateUserRequest {
     pub password: String,
 }
 
-// TODO realm
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PjsipRealtimeAccount {
     pub username: String,
@@ -20,6 +22,18 @@ pub struct PjsipRealtimeAccount {
     pub rtp_timeout: Option<RtpTimeout>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rtp_timeout_hold: Option<RtpTimeout>,
+    // userpass / md5 (未指定の場合はサーバー設定の既定値)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_type: Option<AuthType>,
+    // ps_auths.realm (未指定の場合はサーバー設定の既定値、md5 の場合は必須)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub realm: Option<String>,
+    // transport が tls の場合のみ参照します
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsAccountOptions>,
+    // transport が ws / wss の場合のみ参照します
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dtls: Option<DtlsAccountOptions>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -35,23 +49,185 @@ pub struct PjsipRealtimeAccountWithExternalId {
     pub rtp_timeout: Option<RtpTimeout>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rtp_timeout_hold: Option<RtpTimeout>,
+    // userpass / md5 (未指定の場合はサーバー設定の既定値)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_type: Option<AuthType>,
+    // ps_auths.realm (未指定の場合はサーバー設定の既定値、md5 の場合は必須)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub realm: Option<String>,
+    // transport が tls の場合のみ参照します
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsAccountOptions>,
+    // transport が ws / wss の場合のみ参照します
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dtls: Option<DtlsAccountOptions>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PjsipRealtimeAccountWithId {
     pub id: String,
     pub username: String,
-    pub password: String,
+    pub password: Option<String>, // md5 の場合は平文を保存しないため None
     pub transport: TransportType,
     pub context: String,
     pub from_domain: String,
     pub from_user: String,
     pub rtp_timeout: Option<RtpTimeout>,
     pub rtp_timeout_hold: Option<RtpTimeout>,
+    pub tls: Option<TlsAccountOptions>, // transport が tls の場合のみ (ps_endpoints から取得)
+    pub dtls: Option<DtlsAccountOptions>, // transport が ws / wss の場合のみ (ps_endpoints から取得)
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+// API レスポンス用のアカウントモデル
+// SIP パスワードは返却しません。必要な場合は GET /accounts/{account_id}/credentials を使用してください。
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PjsipRealtimeAccountResponse {
+    pub id: String,
+    pub username: String,
+    pub transport: TransportType,
+    pub context: String,
+    pub from_domain: String,
+    pub from_user: String,
+    pub rtp_timeout: Option<RtpTimeout>,
+    pub rtp_timeout_hold: Option<RtpTimeout>,
+    pub auth_type: Option<AuthType>, // ps_auths.auth_type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub realm: Option<String>, // ps_auths.realm
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsAccountOptions>, // transport が tls の場合のみ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dtls: Option<DtlsAccountOptions>, // transport が ws / wss の場合のみ
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+// 認証情報の参照 (reveal) 用レスポンス
+// md5 のアカウントは平文を保存していないため、password の代わりに md5_cred / realm を返します。
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PjsipRealtimeAccountCredentials {
+    pub id: String,
+    pub username: String,
+    pub auth_type: Option<AuthType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub md5_cred: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub realm: Option<String>,
+}
+
+// PATCH 用の部分更新モデル
+// 指定されたフィールドのみ更新し、None のフィールドは現在の値を維持します。
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PjsipRealtimeAccountPatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transport: Option<TransportType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rtp_timeout: Option<RtpTimeout>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rtp_timeout_hold: Option<RtpTimeout>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_type: Option<AuthType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub realm: Option<String>,
+    // 指定した項目のみ現在の TLS のオプションを更新します
+    // transport が tls の場合のみ指定できます
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsAccountOptions>,
+    // 指定した項目のみ現在の DTLS のオプションを更新します
+    // transport が ws / wss の場合のみ指定できます
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dtls: Option<DtlsAccountOptions>,
+}
+
+impl PjsipRealtimeAccountPatch {
+    /// Returns true when the patch changes the TLS / DTLS options used by the transport.
+    pub fn changes_transport_options(&self, transport: &TransportType) -> bool {
+        match transport {
+            TransportType::Tls => self.tls.is_some(),
+            TransportType::Ws | TransportType::Wss => self.dtls.is_some(),
+            TransportType::Udp | TransportType::Tcp => false,
+        }
+    }
+
+    /// Applies the patch on top of the current account values.
+    /// TLS / DTLS options are merged field by field into the current options.
+    pub fn apply_to(&self, current: &PjsipRealtimeAccountWithId) -> PjsipRealtimeAccountWithId {
+        let transport: TransportType = self
+            .transport
+            .as_ref()
+            .unwrap_or(&current.transport)
+            .clone();
+        let tls: Option<TlsAccountOptions> = match (&self.tls, &current.tls) {
+            (Some(patch), Some(current_tls)) => Some(current_tls.merged_with(patch)),
+            (patch, current_tls) => patch.clone().or_else(|| current_tls.clone()),
+        }
+        .filter(|_| transport == TransportType::Tls);
+        let dtls: Option<DtlsAccountOptions> = match (&self.dtls, &current.dtls) {
+            (Some(patch), Some(current_dtls)) => Some(current_dtls.merged_with(patch)),
+            (patch, current_dtls) => patch.clone().or_else(|| current_dtls.clone()),
+        }
+        .filter(|_| matches!(transport, TransportType::Ws | TransportType::Wss));
+        PjsipRealtimeAccountWithId {
+            id: current.id.clone(),
+            username: self.username.as_ref().unwrap_or(&current.username).clone(),
+            password: self.password.clone().or_else(|| current.password.clone()),
+            transport,
+            context: self.context.as_ref().unwrap_or(&current.context).clone(),
+            from_domain: self
+                .from_domain
+                .as_ref()
+                .unwrap_or(&current.from_domain)
+                .clone(),
+            from_user: self
+                .from_user
+                .as_ref()
+                .unwrap_or(&current.from_user)
+                .clone(),
+            rtp_timeout: self.rtp_timeout.or(current.rtp_timeout),
+            rtp_timeout_hold: self.rtp_timeout_hold.or(current.rtp_timeout_hold),
+            tls,
+            dtls,
+            created_at: current.created_at,
+            updated_at: chrono::Utc::now(),
+        }
+    }
+}
+
+// PUT は全項目置換のため、RTP タイムアウト未指定時は作成時と同じデフォルト値に戻します。
+impl From<PjsipRealtimeAccount> for PjsipRealtimeAccountPatch {
+    fn from(account: PjsipRealtimeAccount) -> Self {
+        PjsipRealtimeAccountPatch {
+            username: Some(account.username),
+            password: Some(account.password),
+            transport: Some(account.transport),
+            context: Some(account.context),
+            from_domain: Some(account.from_domain),
+            from_user: Some(account.from_user),
+            rtp_timeout: Some(account.rtp_timeout.unwrap_or(RtpTimeout::Thirty)),
+            rtp_timeout_hold: Some(account.rtp_timeout_hold.unwrap_or(RtpTimeout::ThreeHundred)),
+            // 未指定の場合は現在の認証方式 / realm を維持します
+            auth_type: account.auth_type,
+            realm: account.realm,
+            // 未指定の場合は現在の TLS / DTLS のオプションを維持します
+            tls: account.tls,
+            dtls: account.dtls,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PjsipDeleteAccount {
     pub account_id: String,
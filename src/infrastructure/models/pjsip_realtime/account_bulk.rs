@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::infrastructure::models::pjsip_realtime::account::PjsipRealtimeAccount;
+
+// アカウントの一括登録 (POST /accounts/bulk)
+// id を指定した項目は accounts_with_id と同様に扱い、未指定の場合は ULID を採番します。
+
+pub const MAX_BULK_ACCOUNTS: usize = 1000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkCreateMode {
+    // 1 トランザクションで登録し、1 件でも失敗した場合はすべて取り消します
+    #[default]
+    Atomic,
+    // 1 件ずつ登録し、項目ごとの結果を返します
+    BestEffort,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct BulkCreateParams {
+    #[serde(default)]
+    pub mode: BulkCreateMode,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct BulkPjsipRealtimeAccount {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(flatten)]
+    pub account: PjsipRealtimeAccount,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BulkCreateResult {
+    pub index: usize,
+    pub status: u16,
+    pub username: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    // problem+json と同じ形式のエラー
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BulkCreateResponse {
+    pub mode: BulkCreateMode,
+    pub created: usize,
+    pub failed: usize,
+    pub results: Vec<BulkCreateResult>,
+}
+
+impl BulkCreateResponse {
+    pub fn new(mode: BulkCreateMode, results: Vec<BulkCreateResult>) -> Self {
+        let created: usize = results.iter().filter(|r| r.error.is_none()).count();
+        BulkCreateResponse {
+            mode,
+            created,
+            failed: results.len() - created,
+            results,
+        }
+    }
+}
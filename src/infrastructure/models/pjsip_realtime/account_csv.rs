@@ -0,0 +1,267 @@
+use std::str::FromStr;
+
+use csv::StringRecord;
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::models::errors::validation_error::{FieldError, ValidationErrors};
+use crate::infrastructure::models::pjsip_realtime::account::PjsipRealtimeAccount;
+use crate::infrastructure::models::pjsip_realtime::account_bulk::BulkPjsipRealtimeAccount;
+use crate::infrastructure::models::pjsip_realtime::enums::{
+    pjsip_auth_enums::AuthType,
+    pjsip_endpoint_enums::{RtpTimeout, TransportType},
+};
+use crate::infrastructure::models::pjsip_realtime::pjsip_conf::{
+    PJSIP_CONF_CONTENT_TYPE, PJSIP_CONF_EXPORT_FILE_NAME,
+};
+
+// アカウントの CSV 入出力 (GET /accounts/export, POST /accounts/import)
+// エクスポートした CSV に password を記入すればそのままインポートできます。
+// パスワードはエクスポートしません (列は空になります)。
+
+pub const CSV_COLUMNS: [&str; 11] = [
+    "id",
+    "username",
+    "password",
+    "transport",
+    "context",
+    "from_domain",
+    "from_user",
+    "rtp_timeout",
+    "rtp_timeout_hold",
+    "auth_type",
+    "realm",
+];
+pub const REQUIRED_CSV_COLUMNS: [&str; 6] = [
+    "username",
+    "password",
+    "transport",
+    "context",
+    "from_domain",
+    "from_user",
+];
+pub const CSV_CONTENT_TYPE: &str = "text/csv; charset=utf-8";
+pub const CSV_EXPORT_FILE_NAME: &str = "pjsip_accounts.csv";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    PjsipConf, // pjsip_conf::render_pjsip_conf
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => CSV_CONTENT_TYPE,
+            ExportFormat::PjsipConf => PJSIP_CONF_CONTENT_TYPE,
+        }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => CSV_EXPORT_FILE_NAME,
+            ExportFormat::PjsipConf => PJSIP_CONF_EXPORT_FILE_NAME,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ExportParams {
+    #[serde(default)]
+    pub format: ExportFormat,
+    // カンマ区切りのアカウント ID (未指定の場合は全件)
+    #[serde(default)]
+    pub ids: Option<String>,
+    // true の場合は password / md5_cred を出力します (pjsip_conf のみ、RevealCredentials が必要)
+    #[serde(default)]
+    pub include_secrets: bool,
+}
+
+impl ExportParams {
+    pub fn id_list(&self) -> Option<Vec<String>> {
+        self.ids.as_ref().map(|ids| {
+            ids.split(',')
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(String::from)
+                .collect()
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ImportParams {
+    // true の場合は検証と重複の確認のみ行い、登録しません
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+// エクスポートする 1 行 (ps_endpoints / ps_auths の値を含む)
+#[derive(Clone, Debug)]
+pub struct PjsipRealtimeAccountExport {
+    pub id: String,
+    pub username: String,
+    pub transport: TransportType,
+    pub context: String,
+    pub from_domain: String,
+    pub from_user: String,
+    pub rtp_timeout: Option<RtpTimeout>,
+    pub rtp_timeout_hold: Option<RtpTimeout>,
+    pub auth_type: Option<AuthType>,
+    pub realm: Option<String>,
+}
+
+impl PjsipRealtimeAccountExport {
+    // CSV_COLUMNS の順
+    pub fn to_csv_record(&self) -> [String; 11] {
+        [
+            self.id.clone(),
+            self.username.clone(),
+            String::new(),
+            self.transport.to_string(),
+            self.context.clone(),
+            self.from_domain.clone(),
+            self.from_user.clone(),
+            self.rtp_timeout
+                .map(|v| v.as_i32().to_string())
+                .unwrap_or_default(),
+            self.rtp_timeout_hold
+                .map(|v| v.as_i32().to_string())
+                .unwrap_or_default(),
+            self.auth_type
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+            self.realm.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+// インポート時の行ごとのエラー (row はヘッダーを 1 行目とした CSV の行番号)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CsvRowError {
+    pub row: usize,
+    pub field: String,
+    pub message: String,
+}
+
+impl CsvRowError {
+    pub fn new(row: usize, error: FieldError) -> Self {
+        CsvRowError {
+            row,
+            field: error.field,
+            message: error.message,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ImportedAccount {
+    pub row: usize,
+    pub id: String,
+    pub username: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub total: usize,
+    pub valid: usize,
+    pub invalid: usize,
+    pub errors: Vec<CsvRowError>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub created: Vec<ImportedAccount>,
+}
+
+// ヘッダーの確認 (未知の列・必須列の欠落はファイル全体のエラー)
+pub fn check_csv_headers(headers: &StringRecord) -> Result<(), String> {
+    if let Some(unknown) = headers.iter().find(|h| !CSV_COLUMNS.contains(h)) {
+        return Err(format!(
+            "Unknown column: {} (expected {})",
+            unknown,
+            CSV_COLUMNS.join(", ")
+        ));
+    }
+    let missing: Vec<&str> = REQUIRED_CSV_COLUMNS
+        .iter()
+        .filter(|column| !headers.iter().any(|h| h == **column))
+        .copied()
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("Missing columns: {}", missing.join(", ")));
+    }
+    Ok(())
+}
+
+// 1 行を PjsipRealtimeAccount に変換します。
+// 値の形式 (transport / rtp_timeout / auth_type) のエラーは項目ごとに返します。
+pub fn parse_csv_record(
+    headers: &StringRecord,
+    record: &StringRecord,
+) -> Result<BulkPjsipRealtimeAccount, ValidationErrors> {
+    let value = |column: &str| -> Option<&str> {
+        headers
+            .iter()
+            .position(|h| h == column)
+            .and_then(|i| record.get(i))
+            .filter(|v| !v.is_empty())
+    };
+    let text = |column: &str| -> String { value(column).unwrap_or_default().to_string() };
+
+    let mut errors = ValidationErrors::new();
+    let transport: Option<TransportType> = match value("transport") {
+        Some(v) => TransportType::from_str(v)
+            .map_err(|_| errors.add("transport", "must be one of udp, tcp, tls, ws, wss"))
+            .ok(),
+        None => {
+            errors.add("transport", "must not be empty");
+            None
+        }
+    };
+    let rtp_timeout: Option<RtpTimeout> = parse_rtp_timeout(value("rtp_timeout"))
+        .map_err(|e| errors.add("rtp_timeout", e))
+        .unwrap_or_default();
+    let rtp_timeout_hold: Option<RtpTimeout> = parse_rtp_timeout(value("rtp_timeout_hold"))
+        .map_err(|e| errors.add("rtp_timeout_hold", e))
+        .unwrap_or_default();
+    let auth_type: Option<AuthType> = match value("auth_type") {
+        Some(v) => AuthType::from_str(v)
+            .map_err(|_| errors.add("auth_type", "must be userpass or md5"))
+            .ok(),
+        None => None,
+    };
+
+    let Some(transport) = transport else {
+        return Err(errors);
+    };
+    errors.into_result()?;
+    Ok(BulkPjsipRealtimeAccount {
+        id: value("id").map(String::from),
+        account: PjsipRealtimeAccount {
+            username: text("username"),
+            password: text("password"),
+            transport,
+            context: text("context"),
+            from_domain: text("from_domain"),
+            from_user: text("from_user"),
+            rtp_timeout,
+            rtp_timeout_hold,
+            auth_type,
+            realm: value("realm").map(String::from),
+            tls: None,
+            dtls: None,
+        },
+    })
+}
+
+fn parse_rtp_timeout(value: Option<&str>) -> Result<Option<RtpTimeout>, String> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    value
+        .parse::<i32>()
+        .map_err(|_| format!("must be a number: {}", value))
+        .and_then(RtpTimeout::try_from)
+        .map(Some)
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::models::pjsip_realtime::{
+    account::PjsipRealtimeAccountResponse,
+    enums::{
+        pjsip_auth_enums::AuthType,
+        pjsip_endpoint_enums::{DtlsFingerprint, DtlsSetup, DtmfMode, MediaEncryption, RtpTimeout},
+        pjsip_realtime_common_enums::TurnOnOff,
+    },
+};
+
+// 1アカウント分の pjsip_realtime_accounts + ps_endpoints + ps_aors + ps_auths の結合ビュー
+// ps_* 側はトランスポートごとに登録するカラムが異なるため、すべて Option で受けます。
+// 対応する ps_* の行が存在しない場合は endpoint / aor / auth 自体が None になります。
+// パスワードは含めません。
+// registered は ps_contacts に有効期限内の contact があるかどうかです。
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PjsipRealtimeAccountDetail {
+    #[serde(flatten)]
+    pub account: PjsipRealtimeAccountResponse,
+    pub registered: bool,
+    pub endpoint: Option<PsEndpointDetail>,
+    pub aor: Option<PsAorDetail>,
+    pub auth: Option<PsAuthDetail>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PsEndpointDetail {
+    pub id: String,
+    pub transport: Option<String>, // トランスポート名 (TLS は任意の名前を設定できます)
+    pub aors: Option<String>,
+    pub auth: Option<String>,
+    pub context: Option<String>,
+    pub disallow: Option<String>,
+    pub allow: Option<String>,
+    pub direct_media: Option<TurnOnOff>,
+    pub dtmf_mode: Option<DtmfMode>,
+    pub force_rport: Option<TurnOnOff>,
+    pub rewrite_contact: Option<TurnOnOff>,
+    pub rtp_ipv6: Option<TurnOnOff>,
+    pub rtp_symmetric: Option<TurnOnOff>,
+    pub media_encryption: Option<MediaEncryption>,
+    pub media_encryption_optimistic: Option<TurnOnOff>,
+    pub from_domain: Option<String>,
+    pub from_user: Option<String>,
+    pub ice_support: Option<TurnOnOff>,
+    pub use_avpf: Option<TurnOnOff>,
+    pub webrtc: Option<TurnOnOff>,
+    pub max_audio_streams: Option<i32>,
+    pub max_video_streams: Option<i32>,
+    pub rtp_timeout: Option<RtpTimeout>,
+    pub rtp_timeout_hold: Option<RtpTimeout>,
+    pub dtls_verify: Option<String>,
+    pub dtls_setup: Option<DtlsSetup>,
+    pub dtls_fingerprint: Option<DtlsFingerprint>,
+    pub dtls_auto_generate_cert: Option<TurnOnOff>,
+    pub rtcp_mux: Option<TurnOnOff>,
+    pub bundle: Option<TurnOnOff>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PsAorDetail {
+    pub id: String,
+    pub default_expiration: Option<i32>,
+    pub max_contacts: Option<i32>,
+    pub minimum_expiration: Option<i32>,
+    pub maximum_expiration: Option<i32>,
+    pub qualify_frequency: Option<i32>,
+    pub qualify_timeout: Option<f64>,
+    pub remove_existing: Option<TurnOnOff>,
+    pub remove_unavailable: Option<TurnOnOff>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PsAuthDetail {
+    pub id: String,
+    pub auth_type: Option<AuthType>,
+    pub realm: Option<String>,
+    pub username: Option<String>,
+}
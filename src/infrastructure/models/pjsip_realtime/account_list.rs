@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::models::pjsip_realtime::enums::pjsip_endpoint_enums::TransportType;
+
+// アカウント一覧 (GET /accounts) の検索条件とページ
+// cursor には前ページ最後のアカウント ID (ULID) を指定します。
+
+pub const DEFAULT_PAGE_LIMIT: i64 = 50;
+pub const MAX_PAGE_LIMIT: i64 = 500;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum AccountSort {
+    #[default]
+    #[serde(rename = "-created_at")]
+    CreatedAtDesc,
+    #[serde(rename = "created_at")]
+    CreatedAtAsc,
+    #[serde(rename = "username")]
+    UsernameAsc,
+    #[serde(rename = "-username")]
+    UsernameDesc,
+}
+
+impl AccountSort {
+    // ORDER BY / keyset 条件に使うカラム (id は同値時の並び順を固定するため)
+    pub fn column(&self) -> &'static str {
+        match self {
+            AccountSort::CreatedAtDesc | AccountSort::CreatedAtAsc => "created_at",
+            AccountSort::UsernameAsc | AccountSort::UsernameDesc => "username",
+        }
+    }
+
+    pub fn is_descending(&self) -> bool {
+        matches!(self, AccountSort::CreatedAtDesc | AccountSort::UsernameDesc)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AccountListParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transport: Option<TransportType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username_prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_from: Option<chrono::DateTime<chrono::Utc>>, // inclusive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_to: Option<chrono::DateTime<chrono::Utc>>, // exclusive
+    #[serde(default)]
+    pub sort: AccountSort,
+}
+
+impl AccountListParams {
+    pub fn page_limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_PAGE_LIMIT)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AccountPage<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>, // None when this is the last page
+    pub total: i64,                  // number of accounts matching the filters
+}
@@ -0,0 +1,426 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use ulid::Ulid;
+
+use crate::config::AppConfig;
+use crate::infrastructure::models::errors::validation_error::ValidationErrors;
+use crate::infrastructure::models::pjsip_realtime::account::{
+    PjsipRealtimeAccount, PjsipRealtimeAccountPatch, PjsipRealtimeAccountWithId,
+};
+use crate::infrastructure::models::pjsip_realtime::enums::pjsip_auth_enums::AuthType;
+use crate::infrastructure::models::pjsip_realtime::enums::pjsip_endpoint_enums::TransportType;
+use crate::infrastructure::models::pjsip_realtime::sip_tls::TlsAccountOptions;
+use crate::infrastructure::models::pjsip_realtime::sip_ws::{
+    DTLS_VERIFY_VALUES, DtlsAccountOptions,
+};
+
+// pjsip_realtime_accounts のカラム長
+pub const USERNAME_MAX_LENGTH: usize = 50;
+pub const FROM_USER_MAX_LENGTH: usize = 50;
+pub const CONTEXT_MAX_LENGTH: usize = 100;
+pub const PASSWORD_MAX_LENGTH: usize = 255;
+pub const PASSWORD_MIN_LENGTH: usize = 8;
+// ホスト名の最大長 (RFC 1035)
+const HOSTNAME_MAX_LENGTH: usize = 253;
+const HOSTNAME_LABEL_MAX_LENGTH: usize = 63;
+
+// SIP URI の user 部で使用できる記号 (RFC 3261 unreserved / user-unreserved)
+// '%' は %HH のエスケープとしてのみ使用できます。
+const SIP_USER_SYMBOLS: &str = "-_.!~*'()&=+$,;?/";
+
+// password の確認内容
+#[derive(Clone, Copy, PartialEq)]
+enum PasswordCheck {
+    // 長さ・文字種・username との一致を含めて確認します (API からの登録)
+    Strength,
+    // 保存できる形式かどうかのみ確認します (既存の pjsip.conf の取り込み)
+    Format,
+    // 確認しません (md5_cred のみの auth の取り込み)
+    Skip,
+}
+
+// アカウント作成時の検証
+// すべての項目を検証し、エラーをまとめて返します。
+// auth_type / realm が未指定の場合はサーバー設定の既定値で md5 の realm を確認します。
+pub fn validate_account(
+    config: &AppConfig,
+    account_id: Option<&str>,
+    account: &PjsipRealtimeAccount,
+) -> Result<(), ValidationErrors> {
+    check_account(config, account_id, account, PasswordCheck::Strength)
+}
+
+// pjsip.conf の取り込み時の検証
+// 取り込むパスワードは稼働中の端末に設定済みのため、強度の規則は適用しません。
+// md5_cred のみの auth は平文のパスワードがないため、password を確認しません。
+pub fn validate_imported_account(
+    config: &AppConfig,
+    account: &PjsipRealtimeAccount,
+    has_md5_cred: bool,
+) -> Result<(), ValidationErrors> {
+    let password_check: PasswordCheck = if has_md5_cred {
+        PasswordCheck::Skip
+    } else {
+        PasswordCheck::Format
+    };
+    check_account(config, None, account, password_check)
+}
+
+fn check_account(
+    config: &AppConfig,
+    account_id: Option<&str>,
+    account: &PjsipRealtimeAccount,
+    password_check: PasswordCheck,
+) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    if let Some(account_id) = account_id {
+        check(&mut errors, "id", check_account_id(account_id));
+    }
+    check(
+        &mut errors,
+        "username",
+        check_sip_user(&account.username, USERNAME_MAX_LENGTH),
+    );
+    match password_check {
+        PasswordCheck::Strength => check(
+            &mut errors,
+            "password",
+            check_password(&account.password)
+                .and_then(|_| check_password_strength(&account.password))
+                .and_then(|_| check_password_not_username(&account.password, &account.username)),
+        ),
+        PasswordCheck::Format => check(&mut errors, "password", check_password(&account.password)),
+        PasswordCheck::Skip => {}
+    }
+    check(&mut errors, "context", check_context(&account.context));
+    check(
+        &mut errors,
+        "from_domain",
+        check_domain(&account.from_domain),
+    );
+    check(
+        &mut errors,
+        "from_user",
+        check_sip_user(&account.from_user, FROM_USER_MAX_LENGTH),
+    );
+    if let Some(auth_type) = &account.auth_type {
+        check(&mut errors, "auth_type", check_auth_type(auth_type));
+    }
+    if let Some(realm) = &account.realm {
+        check(&mut errors, "realm", check_realm(realm));
+    }
+    let auth_type: &AuthType = account
+        .auth_type
+        .as_ref()
+        .unwrap_or(&config.default_auth_type);
+    if *auth_type == AuthType::Md5 && account.realm.is_none() && config.default_realm.is_none() {
+        errors.add("realm", "is required for md5 credentials");
+    }
+    check_transport_options(
+        &mut errors,
+        &account.transport,
+        account.tls.as_ref(),
+        account.dtls.as_ref(),
+    );
+    match account.transport {
+        TransportType::Tls => check_tls_options(&mut errors, account.tls.as_ref()),
+        TransportType::Ws | TransportType::Wss => {
+            check_dtls_options(&mut errors, account.dtls.as_ref())
+        }
+        TransportType::Udp | TransportType::Tcp => {}
+    }
+    errors.into_result()
+}
+
+// アカウント更新時の検証 (指定された項目のみ)
+// md5 の realm や password の要否は現在の認証情報に依存するため、更新処理で確認します。
+pub fn validate_account_patch(patch: &PjsipRealtimeAccountPatch) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    if let Some(username) = &patch.username {
+        check(
+            &mut errors,
+            "username",
+            check_sip_user(username, USERNAME_MAX_LENGTH),
+        );
+    }
+    if let Some(password) = &patch.password {
+        check(
+            &mut errors,
+            "password",
+            check_password(password).and_then(|_| check_password_strength(password)),
+        );
+    }
+    if let Some(context) = &patch.context {
+        check(&mut errors, "context", check_context(context));
+    }
+    if let Some(from_domain) = &patch.from_domain {
+        check(&mut errors, "from_domain", check_domain(from_domain));
+    }
+    if let Some(from_user) = &patch.from_user {
+        check(
+            &mut errors,
+            "from_user",
+            check_sip_user(from_user, FROM_USER_MAX_LENGTH),
+        );
+    }
+    if let Some(auth_type) = &patch.auth_type {
+        check(&mut errors, "auth_type", check_auth_type(auth_type));
+    }
+    if let Some(realm) = &patch.realm {
+        check(&mut errors, "realm", check_realm(realm));
+    }
+    // transport を変更しない場合は現在の transport に依存するため、更新処理で確認します
+    if let Some(transport) = &patch.transport {
+        check_transport_options(
+            &mut errors,
+            transport,
+            patch.tls.as_ref(),
+            patch.dtls.as_ref(),
+        );
+    }
+    check_tls_options(&mut errors, patch.tls.as_ref());
+    check_dtls_options(&mut errors, patch.dtls.as_ref());
+    errors.into_result()
+}
+
+// patch を適用した後のアカウントで、現在の値に依存する項目を確認します
+// - TLS / DTLS のオプションは更新後の transport で確認します
+// - password は更新後の username と比較します (平文を保存していない md5 で password 未指定の場合を除く)
+pub fn validate_updated_account(
+    patch: &PjsipRealtimeAccountPatch,
+    updated: &PjsipRealtimeAccountWithId,
+) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    check_transport_options(
+        &mut errors,
+        &updated.transport,
+        patch.tls.as_ref(),
+        patch.dtls.as_ref(),
+    );
+    if (patch.password.is_some() || patch.username.is_some())
+        && let Some(password) = &updated.password
+    {
+        check(
+            &mut errors,
+            "password",
+            check_password_not_username(password, &updated.username),
+        );
+    }
+    errors.into_result()
+}
+
+// TLS / DTLS のオプションは対応する transport のアカウントにのみ指定できます
+fn check_transport_options(
+    errors: &mut ValidationErrors,
+    transport: &TransportType,
+    tls: Option<&TlsAccountOptions>,
+    dtls: Option<&DtlsAccountOptions>,
+) {
+    if tls.is_some() && *transport != TransportType::Tls {
+        errors.add("tls", "only allowed for transport tls");
+    }
+    if dtls.is_some() && !matches!(transport, TransportType::Ws | TransportType::Wss) {
+        errors.add("dtls", "only allowed for transport ws or wss");
+    }
+}
+
+fn check_tls_options(errors: &mut ValidationErrors, tls: Option<&TlsAccountOptions>) {
+    let empty_transport_name: bool = tls
+        .and_then(|tls| tls.transport_name.as_ref())
+        .is_some_and(|name| name.trim().is_empty());
+    if empty_transport_name {
+        errors.add("tls.transport_name", "must not be empty");
+    }
+}
+
+fn check_dtls_options(errors: &mut ValidationErrors, dtls: Option<&DtlsAccountOptions>) {
+    let invalid_dtls_verify: bool = dtls
+        .and_then(|dtls| dtls.dtls_verify.as_ref())
+        .is_some_and(|verify| !DTLS_VERIFY_VALUES.contains(&verify.as_str()));
+    if invalid_dtls_verify {
+        errors.add(
+            "dtls.dtls_verify",
+            format!("must be one of {}", DTLS_VERIFY_VALUES.join(", ")),
+        );
+    }
+}
+
+pub fn check(errors: &mut ValidationErrors, field: &str, result: Result<(), String>) {
+    if let Err(message) = result {
+        errors.add(field, message);
+    }
+}
+
+// google_oauth は ps_auths の enum にありますが、このサーバーでは登録できません
+fn check_auth_type(auth_type: &AuthType) -> Result<(), String> {
+    match auth_type {
+        AuthType::Userpass | AuthType::Md5 => Ok(()),
+        AuthType::GoogleOauth => Err(String::from("must be userpass or md5")),
+    }
+}
+
+fn check_realm(realm: &str) -> Result<(), String> {
+    if realm.trim().is_empty() {
+        return Err(String::from("must not be empty"));
+    }
+    Ok(())
+}
+
+// ULID (26 文字) または UUID (8-4-4-4-12 の 16 進数)
+fn check_account_id(account_id: &str) -> Result<(), String> {
+    if account_id.is_empty() {
+        return Err(String::from("must not be empty"));
+    }
+    if Ulid::from_string(account_id).is_ok() || is_uuid(account_id) {
+        return Ok(());
+    }
+    Err(String::from("must be a ULID or UUID"))
+}
+
+fn is_uuid(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && group.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+pub fn check_sip_user(value: &str, max_length: usize) -> Result<(), String> {
+    if value.is_empty() {
+        return Err(String::from("must not be empty"));
+    }
+    if value.chars().count() > max_length {
+        return Err(format!("must be at most {} characters", max_length));
+    }
+    let bytes = value.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c == b'%' {
+            let escaped = bytes.get(i + 1..i + 3);
+            if !escaped.is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit)) {
+                return Err(String::from("'%' must be followed by two hex digits"));
+            }
+            i += 3;
+            continue;
+        }
+        if !c.is_ascii_alphanumeric() && !SIP_USER_SYMBOLS.as_bytes().contains(&c) {
+            let invalid: char = value[i..].chars().next().unwrap_or_default();
+            return Err(format!(
+                "contains {:?}, which is not allowed in a SIP user",
+                invalid
+            ));
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+// 保存できる形式かどうか (ps_auths.password のカラム長と制御文字)
+fn check_password(password: &str) -> Result<(), String> {
+    if password.is_empty() {
+        return Err(String::from("must not be empty"));
+    }
+    if password.chars().count() > PASSWORD_MAX_LENGTH {
+        return Err(format!(
+            "must be at most {} characters",
+            PASSWORD_MAX_LENGTH
+        ));
+    }
+    if password.chars().any(char::is_control) {
+        return Err(String::from("must not contain control characters"));
+    }
+    Ok(())
+}
+
+fn check_password_strength(password: &str) -> Result<(), String> {
+    if password.chars().count() < PASSWORD_MIN_LENGTH {
+        return Err(format!(
+            "must be at least {} characters",
+            PASSWORD_MIN_LENGTH
+        ));
+    }
+    let classes: usize = [
+        password.chars().any(|c| c.is_ascii_lowercase()),
+        password.chars().any(|c| c.is_ascii_uppercase()),
+        password.chars().any(|c| c.is_ascii_digit()),
+        password.chars().any(|c| !c.is_ascii_alphanumeric()),
+    ]
+    .iter()
+    .filter(|present| **present)
+    .count();
+    if classes < 2 {
+        return Err(String::from(
+            "must contain at least two of lowercase letters, uppercase letters, digits and symbols",
+        ));
+    }
+    Ok(())
+}
+
+fn check_password_not_username(password: &str, username: &str) -> Result<(), String> {
+    if username.eq_ignore_ascii_case(password) {
+        return Err(String::from("must not be the same as the username"));
+    }
+    Ok(())
+}
+
+pub fn check_context(context: &str) -> Result<(), String> {
+    if context.trim().is_empty() {
+        return Err(String::from("must not be empty"));
+    }
+    if context.chars().count() > CONTEXT_MAX_LENGTH {
+        return Err(format!("must be at most {} characters", CONTEXT_MAX_LENGTH));
+    }
+    if context.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(String::from("must not contain whitespace"));
+    }
+    Ok(())
+}
+
+// ホスト名、IPv4 または IPv6 ("[::1]" 形式も可)
+pub fn check_domain(domain: &str) -> Result<(), String> {
+    if domain.is_empty() {
+        return Err(String::from("must not be empty"));
+    }
+    let bracketed: Option<&str> = domain
+        .strip_prefix('[')
+        .and_then(|inner| inner.strip_suffix(']'));
+    if let Some(inner) = bracketed {
+        return inner
+            .parse::<Ipv6Addr>()
+            .map(|_| ())
+            .map_err(|_| String::from("must be a valid IPv6 address"));
+    }
+    if domain.parse::<Ipv4Addr>().is_ok() || domain.parse::<Ipv6Addr>().is_ok() {
+        return Ok(());
+    }
+    if domain.len() > HOSTNAME_MAX_LENGTH {
+        return Err(format!(
+            "must be at most {} characters",
+            HOSTNAME_MAX_LENGTH
+        ));
+    }
+    let labels: Vec<&str> = domain.split('.').collect();
+    // 数字だけの最後のラベルは IPv4 の誤記とみなします (例: 192.168.0.256)
+    if labels
+        .last()
+        .is_some_and(|label| label.chars().all(|c| c.is_ascii_digit()))
+    {
+        return Err(String::from("must be a valid hostname or IP address"));
+    }
+    // DNS 名として '_' も許容します
+    let valid_labels: bool = labels.iter().all(|label| {
+        !label.is_empty()
+            && label.len() <= HOSTNAME_LABEL_MAX_LENGTH
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    });
+    if !valid_labels {
+        return Err(String::from("must be a valid hostname or IP address"));
+    }
+    Ok(())
+}
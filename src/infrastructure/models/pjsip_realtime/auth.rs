@@ -0,0 +1,51 @@
+use md5::{Digest, Md5};
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::models::pjsip_realtime::enums::pjsip_auth_enums::AuthType;
+
+// ps_auths に保存する認証情報
+// userpass: password を平文で保存します。realm は任意です (未設定の場合は Asterisk の default_realm)。
+// md5: md5_cred (MD5("username:realm:password")) と realm のみを保存し、平文は保存しません。
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuthCredentials {
+    pub auth_type: AuthType,
+    pub password: Option<String>,
+    pub md5_cred: Option<String>,
+    pub realm: Option<String>,
+}
+
+impl AuthCredentials {
+    pub fn userpass(password: &str, realm: Option<&str>) -> Self {
+        AuthCredentials {
+            auth_type: AuthType::Userpass,
+            password: Some(password.to_string()),
+            md5_cred: None,
+            realm: realm.map(str::to_string),
+        }
+    }
+
+    pub fn md5(username: &str, realm: &str, password: &str) -> Self {
+        AuthCredentials {
+            auth_type: AuthType::Md5,
+            password: None,
+            md5_cred: Some(md5_cred(username, realm, password)),
+            realm: Some(realm.to_string()),
+        }
+    }
+
+    // 計算済みの md5_cred (pjsip.conf の取り込みなど) から md5 の認証情報を作成します。
+    pub fn md5_from_cred(md5_cred: &str, realm: &str) -> Self {
+        AuthCredentials {
+            auth_type: AuthType::Md5,
+            password: None,
+            md5_cred: Some(md5_cred.to_string()),
+            realm: Some(realm.to_string()),
+        }
+    }
+}
+
+// RFC 2617 の HA1 (Asterisk の md5_cred と同じ形式: 小文字 16 進 32 文字)
+pub fn md5_cred(username: &str, realm: &str, password: &str) -> String {
+    let digest = Md5::digest(format!("{}:{}:{}", username, realm, password).as_bytes());
+    format!("{:x}", digest)
+}
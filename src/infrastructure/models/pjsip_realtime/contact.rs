@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+// Asterisk が REGISTER を受けて登録する ps_contacts の行
+// id は "{aor};@{hash}" ですが、realtime では ';' を "^3B" にエスケープして "{aor}^3B@{hash}" で保存されます。
+// 有効期限 (expiration_time) は UNIX 時刻 (秒) です。
+// 有効期限を過ぎた行は登録されていないものとして扱います。
+// qualify の RTT は Asterisk のメモリ上にのみ保持され、ps_contacts には書き込まれません。
+
+// GET /accounts/{account_id}/contacts のレスポンス
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PsContactResponse {
+    pub id: String,
+    pub uri: Option<String>,
+    pub user_agent: Option<String>,
+    pub expiration_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_in: Option<i64>, // 有効期限までの秒数
+    pub via_addr: Option<String>,
+    pub via_port: Option<i32>,
+    pub qualify_frequency: Option<i32>,
+    pub qualify_timeout: Option<f64>,
+    pub reg_server: Option<String>,
+}
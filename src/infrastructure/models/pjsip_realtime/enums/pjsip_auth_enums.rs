@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
 #[sqlx(type_name = "auth_type", rename_all = "lowercase")]
 pub enum AuthType {
     Userpass,
@@ -18,3 +20,16 @@ impl fmt::Display for AuthType {
         }
     }
 }
+
+impl FromStr for AuthType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "userpass" => Ok(AuthType::Userpass),
+            "md5" => Ok(AuthType::Md5),
+            "google_oauth" => Ok(AuthType::GoogleOauth),
+            _ => Err(format!("Invalid auth type: {}", s)),
+        }
+    }
+}
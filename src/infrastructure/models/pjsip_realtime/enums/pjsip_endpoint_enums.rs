@@ -1,5 +1,6 @@
 use serde::{Deserialize, Deserializer, Serialize};
 use std::fmt;
+use std::str::FromStr;
 
 // PJSIP Transport Type
 #[derive(Debug, Clone, PartialEq, Serialize, sqlx::Type)]
@@ -26,6 +27,21 @@ impl fmt::Display for TransportType {
     }
 }
 
+impl FromStr for TransportType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "udp" => Ok(TransportType::Udp),
+            "tcp" => Ok(TransportType::Tcp),
+            "tls" => Ok(TransportType::Tls),
+            "ws" => Ok(TransportType::Ws),
+            "wss" => Ok(TransportType::Wss),
+            _ => Err(format!("Invalid transport type: {}", s)),
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for TransportType {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -107,6 +123,21 @@ impl fmt::Display for DtmfMode {
     }
 }
 
+impl FromStr for DtmfMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rfc4733" => Ok(DtmfMode::Rfc4733),
+            "inband" => Ok(DtmfMode::Inband),
+            "info" => Ok(DtmfMode::Info),
+            "auto" => Ok(DtmfMode::Auto),
+            "auto_info" => Ok(DtmfMode::Autoinfo),
+            _ => Err(format!("Invalid dtmf mode: {}", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "timers", rename_all = "snake_case")]
 pub enum Timers {
@@ -178,6 +209,7 @@ impl fmt::Display for HundredRel {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
 #[sqlx(type_name = "media_encryption", rename_all = "snake_case")]
 pub enum MediaEncryption {
     No,
@@ -195,6 +227,19 @@ impl fmt::Display for MediaEncryption {
     }
 }
 
+impl FromStr for MediaEncryption {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "no" => Ok(MediaEncryption::No),
+            "sdes" => Ok(MediaEncryption::Sdes),
+            "dtls" => Ok(MediaEncryption::Dtls),
+            _ => Err(format!("Invalid media encryption: {}", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "t38_udptl_ec", rename_all = "snake_case")]
 pub enum T38UdptlEc {
@@ -214,6 +259,7 @@ impl fmt::Display for T38UdptlEc {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
 #[sqlx(type_name = "ENUM", rename_all = "snake_case")]
 pub enum DtlsSetup {
     Active,
@@ -231,11 +277,26 @@ impl fmt::Display for DtlsSetup {
     }
 }
 
+impl FromStr for DtlsSetup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "active" => Ok(DtlsSetup::Active),
+            "passive" => Ok(DtlsSetup::Passive),
+            "actpass" => Ok(DtlsSetup::Actpass),
+            _ => Err(format!("Invalid DTLS setup: {}", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "dtls_fingerprint")]
 pub enum DtlsFingerprint {
+    #[serde(rename = "SHA-1")]
     #[sqlx(rename = "SHA-1")]
     Sha1,
+    #[serde(rename = "SHA-256")]
     #[sqlx(rename = "SHA-256")]
     Sha256,
 }
@@ -249,6 +310,18 @@ impl fmt::Display for DtlsFingerprint {
     }
 }
 
+impl FromStr for DtlsFingerprint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "SHA-1" => Ok(DtlsFingerprint::Sha1),
+            "SHA-256" => Ok(DtlsFingerprint::Sha256),
+            _ => Err(format!("Invalid DTLS fingerprint: {}", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "redirect_method", rename_all = "snake_case")]
 pub enum RedirectMethod {
@@ -356,6 +429,14 @@ impl<'de> Deserialize<'de> for RtpTimeout {
         D: Deserializer<'de>,
     {
         let value = i32::deserialize(deserializer)?;
+        RtpTimeout::try_from(value).map_err(serde::de::Error::custom)
+    }
+}
+
+impl TryFrom<i32> for RtpTimeout {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
         match value {
             0 => Ok(RtpTimeout::Zero),
             15 => Ok(RtpTimeout::Fifteen),
@@ -366,10 +447,10 @@ impl<'de> Deserialize<'de> for RtpTimeout {
             180 => Ok(RtpTimeout::OneEighty),
             300 => Ok(RtpTimeout::ThreeHundred),
             600 => Ok(RtpTimeout::SixHundred),
-            _ => Err(serde::de::Error::custom(format!(
+            _ => Err(format!(
                 "Invalid RTP timeout value: {}. Valid values are: 0, 15, 30, 60, 90, 120, 180, 300, 600",
                 value
-            ))),
+            )),
         }
     }
 }
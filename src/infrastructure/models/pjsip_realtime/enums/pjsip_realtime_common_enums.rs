@@ -1,12 +1,14 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
 #[sqlx(type_name = "turn_on_off", rename_all = "lowercase")]
 pub enum TurnOnOff {
-    #[serde(alias = "0")]
+    #[serde(rename = "0")]
     Zero,
-    #[serde(alias = "1")]
+    #[serde(rename = "1")]
     One,
     Off,
     On,
@@ -31,3 +33,21 @@ impl fmt::Display for TurnOnOff {
         write!(f, "{}", s)
     }
 }
+
+impl FromStr for TurnOnOff {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "0" => Ok(TurnOnOff::Zero),
+            "1" => Ok(TurnOnOff::One),
+            "off" => Ok(TurnOnOff::Off),
+            "on" => Ok(TurnOnOff::On),
+            "false" => Ok(TurnOnOff::False),
+            "true" => Ok(TurnOnOff::True),
+            "no" => Ok(TurnOnOff::No),
+            "yes" => Ok(TurnOnOff::Yes),
+            _ => Err(format!("Invalid turn_on_off value: {}", s)),
+        }
+    }
+}
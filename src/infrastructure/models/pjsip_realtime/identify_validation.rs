@@ -0,0 +1,97 @@
+use crate::infrastructure::models::errors::validation_error::ValidationErrors;
+use crate::infrastructure::models::pjsip_realtime::account_validation::{check, check_domain};
+use crate::infrastructure::models::pjsip_realtime::sip_identify::{
+    MatchNetwork, PjsipEndpointIdentify, matches_overlap,
+};
+
+// ps_endpoint_id_ips のカラム長
+pub const MATCH_MAX_LENGTH: usize = 80;
+pub const MATCH_HEADER_MAX_LENGTH: usize = 255;
+
+// ps_endpoint_id_ips の登録時の検証
+// match はすべて 1 行の match カラムに保存するため、合計の長さも確認します。
+pub fn validate_identify(identify: &PjsipEndpointIdentify) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    if identify.matches.is_empty() && identify.match_header.is_none() {
+        errors.add("match", "either match or match_header is required");
+    }
+    check_matches(&mut errors, &identify.matches);
+    if identify.matches.join(",").chars().count() > MATCH_MAX_LENGTH {
+        errors.add(
+            "match",
+            format!("must be at most {} characters in total", MATCH_MAX_LENGTH),
+        );
+    }
+    if let Some(match_header) = &identify.match_header {
+        check(
+            &mut errors,
+            "match_header",
+            check_match_header(match_header),
+        );
+    }
+    errors.into_result()
+}
+
+pub fn check_matches(errors: &mut ValidationErrors, matches: &[String]) {
+    for (i, value) in matches.iter().enumerate() {
+        let field: String = format!("match[{}]", i);
+        check(errors, &field, check_match(value));
+        if let Some(j) = matches[..i]
+            .iter()
+            .position(|other| matches_overlap(other, value))
+        {
+            errors.add(&field, format!("overlaps match[{}]", j));
+        }
+    }
+}
+
+// ps_endpoint_id_ips.match (IP アドレス、CIDR またはホスト名)
+fn check_match(value: &str) -> Result<(), String> {
+    if value.is_empty() {
+        return Err(String::from("must not be empty"));
+    }
+    if value.chars().count() > MATCH_MAX_LENGTH {
+        return Err(format!("must be at most {} characters", MATCH_MAX_LENGTH));
+    }
+    if value.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(String::from("must not contain whitespace"));
+    }
+    // "/" を含むもの、または数字と "." / ":" だけのものは IP アドレスとして検証します
+    let looks_like_address: bool = value.contains('/')
+        || value.chars().all(|c| c.is_ascii_digit() || c == '.')
+        || (value.contains(':')
+            && value
+                .chars()
+                .all(|c| c.is_ascii_hexdigit() || c == ':' || c == '.'));
+    if looks_like_address {
+        return value.parse::<MatchNetwork>().map(|_| ());
+    }
+    check_domain(value)
+}
+
+// "Header-Name: value" (ヘッダー名は RFC 3261 の token)
+fn check_match_header(value: &str) -> Result<(), String> {
+    if value.chars().count() > MATCH_HEADER_MAX_LENGTH {
+        return Err(format!(
+            "must be at most {} characters",
+            MATCH_HEADER_MAX_LENGTH
+        ));
+    }
+    if value.chars().any(char::is_control) {
+        return Err(String::from("must not contain control characters"));
+    }
+    let Some((name, header_value)) = value.split_once(':') else {
+        return Err(String::from("must be in the form \"Header-Name: value\""));
+    };
+    let valid_name: bool = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-.!%*_+`'~".contains(c));
+    if !valid_name {
+        return Err(String::from("header name must be a valid SIP header name"));
+    }
+    if header_value.trim().is_empty() {
+        return Err(String::from("header value must not be empty"));
+    }
+    Ok(())
+}
@@ -0,0 +1,991 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::models::pjsip_realtime::account::{
+    PjsipRealtimeAccount, PjsipRealtimeAccountCredentials,
+};
+use crate::infrastructure::models::pjsip_realtime::account_detail::PjsipRealtimeAccountDetail;
+use crate::infrastructure::models::pjsip_realtime::enums::{
+    pjsip_auth_enums::AuthType,
+    pjsip_endpoint_enums::{DtmfMode, MediaEncryption, RtpTimeout, TransportType},
+    pjsip_realtime_common_enums::TurnOnOff,
+};
+use crate::infrastructure::models::pjsip_realtime::{
+    sip_account::PjsipAccountRows,
+    sip_tls::TlsAccountOptions,
+    sip_ws::{DTLS_VERIFY_VALUES, DtlsAccountOptions},
+};
+
+// 静的な pjsip.conf の取り込み (POST /accounts/import/pjsip_conf, CLI: import-pjsip-conf)
+// type=endpoint のセクションごとに、参照している auth / aor をまとめて 1 アカウントとして登録します。
+// - テンプレート ([name](!)) と継承 ([name](tpl1,tpl2))、追記 ([name](+)) に対応します。
+// - auth / aor の ID はエンドポイント名に揃えます (API で作成したアカウントと同じ形)。
+// - 取り込めないオプションとセクションは登録せず、レポートに出力します。
+
+// Asterisk の既定値 (context 未指定の場合)
+pub const DEFAULT_PJSIP_CONF_CONTEXT: &str = "default";
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct PjsipConfImportParams {
+    // true の場合は検証結果のみ返し、登録しません
+    #[serde(default)]
+    pub dry_run: bool,
+    // from_domain が設定されていないエンドポイントに使用します
+    #[serde(default)]
+    pub from_domain: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PjsipConfOption {
+    pub name: String,
+    pub value: String,
+    pub line: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct PjsipConfSection {
+    pub name: String,
+    pub line: usize,
+    pub is_template: bool,
+    pub templates: Vec<String>,
+    pub options: Vec<PjsipConfOption>,
+}
+
+// エラーと取り込めないオプションの報告 (line は pjsip.conf の行番号)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PjsipConfIssue {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    pub line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub option: Option<String>,
+    pub message: String,
+}
+
+impl PjsipConfIssue {
+    fn new(section: Option<&str>, line: usize, option: Option<&str>, message: String) -> Self {
+        PjsipConfIssue {
+            section: section.map(String::from),
+            line,
+            option: option.map(String::from),
+            message,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PjsipConfSkippedSection {
+    pub section: String,
+    pub line: usize,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub section_type: Option<String>,
+    pub reason: String,
+}
+
+// ps_endpoints / ps_aors の既定値を上書きするオプション
+#[derive(Clone, Debug, Default)]
+pub struct PjsipConfOverrides {
+    pub disallow: Option<String>,
+    pub allow: Option<String>,
+    pub direct_media: Option<TurnOnOff>,
+    pub dtmf_mode: Option<DtmfMode>,
+    pub force_rport: Option<TurnOnOff>,
+    pub rewrite_contact: Option<TurnOnOff>,
+    pub rtp_ipv6: Option<TurnOnOff>,
+    pub rtp_symmetric: Option<TurnOnOff>,
+    pub media_encryption: Option<MediaEncryption>,
+    pub max_contacts: Option<i32>,
+    pub remove_existing: Option<TurnOnOff>,
+    pub remove_unavailable: Option<TurnOnOff>,
+    pub default_expiration: Option<i32>,
+    pub minimum_expiration: Option<i32>,
+    pub maximum_expiration: Option<i32>,
+    pub qualify_frequency: Option<i32>,
+    pub qualify_timeout: Option<i32>,
+}
+
+impl PjsipConfOverrides {
+    // ps_aors の qualify_* は ws / wss では読み込まないため、指定された場合のみ上書きします
+    pub fn apply_to(&self, rows: &mut PjsipAccountRows) {
+        let PjsipAccountRows { aor, endpoint, .. } = rows;
+        if let Some(value) = &self.disallow {
+            endpoint.disallow = value.clone();
+        }
+        if let Some(value) = &self.allow {
+            endpoint.allow = value.clone();
+        }
+        if let Some(value) = &self.direct_media {
+            endpoint.direct_media = value.clone();
+        }
+        if let Some(value) = &self.dtmf_mode {
+            endpoint.dtmf_mode = value.clone();
+        }
+        if let Some(value) = &self.force_rport {
+            endpoint.force_rport = value.clone();
+        }
+        if let Some(value) = &self.rewrite_contact {
+            endpoint.rewrite_contact = value.clone();
+        }
+        if let Some(value) = &self.rtp_ipv6 {
+            endpoint.rtp_ipv6 = value.clone();
+        }
+        if let Some(value) = &self.rtp_symmetric {
+            endpoint.rtp_symmetric = value.clone();
+        }
+        if let Some(value) = &self.media_encryption {
+            endpoint.media_encryption = value.clone();
+        }
+        if let Some(value) = self.max_contacts {
+            aor.max_contacts = value;
+        }
+        if let Some(value) = &self.remove_existing {
+            aor.remove_existing = value.clone();
+        }
+        if let Some(value) = &self.remove_unavailable {
+            aor.remove_unavailable = value.clone();
+        }
+        if let Some(value) = self.default_expiration {
+            aor.default_expiration = value;
+        }
+        if let Some(value) = self.minimum_expiration {
+            aor.minimum_expiration = value;
+        }
+        if let Some(value) = self.maximum_expiration {
+            aor.maximum_expiration = value;
+        }
+        if self.qualify_frequency.is_some() {
+            aor.qualify_frequency = self.qualify_frequency;
+        }
+        if self.qualify_timeout.is_some() {
+            aor.qualify_timeout = self.qualify_timeout;
+        }
+    }
+}
+
+// 取り込み対象のエンドポイント (id はセクション名)
+#[derive(Clone, Debug)]
+pub struct PjsipConfEndpoint {
+    pub id: String,
+    pub line: usize,
+    pub account: PjsipRealtimeAccount,
+    // password がなく md5_cred のみの auth の場合はそのまま取り込みます (account.password は空)
+    pub md5_cred: Option<String>,
+    pub overrides: PjsipConfOverrides,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PjsipConf {
+    // type=endpoint のセクション数 (エラーのあるものを含む)
+    pub endpoint_sections: usize,
+    pub endpoints: Vec<PjsipConfEndpoint>,
+    pub errors: Vec<PjsipConfIssue>,
+    pub unsupported_options: Vec<PjsipConfIssue>,
+    pub skipped_sections: Vec<PjsipConfSkippedSection>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PjsipConfImportedEndpoint {
+    // セクション名 (= アカウント ID)
+    pub id: String,
+    pub username: String,
+    pub transport: TransportType,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PjsipConfImportReport {
+    pub dry_run: bool,
+    pub endpoints: usize,
+    pub valid: usize,
+    pub invalid: usize,
+    pub errors: Vec<PjsipConfIssue>,
+    pub unsupported_options: Vec<PjsipConfIssue>,
+    pub skipped_sections: Vec<PjsipConfSkippedSection>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub created: Vec<PjsipConfImportedEndpoint>,
+}
+
+// pjsip.conf をセクションに分割します (テンプレートの継承はまだ適用しません)。
+fn parse_pjsip_conf_sections(
+    text: &str,
+) -> (
+    Vec<PjsipConfSection>,
+    Vec<PjsipConfIssue>,
+    Vec<PjsipConfIssue>,
+) {
+    let mut sections: Vec<PjsipConfSection> = Vec::new();
+    let mut errors: Vec<PjsipConfIssue> = Vec::new();
+    let mut unsupported: Vec<PjsipConfIssue> = Vec::new();
+    let mut current: Option<usize> = None;
+    let mut in_block_comment: bool = false;
+
+    for (index, raw_line) in text.lines().enumerate() {
+        let line_number: usize = index + 1;
+        let stripped: String = strip_comment(raw_line, &mut in_block_comment);
+        let line: &str = stripped.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('#') {
+            let directive: &str = line.split_whitespace().next().unwrap_or(line);
+            unsupported.push(PjsipConfIssue::new(
+                None,
+                line_number,
+                Some(directive),
+                String::from("directives are not processed; merge included files first"),
+            ));
+            continue;
+        }
+
+        if line.starts_with('[') {
+            match parse_section_header(line) {
+                Ok((name, flags)) => {
+                    let mut is_template: bool = false;
+                    let mut append: bool = false;
+                    let mut templates: Vec<String> = Vec::new();
+                    for flag in flags {
+                        match flag.as_str() {
+                            "!" => is_template = true,
+                            "+" => append = true,
+                            _ => templates.push(flag),
+                        }
+                    }
+                    let existing: Option<usize> = if append {
+                        sections.iter().rposition(|s| s.name == name)
+                    } else {
+                        None
+                    };
+                    current = Some(existing.unwrap_or_else(|| {
+                        sections.push(PjsipConfSection {
+                            name,
+                            line: line_number,
+                            is_template,
+                            templates,
+                            options: Vec::new(),
+                        });
+                        sections.len() - 1
+                    }));
+                }
+                Err(message) => {
+                    errors.push(PjsipConfIssue::new(None, line_number, None, message));
+                    current = None;
+                }
+            }
+            continue;
+        }
+
+        let Some((name, value)) = line.split_once('=') else {
+            errors.push(PjsipConfIssue::new(
+                None,
+                line_number,
+                None,
+                String::from("expected <option> = <value>"),
+            ));
+            continue;
+        };
+        // "option => value" も "option = value" と同じ扱いです
+        let value: &str = value.strip_prefix('>').unwrap_or(value);
+        let option = PjsipConfOption {
+            name: name.trim().to_lowercase(),
+            value: value.trim().to_string(),
+            line: line_number,
+        };
+        match current {
+            Some(section) => sections[section].options.push(option),
+            None => errors.push(PjsipConfIssue::new(
+                None,
+                line_number,
+                Some(&option.name),
+                String::from("option is outside of a section"),
+            )),
+        }
+    }
+    (sections, errors, unsupported)
+}
+
+// ";" 以降と ";-- ... --;" をコメントとして取り除きます ("\;" は ";" として扱います)。
+fn strip_comment(line: &str, in_block_comment: &mut bool) -> String {
+    let mut result: String = String::new();
+    let mut rest: &str = line;
+    while !rest.is_empty() {
+        if *in_block_comment {
+            match rest.find("--;") {
+                Some(end) => {
+                    *in_block_comment = false;
+                    rest = &rest[end + 3..];
+                    continue;
+                }
+                None => break,
+            }
+        }
+        if let Some(next) = rest.strip_prefix("\\;") {
+            result.push(';');
+            rest = next;
+        } else if let Some(next) = rest.strip_prefix(";--") {
+            *in_block_comment = true;
+            rest = next;
+        } else if rest.starts_with(';') {
+            break;
+        } else {
+            let c: char = rest.chars().next().unwrap_or_default();
+            result.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    result
+}
+
+// "[name]" / "[name](!)" / "[name](tpl1,tpl2)" / "[name](!,tpl)" / "[name](+)"
+fn parse_section_header(line: &str) -> Result<(String, Vec<String>), String> {
+    let end: usize = line
+        .find(']')
+        .ok_or_else(|| format!("invalid section header: {}", line))?;
+    let name: &str = line[1..end].trim();
+    if name.is_empty() {
+        return Err(String::from("section name is empty"));
+    }
+    let rest: &str = line[end + 1..].trim();
+    if rest.is_empty() {
+        return Ok((name.to_string(), Vec::new()));
+    }
+    let flags: &str = rest
+        .strip_prefix('(')
+        .and_then(|r| r.strip_suffix(')'))
+        .ok_or_else(|| format!("invalid section header: {}", line))?;
+    let flags: Vec<String> = flags
+        .split(',')
+        .map(|f| f.trim().to_string())
+        .filter(|f| !f.is_empty())
+        .collect();
+    Ok((name.to_string(), flags))
+}
+
+// テンプレートの継承を適用したセクション
+// 同じ名前のオプションは後の値を使用し、allow / disallow は連結します。
+#[derive(Clone, Debug)]
+struct ResolvedSection {
+    name: String,
+    line: usize,
+    section_type: Option<String>,
+    options: Vec<PjsipConfOption>,
+}
+
+impl ResolvedSection {
+    fn get(&self, name: &str) -> Option<&PjsipConfOption> {
+        self.options.iter().find(|o| o.name == name)
+    }
+}
+
+const ACCUMULATED_OPTIONS: [&str; 2] = ["allow", "disallow"];
+
+fn resolve_section(
+    sections: &[PjsipConfSection],
+    index: usize,
+    errors: &mut Vec<PjsipConfIssue>,
+) -> ResolvedSection {
+    let mut stack: Vec<usize> = Vec::new();
+    let inherited: Vec<PjsipConfOption> = inherited_options(sections, index, &mut stack, errors);
+
+    let mut options: Vec<PjsipConfOption> = Vec::new();
+    for option in inherited {
+        match options.iter_mut().find(|o| o.name == option.name) {
+            Some(existing) if ACCUMULATED_OPTIONS.contains(&option.name.as_str()) => {
+                existing.value = format!("{},{}", existing.value, option.value);
+                existing.line = option.line;
+            }
+            Some(existing) => *existing = option,
+            None => options.push(option),
+        }
+    }
+    let section: &PjsipConfSection = &sections[index];
+    ResolvedSection {
+        name: section.name.clone(),
+        line: section.line,
+        section_type: options
+            .iter()
+            .find(|o| o.name == "type")
+            .map(|o| o.value.to_lowercase()),
+        options,
+    }
+}
+
+// 継承元 (テンプレート) のオプションを先に並べます
+fn inherited_options(
+    sections: &[PjsipConfSection],
+    index: usize,
+    stack: &mut Vec<usize>,
+    errors: &mut Vec<PjsipConfIssue>,
+) -> Vec<PjsipConfOption> {
+    let section: &PjsipConfSection = &sections[index];
+    stack.push(index);
+    let mut options: Vec<PjsipConfOption> = Vec::new();
+    for template in &section.templates {
+        // Asterisk と同じく、参照より前に定義されたセクションのみ継承できます
+        match sections[..index].iter().rposition(|s| &s.name == template) {
+            None => errors.push(PjsipConfIssue::new(
+                Some(&section.name),
+                section.line,
+                None,
+                format!("template not found: {}", template),
+            )),
+            Some(parent) if stack.contains(&parent) => errors.push(PjsipConfIssue::new(
+                Some(&section.name),
+                section.line,
+                None,
+                format!("circular template reference: {}", template),
+            )),
+            Some(parent) => options.extend(inherited_options(sections, parent, stack, errors)),
+        }
+    }
+    options.extend(section.options.iter().cloned());
+    stack.pop();
+    options
+}
+
+// pjsip.conf を読み込み、取り込み対象のエンドポイントとレポートを返します。
+pub fn read_pjsip_conf(text: &str, default_from_domain: Option<&str>) -> PjsipConf {
+    let (sections, mut errors, mut unsupported) = parse_pjsip_conf_sections(text);
+    let resolved: Vec<ResolvedSection> = sections
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| !s.is_template)
+        .map(|(index, _)| resolve_section(&sections, index, &mut errors))
+        .collect();
+
+    let by_type = |section_type: &str| -> HashMap<&str, &ResolvedSection> {
+        resolved
+            .iter()
+            .filter(|s| s.section_type.as_deref() == Some(section_type))
+            .map(|s| (s.name.as_str(), s))
+            .collect()
+    };
+    let auths: HashMap<&str, &ResolvedSection> = by_type("auth");
+    let aors: HashMap<&str, &ResolvedSection> = by_type("aor");
+    let transports: HashMap<&str, &ResolvedSection> = by_type("transport");
+
+    let mut conf: PjsipConf = PjsipConf::default();
+    let mut referenced: HashSet<(&str, &str)> = HashSet::new();
+    for section in &resolved {
+        match section.section_type.as_deref() {
+            Some("endpoint") => {
+                conf.endpoint_sections += 1;
+                let sections = EndpointSections {
+                    auths: &auths,
+                    aors: &aors,
+                    transports: &transports,
+                };
+                match read_endpoint(
+                    section,
+                    &sections,
+                    default_from_domain,
+                    &mut referenced,
+                    &mut unsupported,
+                ) {
+                    Ok(endpoint) => conf.endpoints.push(endpoint),
+                    Err(endpoint_errors) => errors.extend(endpoint_errors),
+                }
+            }
+            Some("auth") | Some("aor") => {}
+            Some("transport") => conf.skipped_sections.push(PjsipConfSkippedSection {
+                section: section.name.clone(),
+                line: section.line,
+                section_type: section.section_type.clone(),
+                reason: String::from("transports are not stored in realtime tables"),
+            }),
+            Some(other) => conf.skipped_sections.push(PjsipConfSkippedSection {
+                section: section.name.clone(),
+                line: section.line,
+                section_type: section.section_type.clone(),
+                reason: format!("type {} is not supported", other),
+            }),
+            None => errors.push(PjsipConfIssue::new(
+                Some(&section.name),
+                section.line,
+                Some("type"),
+                String::from("type is not set"),
+            )),
+        }
+    }
+
+    for section in &resolved {
+        let section_type: &str = section.section_type.as_deref().unwrap_or_default();
+        if (section_type == "auth" || section_type == "aor")
+            && !referenced.contains(&(section_type, section.name.as_str()))
+        {
+            conf.skipped_sections.push(PjsipConfSkippedSection {
+                section: section.name.clone(),
+                line: section.line,
+                section_type: section.section_type.clone(),
+                reason: String::from("not referenced by an endpoint"),
+            });
+        }
+    }
+
+    errors.sort_by_key(|e| e.line);
+    unsupported.sort_by_key(|e| e.line);
+    conf.skipped_sections.sort_by_key(|s| s.line);
+    conf.errors = errors;
+    conf.unsupported_options = unsupported;
+    conf
+}
+
+struct EndpointSections<'a> {
+    auths: &'a HashMap<&'a str, &'a ResolvedSection>,
+    aors: &'a HashMap<&'a str, &'a ResolvedSection>,
+    transports: &'a HashMap<&'a str, &'a ResolvedSection>,
+}
+
+// エンドポイントが参照する auth / aor を 1 件取得します (複数指定は先頭のみ)。
+fn referenced_section<'a>(
+    endpoint: &ResolvedSection,
+    option: &str,
+    sections: &HashMap<&str, &'a ResolvedSection>,
+    errors: &mut Vec<PjsipConfIssue>,
+    unsupported: &mut Vec<PjsipConfIssue>,
+) -> Option<&'a ResolvedSection> {
+    let value: &PjsipConfOption = endpoint.get(option)?;
+    let mut names = value
+        .value
+        .split(',')
+        .map(str::trim)
+        .filter(|n| !n.is_empty());
+    let name: &str = names.next()?;
+    if names.next().is_some() {
+        unsupported.push(PjsipConfIssue::new(
+            Some(&endpoint.name),
+            value.line,
+            Some(option),
+            format!("only the first {} ({}) is imported", option, name),
+        ));
+    }
+    let section: Option<&'a ResolvedSection> = sections.get(name).copied();
+    if section.is_none() {
+        errors.push(PjsipConfIssue::new(
+            Some(&endpoint.name),
+            value.line,
+            Some(option),
+            format!("{} section not found: {}", option, name),
+        ));
+    }
+    section
+}
+
+fn parse_option<T: FromStr>(
+    section: &str,
+    option: &PjsipConfOption,
+    errors: &mut Vec<PjsipConfIssue>,
+) -> Option<T> {
+    let parsed: Option<T> = T::from_str(&option.value).ok();
+    if parsed.is_none() {
+        errors.push(PjsipConfIssue::new(
+            Some(section),
+            option.line,
+            Some(&option.name),
+            format!("invalid value: {}", option.value),
+        ));
+    }
+    parsed
+}
+
+// transport によっては登録できるオプション
+const TRANSPORT_SPECIFIC_ENDPOINT_OPTIONS: [&str; 7] = [
+    "media_encryption_optimistic",
+    "dtls_verify",
+    "dtls_setup",
+    "dtls_fingerprint",
+    "dtls_auto_generate_cert",
+    "rtcp_mux",
+    "bundle",
+];
+const TRANSPORT_SPECIFIC_AOR_OPTIONS: [&str; 2] = ["qualify_frequency", "qualify_timeout"];
+
+fn unsupported_reason(option: &PjsipConfOption, transport_specific: &[&str]) -> &'static str {
+    if transport_specific.contains(&option.name.as_str()) {
+        "not supported for this transport"
+    } else {
+        "not supported"
+    }
+}
+
+fn unsupported_option(section: &str, option: &PjsipConfOption, reason: &str) -> PjsipConfIssue {
+    PjsipConfIssue::new(
+        Some(section),
+        option.line,
+        Some(&option.name),
+        reason.to_string(),
+    )
+}
+
+fn read_endpoint<'a>(
+    endpoint: &'a ResolvedSection,
+    sections: &EndpointSections<'a>,
+    default_from_domain: Option<&str>,
+    referenced: &mut HashSet<(&'a str, &'a str)>,
+    unsupported: &mut Vec<PjsipConfIssue>,
+) -> Result<PjsipConfEndpoint, Vec<PjsipConfIssue>> {
+    let name: &str = &endpoint.name;
+    let mut errors: Vec<PjsipConfIssue> = Vec::new();
+
+    // transport は transport セクションの protocol から決定します (未指定は udp)
+    let mut transport: TransportType = TransportType::Udp;
+    let mut transport_name: Option<String> = None;
+    if let Some(option) = endpoint.get("transport") {
+        match sections.transports.get(option.value.as_str()) {
+            Some(section) => {
+                let protocol: &str = section.get("protocol").map_or("udp", |p| p.value.as_str());
+                match TransportType::from_str(protocol) {
+                    Ok(value) => transport = value,
+                    Err(_) => errors.push(PjsipConfIssue::new(
+                        Some(&section.name),
+                        section.line,
+                        Some("protocol"),
+                        format!("unsupported protocol: {}", protocol),
+                    )),
+                }
+                transport_name = Some(option.value.clone());
+            }
+            None => errors.push(PjsipConfIssue::new(
+                Some(name),
+                option.line,
+                Some("transport"),
+                format!("transport section not found: {}", option.value),
+            )),
+        }
+    }
+    let is_ws: bool = matches!(transport, TransportType::Ws | TransportType::Wss);
+
+    let mut overrides: PjsipConfOverrides = PjsipConfOverrides::default();
+    let mut tls: TlsAccountOptions = TlsAccountOptions {
+        transport_name,
+        ..TlsAccountOptions::default()
+    };
+    let mut dtls: DtlsAccountOptions = DtlsAccountOptions::default();
+    let mut context: String = String::from(DEFAULT_PJSIP_CONF_CONTEXT);
+    let mut from_domain: Option<String> = default_from_domain.map(String::from);
+    let mut from_user: Option<String> = None;
+    let mut rtp_timeout: Option<RtpTimeout> = None;
+    let mut rtp_timeout_hold: Option<RtpTimeout> = None;
+
+    for option in &endpoint.options {
+        let errors: &mut Vec<PjsipConfIssue> = &mut errors;
+        match option.name.as_str() {
+            "type" | "transport" | "auth" | "aors" => {}
+            "context" => context = option.value.clone(),
+            "from_domain" => from_domain = Some(option.value.clone()),
+            "from_user" => from_user = Some(option.value.clone()),
+            "rtp_timeout" => rtp_timeout = parse_rtp_timeout(name, option, errors),
+            "rtp_timeout_hold" => rtp_timeout_hold = parse_rtp_timeout(name, option, errors),
+            "disallow" => overrides.disallow = Some(option.value.clone()),
+            "allow" => overrides.allow = Some(option.value.clone()),
+            "direct_media" => overrides.direct_media = parse_option(name, option, errors),
+            "dtmf_mode" => overrides.dtmf_mode = parse_option(name, option, errors),
+            "force_rport" => overrides.force_rport = parse_option(name, option, errors),
+            "rewrite_contact" => overrides.rewrite_contact = parse_option(name, option, errors),
+            "rtp_ipv6" => overrides.rtp_ipv6 = parse_option(name, option, errors),
+            "rtp_symmetric" => overrides.rtp_symmetric = parse_option(name, option, errors),
+            "media_encryption" => overrides.media_encryption = parse_option(name, option, errors),
+            "media_encryption_optimistic" if transport == TransportType::Tls => {
+                tls.media_encryption_optimistic = parse_option(name, option, errors)
+            }
+            "dtls_verify" if is_ws => {
+                if DTLS_VERIFY_VALUES.contains(&option.value.as_str()) {
+                    dtls.dtls_verify = Some(option.value.clone());
+                } else {
+                    errors.push(PjsipConfIssue::new(
+                        Some(name),
+                        option.line,
+                        Some("dtls_verify"),
+                        format!("invalid value: {}", option.value),
+                    ));
+                }
+            }
+            "dtls_setup" if is_ws => dtls.dtls_setup = parse_option(name, option, errors),
+            "dtls_fingerprint" if is_ws => {
+                dtls.dtls_fingerprint = parse_option(name, option, errors)
+            }
+            "dtls_auto_generate_cert" if is_ws => {
+                dtls.dtls_auto_generate_cert = parse_option(name, option, errors)
+            }
+            "rtcp_mux" if is_ws => dtls.rtcp_mux = parse_option(name, option, errors),
+            "bundle" if is_ws => dtls.bundle = parse_option(name, option, errors),
+            _ => unsupported.push(unsupported_option(
+                name,
+                option,
+                unsupported_reason(option, &TRANSPORT_SPECIFIC_ENDPOINT_OPTIONS),
+            )),
+        }
+    }
+
+    // auth (認証情報は必須です)
+    let mut username: Option<String> = None;
+    let mut password: Option<String> = None;
+    let mut md5_cred: Option<String> = None;
+    let mut auth_type: Option<AuthType> = None;
+    let mut realm: Option<String> = None;
+    match referenced_section(endpoint, "auth", sections.auths, &mut errors, unsupported) {
+        Some(auth) => {
+            referenced.insert(("auth", auth.name.as_str()));
+            for option in &auth.options {
+                match option.name.as_str() {
+                    "type" => {}
+                    "username" => username = Some(option.value.clone()),
+                    "password" => password = Some(option.value.clone()),
+                    "realm" => realm = Some(option.value.clone()),
+                    "auth_type" => match AuthType::from_str(&option.value) {
+                        Ok(AuthType::GoogleOauth) | Err(_) => errors.push(PjsipConfIssue::new(
+                            Some(&auth.name),
+                            option.line,
+                            Some("auth_type"),
+                            format!("unsupported auth_type: {}", option.value),
+                        )),
+                        Ok(value) => auth_type = Some(value),
+                    },
+                    "md5_cred" => {
+                        if is_md5_cred(&option.value) {
+                            md5_cred = Some(option.value.to_ascii_lowercase());
+                        } else {
+                            errors.push(PjsipConfIssue::new(
+                                Some(&auth.name),
+                                option.line,
+                                Some("md5_cred"),
+                                String::from("md5_cred must be 32 hexadecimal characters"),
+                            ));
+                        }
+                    }
+                    _ => unsupported.push(unsupported_option(&auth.name, option, "not supported")),
+                }
+            }
+            if username.is_none() {
+                errors.push(PjsipConfIssue::new(
+                    Some(&auth.name),
+                    auth.line,
+                    Some("username"),
+                    String::from("username is not set"),
+                ));
+            }
+            // password がある場合は md5_cred を password と realm から計算し直します。
+            // md5_cred のみの場合は md5 の認証情報としてそのまま取り込みます。
+            match (&password, &md5_cred, &auth_type) {
+                (Some(_), _, _) => md5_cred = None,
+                (None, Some(_), None | Some(AuthType::Md5)) => auth_type = Some(AuthType::Md5),
+                _ => errors.push(PjsipConfIssue::new(
+                    Some(&auth.name),
+                    auth.line,
+                    Some("password"),
+                    String::from("password is not set"),
+                )),
+            }
+        }
+        None if endpoint.get("auth").is_none() => errors.push(PjsipConfIssue::new(
+            Some(name),
+            endpoint.line,
+            Some("auth"),
+            String::from("auth is not set; realtime accounts require credentials"),
+        )),
+        None => {}
+    }
+
+    // aor (未指定の場合は transport ごとの既定値)
+    if let Some(aor) = referenced_section(endpoint, "aors", sections.aors, &mut errors, unsupported)
+    {
+        referenced.insert(("aor", aor.name.as_str()));
+        for option in &aor.options {
+            let errors: &mut Vec<PjsipConfIssue> = &mut errors;
+            let aor_name: &str = &aor.name;
+            match option.name.as_str() {
+                "type" => {}
+                "max_contacts" => overrides.max_contacts = parse_option(aor_name, option, errors),
+                "remove_existing" => {
+                    overrides.remove_existing = parse_option(aor_name, option, errors)
+                }
+                "remove_unavailable" => {
+                    overrides.remove_unavailable = parse_option(aor_name, option, errors)
+                }
+                "default_expiration" => {
+                    overrides.default_expiration = parse_option(aor_name, option, errors)
+                }
+                "minimum_expiration" => {
+                    overrides.minimum_expiration = parse_option(aor_name, option, errors)
+                }
+                "maximum_expiration" => {
+                    overrides.maximum_expiration = parse_option(aor_name, option, errors)
+                }
+                "qualify_frequency" if !is_ws => {
+                    overrides.qualify_frequency = parse_option(aor_name, option, errors)
+                }
+                // qualify_timeout は秒 (小数可) で指定されます
+                "qualify_timeout" if !is_ws => {
+                    overrides.qualify_timeout = parse_option::<f64>(aor_name, option, errors)
+                        .map(|value| value.round() as i32)
+                }
+                _ => unsupported.push(unsupported_option(
+                    aor_name,
+                    option,
+                    unsupported_reason(option, &TRANSPORT_SPECIFIC_AOR_OPTIONS),
+                )),
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let username: String = username.unwrap_or_default();
+    Ok(PjsipConfEndpoint {
+        id: name.to_string(),
+        line: endpoint.line,
+        account: PjsipRealtimeAccount {
+            from_user: from_user.unwrap_or_else(|| username.clone()),
+            username,
+            password: password.unwrap_or_default(),
+            transport: transport.clone(),
+            context,
+            from_domain: from_domain.unwrap_or_default(),
+            rtp_timeout,
+            rtp_timeout_hold,
+            auth_type,
+            realm,
+            tls: (transport == TransportType::Tls).then_some(tls),
+            dtls: is_ws.then_some(dtls),
+        },
+        md5_cred,
+        overrides,
+    })
+}
+
+// Asterisk の md5_cred (MD5("username:realm:password") の 16 進数表記)
+fn is_md5_cred(value: &str) -> bool {
+    value.len() == 32 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn parse_rtp_timeout(
+    section: &str,
+    option: &PjsipConfOption,
+    errors: &mut Vec<PjsipConfIssue>,
+) -> Option<RtpTimeout> {
+    let value: i32 = parse_option(section, option, errors)?;
+    match RtpTimeout::try_from(value) {
+        Ok(timeout) => Some(timeout),
+        Err(e) => {
+            errors.push(PjsipConfIssue::new(
+                Some(section),
+                option.line,
+                Some(&option.name),
+                e,
+            ));
+            None
+        }
+    }
+}
+
+// pjsip.conf の書き出し (GET /accounts/{account_id}/pjsip.conf, GET /accounts/export?format=pjsip_conf)
+// アカウントごとに endpoint / auth / aor のセクションを出力します。
+// - NULL のカラムは出力しません (Asterisk の既定値が使われます)。
+// - パスワード (password / md5_cred) は CSV エクスポートと同じく出力しません。
+//   include_secrets を指定した場合のみ、secrets で渡された認証情報を [auth] に出力します。
+// - transport が参照するトランスポートのセクションは含みません。
+
+pub const PJSIP_CONF_CONTENT_TYPE: &str = "text/plain; charset=utf-8";
+pub const PJSIP_CONF_EXPORT_FILE_NAME: &str = "pjsip.conf";
+
+// 値が設定されている項目のみ (オプション名, 値) として並べます
+macro_rules! conf_options {
+    ($row:expr; $($field:ident),* $(,)?) => {{
+        let mut options: Vec<(&'static str, String)> = Vec::new();
+        $(
+            if let Some(value) = &$row.$field {
+                options.push((stringify!($field), value.to_string()));
+            }
+        )*
+        options
+    }};
+}
+
+pub fn render_pjsip_conf(
+    accounts: &[PjsipRealtimeAccountDetail],
+    secrets: Option<&[PjsipRealtimeAccountCredentials]>,
+) -> String {
+    let secrets: HashMap<&str, &PjsipRealtimeAccountCredentials> = secrets
+        .unwrap_or_default()
+        .iter()
+        .map(|credentials| (credentials.id.as_str(), credentials))
+        .collect();
+    accounts
+        .iter()
+        .map(|detail| {
+            let secret = secrets.get(detail.account.id.as_str()).copied();
+            render_pjsip_conf_account(detail, secret)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn render_pjsip_conf_account(
+    detail: &PjsipRealtimeAccountDetail,
+    secret: Option<&PjsipRealtimeAccountCredentials>,
+) -> String {
+    let account = &detail.account;
+    let mut text: String = format!(
+        "; account {} (username: {}, transport: {})\n",
+        account.id, account.username, account.transport
+    );
+
+    if let Some(endpoint) = &detail.endpoint {
+        let options = conf_options!(endpoint;
+            transport, aors, auth, context, disallow, allow, direct_media, dtmf_mode,
+            force_rport, rewrite_contact, rtp_ipv6, rtp_symmetric, media_encryption,
+            media_encryption_optimistic, from_domain, from_user, ice_support, use_avpf, webrtc,
+            max_audio_streams, max_video_streams, rtp_timeout, rtp_timeout_hold, dtls_verify,
+            dtls_setup, dtls_fingerprint, dtls_auto_generate_cert, rtcp_mux, bundle,
+        );
+        push_pjsip_conf_section(&mut text, &endpoint.id, "endpoint", &options, None);
+    }
+
+    if let Some(auth) = &detail.auth {
+        let mut options = conf_options!(auth; auth_type, username, realm);
+        let (secret_option, secret_value): (&'static str, Option<&String>) = match auth.auth_type {
+            Some(AuthType::Md5) => ("md5_cred", secret.and_then(|s| s.md5_cred.as_ref())),
+            _ => ("password", secret.and_then(|s| s.password.as_ref())),
+        };
+        let note: Option<String> = match secret_value {
+            Some(value) => {
+                options.push((secret_option, value.clone()));
+                None
+            }
+            None => Some(format!("{} is not exported", secret_option)),
+        };
+        push_pjsip_conf_section(&mut text, &auth.id, "auth", &options, note.as_deref());
+    }
+
+    if let Some(aor) = &detail.aor {
+        let options = conf_options!(aor;
+            max_contacts, remove_existing, remove_unavailable, default_expiration,
+            minimum_expiration, maximum_expiration, qualify_frequency, qualify_timeout,
+        );
+        push_pjsip_conf_section(&mut text, &aor.id, "aor", &options, None);
+    }
+    text
+}
+
+fn push_pjsip_conf_section(
+    text: &mut String,
+    name: &str,
+    section_type: &str,
+    options: &[(&'static str, String)],
+    note: Option<&str>,
+) {
+    text.push_str(&format!("\n[{}]\ntype={}\n", name, section_type));
+    for (option, value) in options {
+        text.push_str(&format!("{}={}\n", option, escape_conf_value(value)));
+    }
+    if let Some(note) = note {
+        text.push_str(&format!("; {}\n", note));
+    }
+}
+
+// 値の ";" はコメントとして扱われるため "\;" にします (strip_comment の逆)
+fn escape_conf_value(value: &str) -> String {
+    value.replace(';', "\\;")
+}
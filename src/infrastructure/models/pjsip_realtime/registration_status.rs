@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::models::pjsip_realtime::{
+    account_list::DEFAULT_PAGE_LIMIT, enums::pjsip_endpoint_enums::TransportType,
+};
+
+// アカウント全体の登録状況 (GET /accounts/registrations)
+// pjsip_realtime_accounts と ps_contacts を結合して集計します。
+// - registered: 有効期限内の contact がある
+// - no_contacts: ps_contacts に行が 1 つもない
+//   (Asterisk は期限切れの contact を削除するため、過去に登録したかどうかは判定できません)
+// - expiring_soon: 登録中で、最も遅い有効期限が expiring_within 秒以内
+// 集計と status の絞り込みは SQL で行い、accounts は (created_at, id) 順に cursor でページングします。
+
+pub const DEFAULT_EXPIRING_WITHIN: i64 = 300;
+pub const MAX_EXPIRING_WITHIN: i64 = 86400;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistrationState {
+    Registered,
+    Unregistered,
+    NoContacts,
+    ExpiringSoon,
+}
+
+impl RegistrationState {
+    // 集計と accounts の絞り込みの条件 (アカウントごとの登録状況のカラムを参照します)
+    pub fn condition(&self) -> &'static str {
+        match self {
+            RegistrationState::Registered => "contacts > 0",
+            RegistrationState::Unregistered => "contacts = 0",
+            RegistrationState::NoContacts => "known_contacts = 0",
+            RegistrationState::ExpiringSoon => {
+                "contacts > 0 AND latest_expiration - now_epoch <= expiring_within"
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RegistrationSummaryParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiring_within: Option<i64>, // 秒 (default: DEFAULT_EXPIRING_WITHIN)
+    // 指定した状態のアカウントを accounts に列挙します
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<RegistrationState>,
+    // accounts のページング (cursor には前ページ最後のアカウント ID を指定します)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+impl RegistrationSummaryParams {
+    pub fn expiring_within(&self) -> i64 {
+        self.expiring_within.unwrap_or(DEFAULT_EXPIRING_WITHIN)
+    }
+
+    pub fn page_limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_PAGE_LIMIT)
+    }
+}
+
+// 1 アカウント分の登録状況
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AccountRegistration {
+    pub id: String,
+    pub username: String,
+    pub transport: TransportType,
+    pub context: String,
+    pub registered: bool,
+    // 期限切れを含めて ps_contacts に行がない (未登録、または期限切れの contact が削除済み)
+    pub no_contacts: bool,
+    pub contacts: i64, // 有効期限内の contact の数
+    pub expiration_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_in: Option<i64>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistrationCounts {
+    pub total: i64,
+    pub registered: i64,
+    pub unregistered: i64,
+    pub no_contacts: i64,
+    pub expiring_soon: i64,
+}
+
+impl RegistrationCounts {
+    pub fn add(&mut self, counts: &RegistrationCounts) {
+        self.total += counts.total;
+        self.registered += counts.registered;
+        self.unregistered += counts.unregistered;
+        self.no_contacts += counts.no_contacts;
+        self.expiring_soon += counts.expiring_soon;
+    }
+}
+
+// transport と context の組ごとの集計
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RegistrationGroup {
+    pub transport: TransportType,
+    pub context: String,
+    #[serde(flatten)]
+    pub counts: RegistrationCounts,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RegistrationSummary {
+    pub expiring_within: i64,
+    #[serde(flatten)]
+    pub counts: RegistrationCounts,
+    pub groups: Vec<RegistrationGroup>,
+    // status を指定した場合のみ返します
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accounts: Option<Vec<AccountRegistration>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>, // None when this is the last page
+}
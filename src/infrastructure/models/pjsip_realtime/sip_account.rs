@@ -1,25 +1,30 @@
 use serde::{Deserialize, Serialize};
 
+use crate::infrastructure::models::pjsip_realtime::account::PjsipRealtimeAccountWithId;
 use crate::infrastructure::models::pjsip_realtime::enums::{
     pjsip_auth_enums::AuthType, pjsip_endpoint_enums::*, pjsip_realtime_common_enums::TurnOnOff,
 };
 
-// SIP-UDP方式のPJSIP REALTIME 情報
+// アカウントの PJSIP REALTIME 情報
+// transport によらず同じ行を組み立て、transport ごとの既定値を上書きして登録します
+// (build_pjsip_account_rows)。
 // データベース上では、id以外のカラムはNULLABLEです。
 // 1. データを登録するカラムでは Optionを外します。
 //    必ず値を設定します。
-// 2. データを登録しないカラムはコメントアウトします。
+// 2. transport によって登録しないカラムは Option にし、None の場合は NULL を登録します。
+// 3. データを登録しないカラムはコメントアウトします。
 
-// insert into ps_auths (id, auth_type, password, username) values ...;
-// insert into ps_aors (id, default_expiration, max_contacts, minimum_expiration, qualify_frequency, maximum_expiration, qualify_timeout) values ...;
-// insert into ps_endpoints (id, transport, aors, context, disallow, allow, direct_media, force_rport, rewrite_contact, rtp_symmetric, media_encryption, from_domain, from_user) values ...;
+// insert into ps_auths (id, auth_type, md5_cred, password, realm, username) values ...;
+// insert into ps_aors (id, default_expiration, max_contacts, minimum_expiration, qualify_frequency, maximum_expiration, qualify_timeout, remove_existing, remove_unavailable) values ...;
+// insert into ps_endpoints (id, transport, aors, auth, context, disallow, allow, direct_media, dtmf_mode, force_rport, rewrite_contact, rtp_ipv6, rtp_symmetric, media_encryption, media_encryption_optimistic, from_domain, from_user, rtp_timeout, rtp_timeout_hold, ice_support, use_avpf, webrtc, max_audio_streams, max_video_streams, dtls_verify, dtls_setup, dtls_fingerprint, dtls_auto_generate_cert, rtcp_mux, bundle) values ...;
 
 #[derive(Clone, Debug, Serialize, Deserialize, sqlx::FromRow)]
-pub struct PsAuthForUdp {
+pub struct PsAuthForAccount {
     pub id: String,
     pub auth_type: AuthType, // AuthType enum('md5','userpass','google_oauth')
-    pub password: String,
-    // pub realm: Option<String>,
+    pub md5_cred: Option<String>, // auth_type = md5 の場合のみ
+    pub password: Option<String>, // auth_type = userpass の場合のみ
+    pub realm: Option<String>,
     pub username: String,
     // pub refresh_token: Option<String>,
     // pub oauth_clientid: Option<String>,
@@ -30,7 +35,7 @@ pub struct PsAuthForUdp {
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, sqlx::FromRow)]
-pub struct PsAorForUdp {
+pub struct PsAorForAccount {
     pub id: String,
     // pub contact: Option<String>,
     pub default_expiration: i32,
@@ -38,22 +43,21 @@ pub struct PsAorForUdp {
     pub max_contacts: i32,
     pub minimum_expiration: i32,
     pub remove_existing: TurnOnOff, // enum('0','1','off','on','false','true','no','yes')
-    pub qualify_frequency: i32,
+    pub qualify_frequency: Option<i32>, // ws / wss では登録しません
     // pub authenticate_qualify: Option<TurnOnOff>, // enum('0','1','off','on','false','true','no','yes')
     pub maximum_expiration: i32,
     // pub outbound_proxy: Option<String>,
     // pub support_path: Option<TurnOnOff>, // enum('0','1','off','on','false','true','no','yes')
-    pub qualify_timeout: i32,
+    pub qualify_timeout: Option<i32>, // ws / wss では登録しません
     // pub voicemail_extension: Option<String>,
     pub remove_unavailable: TurnOnOff, // enum('0','1','off','on','false','true','no','yes')
-
                                        // pub qualify_2xx_only: Option<TurnOnOff>, // enum('0','1','off','on','false','true','no','yes')
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, sqlx::FromRow)]
-pub struct PsEndpointForUdp {
+pub struct PsEndpointForAccount {
     pub id: String,
-    pub transport: TransportType, // String から TransportType に変更
+    pub transport: String, // トランスポート名 (TLS は pjsip.conf 側の TLS トランスポート名)
     pub aors: String,
     pub auth: String,
     pub context: String,
@@ -67,7 +71,7 @@ pub struct PsEndpointForUdp {
     pub dtmf_mode: DtmfMode,
     // pub external_media_address: Option<String>,
     pub force_rport: TurnOnOff,
-    // pub ice_support: Option<TurnOnOff>,
+    pub ice_support: Option<TurnOnOff>,
     // pub identify_by: Option<String>,
     // pub mailboxes: Option<String>,
     // pub moh_suggest: Option<String>,
@@ -90,7 +94,7 @@ pub struct PsEndpointForUdp {
     // pub trust_id_inbound: Option<TurnOnOff>,
     // pub trust_id_outbound: Option<TurnOnOff>,
     // pub use_ptime: Option<TurnOnOff>,
-    // pub use_avpf: Option<TurnOnOff>,
+    pub use_avpf: Option<TurnOnOff>,
     pub media_encryption: MediaEncryption,
     // pub inband_progress: Option<TurnOnOff>,
     // pub call_group: Option<String>,
@@ -120,14 +124,14 @@ pub struct PsEndpointForUdp {
     pub from_domain: String,
     pub from_user: String,
     // pub mwi_from_user: Option<String>,
-    // pub dtls_verify: Option<String>,
+    pub dtls_verify: Option<String>,
     // pub dtls_rekey: Option<String>,
     // pub dtls_cert_file: Option<String>,
     // pub dtls_private_key: Option<String>,
     // pub dtls_cipher: Option<String>,
     // pub dtls_ca_file: Option<String>,
     // pub dtls_ca_path: Option<String>,
-    // pub dtls_setup: Option<DtlsSetup>,
+    pub dtls_setup: Option<DtlsSetup>,
     // pub srtp_tag_32: Option<TurnOnOff>,
     // pub media_address: Option<String>,
     // pub redirect_method: Option<RedirectMethod>,
@@ -140,12 +144,12 @@ pub struct PsEndpointForUdp {
     // pub accountcode: Option<String>,
     // pub user_eq_phone: Option<TurnOnOff>,
     // pub moh_passthrough: Option<TurnOnOff>,
-    // pub media_encryption_optimistic: Option<TurnOnOff>,
+    pub media_encryption_optimistic: Option<TurnOnOff>,
     // pub rpid_immediate: Option<TurnOnOff>,
     // pub g726_non_standard: Option<TurnOnOff>,
     // pub rtp_keepalive: Option<i32>,
-    // pub rtp_timeout: Option<i32>,
-    // pub rtp_timeout_hold: Option<i32>,
+    pub rtp_timeout: Option<RtpTimeout>,
+    pub rtp_timeout_hold: Option<RtpTimeout>,
     // pub bind_rtp_to_media_address: Option<TurnOnOff>,
     // pub voicemail_extension: Option<String>,
     // pub mwi_subscribe_replaces_unsolicited: Option<TurnOnOff>,
@@ -160,17 +164,17 @@ pub struct PsEndpointForUdp {
     // pub contact_user: Option<String>,
     // pub preferred_codec_only: Option<TurnOnOff>,
     // pub asymmetric_rtp_codec: Option<TurnOnOff>,
-    // pub rtcp_mux: Option<TurnOnOff>,
+    pub rtcp_mux: Option<TurnOnOff>,
     // pub allow_overlap: Option<TurnOnOff>,
     // pub refer_blind_progress: Option<TurnOnOff>,
     // pub notify_early_inuse_ringing: Option<TurnOnOff>,
-    // pub max_audio_streams: Option<i32>,
-    // pub max_video_streams: Option<i32>,
-    // pub webrtc: Option<TurnOnOff>,
-    // pub dtls_fingerprint: Option<DtlsFingerprint>,
+    pub max_audio_streams: Option<i32>,
+    pub max_video_streams: Option<i32>,
+    pub webrtc: Option<TurnOnOff>,
+    pub dtls_fingerprint: Option<DtlsFingerprint>,
     // pub incoming_mwi_mailbox: Option<String>,
-    // pub bundle: Option<TurnOnOff>,
-    // pub dtls_auto_generate_cert: Option<TurnOnOff>,
+    pub bundle: Option<TurnOnOff>,
+    pub dtls_auto_generate_cert: Option<TurnOnOff>,
     // pub follow_early_media_fork: Option<TurnOnOff>,
     // pub accept_multiple_sdp_answers: Option<TurnOnOff>,
     // pub suppress_q850_reason_headers: Option<TurnOnOff>,
@@ -197,3 +201,12 @@ pub struct PsEndpointForUdp {
     // pub tenantid: Option<String>,
     // pub suppress_moh_on_sendonly: Option<TurnOnOff>,
 }
+
+// 1 アカウント分の pjsip_realtime_accounts / ps_* 行
+#[derive(Clone, Debug)]
+pub struct PjsipAccountRows {
+    pub account: PjsipRealtimeAccountWithId,
+    pub auth: PsAuthForAccount,
+    pub aor: PsAorForAccount,
+    pub endpoint: PsEndpointForAccount,
+}
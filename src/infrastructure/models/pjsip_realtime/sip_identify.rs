@@ -0,0 +1,116 @@
+use std::net::{IpAddr, Ipv4Addr};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+// 送信元 IP / SIP ヘッダーによるエンドポイントの識別 (ps_endpoint_id_ips)
+// キャリアや SBC のようにユーザー名で認証しない相手からの着信を、エンドポイントに紐付けます。
+// 1 リクエストにつき 1 行を登録し、複数の match は "," 区切りで match カラムに保存します。
+
+// insert into ps_endpoint_id_ips (id, endpoint, match, match_header) values ...;
+
+// POST /endpoints/{endpoint_id}/identifies のリクエスト
+// match と match_header の少なくとも一方を指定します。
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PjsipEndpointIdentify {
+    // IP アドレス、CIDR (203.0.113.0/24, 203.0.113.0/255.255.255.0) またはホスト名
+    #[serde(default, rename = "match")]
+    pub matches: Vec<String>,
+    // "Header-Name: value" の形式 (例: "X-Carrier-Id: carrier-a")
+    #[serde(default)]
+    pub match_header: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PjsipEndpointIdentifyResponse {
+    pub id: String,
+    pub endpoint: String,
+    #[serde(rename = "match")]
+    pub matches: Vec<String>,
+    pub match_header: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PsEndpointIdIp {
+    pub id: String,
+    pub endpoint: String,
+    pub r#match: Option<String>,
+    pub match_header: Option<String>,
+}
+
+// match カラムの値を個々の match に分割します
+pub fn split_matches(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+// 2 つの match が同じ送信元を含むかどうか
+// IP アドレス / CIDR はネットワークの重なりで、ホスト名は名前の一致で判定します。
+pub fn matches_overlap(a: &str, b: &str) -> bool {
+    match (MatchNetwork::from_str(a), MatchNetwork::from_str(b)) {
+        (Ok(a), Ok(b)) => a.overlaps(&b),
+        (Err(_), Err(_)) => a.eq_ignore_ascii_case(b),
+        _ => false,
+    }
+}
+
+// IP アドレスまたは CIDR で表した match のネットワーク
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MatchNetwork {
+    address: IpAddr,
+    prefix: u8,
+}
+
+impl MatchNetwork {
+    pub fn overlaps(&self, other: &MatchNetwork) -> bool {
+        let prefix: u8 = self.prefix.min(other.prefix);
+        match (self.address, other.address) {
+            (IpAddr::V4(a), IpAddr::V4(b)) => {
+                let mask: u32 = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+                u32::from(a) & mask == u32::from(b) & mask
+            }
+            (IpAddr::V6(a), IpAddr::V6(b)) => {
+                let mask: u128 = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+                u128::from(a) & mask == u128::from(b) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+// "203.0.113.10", "203.0.113.0/24", "203.0.113.0/255.255.255.0", "2001:db8::/32"
+impl FromStr for MatchNetwork {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (address, prefix): (&str, Option<&str>) = match value.split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (value, None),
+        };
+        let address: IpAddr = address
+            .parse()
+            .map_err(|_| String::from("must be a valid IP address"))?;
+        let max_prefix: u8 = if address.is_ipv4() { 32 } else { 128 };
+        let prefix: u8 = match prefix {
+            None => max_prefix,
+            Some(prefix) => match prefix.parse::<u8>() {
+                Ok(prefix) if prefix <= max_prefix => prefix,
+                Ok(_) => return Err(format!("prefix length must be at most {}", max_prefix)),
+                // IPv4 はネットマスク (255.255.255.0) でも指定できます
+                Err(_) if address.is_ipv4() => prefix
+                    .parse::<Ipv4Addr>()
+                    .ok()
+                    .map(u32::from)
+                    .filter(|mask| mask.leading_ones() + mask.trailing_zeros() == 32)
+                    .map(|mask| mask.leading_ones() as u8)
+                    .ok_or_else(|| String::from("must have a valid prefix length or netmask"))?,
+                Err(_) => return Err(String::from("must have a valid prefix length")),
+            },
+        };
+        Ok(MatchNetwork { address, prefix })
+    }
+}
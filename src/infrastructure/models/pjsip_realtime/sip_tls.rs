@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::models::pjsip_realtime::enums::{
+    pjsip_endpoint_enums::MediaEncryption, pjsip_realtime_common_enums::TurnOnOff,
+};
+
+// SIP-TLS方式のアカウントのオプション
+// シグナリングは TLS、メディアは SRTP (SDES) で暗号化します。
+// ps_endpoints.transport には pjsip.conf 側の TLS トランスポート名を設定します。
+
+// TLS アカウント作成時のリクエストオプション
+// 未指定の項目は build_pjsip_account_rows のデフォルト値を使います。
+pub const DEFAULT_TLS_TRANSPORT_NAME: &str = "tls";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TlsAccountOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transport_name: Option<String>, // default: DEFAULT_TLS_TRANSPORT_NAME
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_encryption: Option<MediaEncryption>, // default: MediaEncryption::Sdes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_encryption_optimistic: Option<TurnOnOff>, // default: NULL (Asterisk default)
+}
+
+impl TlsAccountOptions {
+    /// Returns the options with the fields present in `patch` overriding the current values.
+    pub fn merged_with(&self, patch: &TlsAccountOptions) -> TlsAccountOptions {
+        TlsAccountOptions {
+            transport_name: patch
+                .transport_name
+                .clone()
+                .or_else(|| self.transport_name.clone()),
+            media_encryption: patch
+                .media_encryption
+                .clone()
+                .or_else(|| self.media_encryption.clone()),
+            media_encryption_optimistic: patch
+                .media_encryption_optimistic
+                .clone()
+                .or_else(|| self.media_encryption_optimistic.clone()),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::models::pjsip_realtime::enums::{
+    pjsip_auth_enums::AuthType, pjsip_endpoint_enums::*, pjsip_realtime_common_enums::TurnOnOff,
+};
+use crate::infrastructure::models::pjsip_realtime::sip_identify::PsEndpointIdIp;
+
+// SIP トランク (キャリア) の PJSIP REALTIME 情報
+// 1 トランクにつき、pjsip_realtime_trunks と同じ id で次の行を登録します。
+// - ps_registrations: キャリアへの REGISTER (outbound_auth で ps_auths を参照)
+// - ps_auths: outbound_auth 用の認証情報
+// - ps_aors: 発信先 (contact = server_uri)
+// - ps_endpoints: 発着信用のエンドポイント (registration の line / endpoint で着信を紐付けます)
+// - ps_endpoint_id_ips: 送信元 IP による着信の識別 (match ごとに 1 行、id は ULID)
+
+// insert into ps_registrations (id, auth_rejection_permanent, client_uri, contact_user, expiration, max_retries, outbound_auth, outbound_proxy, retry_interval, forbidden_retry_interval, server_uri, transport, line, endpoint) values ...;
+// insert into ps_auths (id, auth_type, password, realm, username) values ...;
+// insert into ps_aors (id, contact, max_contacts, qualify_frequency) values ...;
+// insert into ps_endpoints (id, transport, aors, outbound_auth, context, disallow, allow, direct_media, dtmf_mode, force_rport, rewrite_contact, rtp_symmetric, from_domain, from_user, outbound_proxy) values ...;
+// insert into ps_endpoint_id_ips (id, endpoint, match) values ...;
+
+pub const DEFAULT_TRUNK_EXPIRATION: i32 = 3600;
+pub const DEFAULT_TRUNK_RETRY_INTERVAL: i32 = 60;
+pub const DEFAULT_TRUNK_MAX_RETRIES: i32 = 10;
+// 認証に失敗した場合 (403) は間隔を空けて再試行します
+pub const DEFAULT_TRUNK_FORBIDDEN_RETRY_INTERVAL: i32 = 300;
+pub const DEFAULT_TRUNK_QUALIFY_FREQUENCY: i32 = 60;
+
+// POST /trunks, PUT /trunks/{trunk_id} のリクエスト
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PjsipRealtimeTrunk {
+    pub name: String,
+    pub transport: TransportType, // udp / tcp / tls
+    pub server_uri: String,       // e.g. sip:sip.carrier.example.com
+    #[serde(default)]
+    pub client_uri: Option<String>, // default: sip:{username}@{server_uri のホスト}
+    pub username: String,
+    pub password: String,
+    #[serde(default)]
+    pub realm: Option<String>,
+    pub context: String,
+    #[serde(default)]
+    pub from_domain: Option<String>,
+    #[serde(default)]
+    pub from_user: Option<String>,
+    #[serde(default)]
+    pub contact_user: Option<String>,
+    #[serde(default)]
+    pub outbound_proxy: Option<String>,
+    #[serde(default)]
+    pub expiration: Option<i32>, // default: DEFAULT_TRUNK_EXPIRATION
+    #[serde(default)]
+    pub retry_interval: Option<i32>, // default: DEFAULT_TRUNK_RETRY_INTERVAL
+    #[serde(default)]
+    pub max_retries: Option<i32>, // default: DEFAULT_TRUNK_MAX_RETRIES
+    // 着信を識別する送信元の IP アドレス (ps_endpoint_id_ips.match)
+    #[serde(default, rename = "match")]
+    pub matches: Vec<String>,
+}
+
+// トランクのレスポンス (パスワードは返却しません)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PjsipRealtimeTrunkResponse {
+    pub id: String,
+    pub name: String,
+    pub transport: TransportType,
+    pub server_uri: String,
+    pub client_uri: Option<String>,
+    pub username: Option<String>,
+    pub realm: Option<String>,
+    pub context: String,
+    pub from_domain: Option<String>,
+    pub from_user: Option<String>,
+    pub contact_user: Option<String>,
+    pub outbound_proxy: Option<String>,
+    pub expiration: Option<i32>,
+    pub retry_interval: Option<i32>,
+    pub max_retries: Option<i32>,
+    #[serde(rename = "match")]
+    pub matches: Vec<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PsRegistrationForTrunk {
+    pub id: String,
+    pub auth_rejection_permanent: TurnOnOff,
+    pub client_uri: String,
+    pub contact_user: Option<String>,
+    pub expiration: i32,
+    pub max_retries: i32,
+    pub outbound_auth: String,
+    pub outbound_proxy: Option<String>,
+    pub retry_interval: i32,
+    pub forbidden_retry_interval: i32,
+    pub server_uri: String,
+    pub transport: String, // トランスポート名
+    // pub support_path: Option<TurnOnOff>,
+    // pub fatal_retry_interval: Option<i32>,
+    pub line: TurnOnOff,
+    pub endpoint: String,
+    // pub support_outbound: Option<TurnOnOff>,
+    // pub contact_header_params: Option<String>,
+    // pub max_random_initial_delay: Option<i32>,
+    // pub user_agent: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PsAuthForTrunk {
+    pub id: String,
+    pub auth_type: AuthType, // userpass のみ
+    pub password: String,
+    pub realm: Option<String>,
+    pub username: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PsAorForTrunk {
+    pub id: String,
+    pub contact: String,
+    pub max_contacts: i32,
+    pub qualify_frequency: i32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PsEndpointForTrunk {
+    pub id: String,
+    pub transport: String, // トランスポート名
+    pub aors: String,
+    pub outbound_auth: String,
+    pub context: String,
+    pub disallow: String,
+    pub allow: String,
+    pub direct_media: TurnOnOff,
+    pub dtmf_mode: DtmfMode,
+    pub force_rport: TurnOnOff,
+    pub rewrite_contact: TurnOnOff,
+    pub rtp_symmetric: TurnOnOff,
+    pub from_domain: Option<String>,
+    pub from_user: Option<String>,
+    pub outbound_proxy: Option<String>,
+}
+
+// 1 トランク分の ps_* 行
+#[derive(Clone, Debug)]
+pub struct PjsipTrunkRows {
+    pub registration: PsRegistrationForTrunk,
+    pub auth: PsAuthForTrunk,
+    pub aor: PsAorForTrunk,
+    pub endpoint: PsEndpointForTrunk,
+    pub identifies: Vec<PsEndpointIdIp>,
+}
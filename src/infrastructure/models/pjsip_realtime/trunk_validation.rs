@@ -0,0 +1,173 @@
+use crate::infrastructure::models::errors::validation_error::ValidationErrors;
+use crate::infrastructure::models::pjsip_realtime::account_validation::{
+    FROM_USER_MAX_LENGTH, PASSWORD_MAX_LENGTH, check, check_context, check_domain, check_sip_user,
+};
+use crate::infrastructure::models::pjsip_realtime::enums::pjsip_endpoint_enums::TransportType;
+use crate::infrastructure::models::pjsip_realtime::identify_validation::check_matches;
+use crate::infrastructure::models::pjsip_realtime::sip_trunk::PjsipRealtimeTrunk;
+
+// pjsip_realtime_trunks / ps_registrations のカラム長
+pub const TRUNK_NAME_MAX_LENGTH: usize = 100;
+pub const SIP_URI_MAX_LENGTH: usize = 255;
+pub const TRUNK_USERNAME_MAX_LENGTH: usize = 255;
+pub const CONTACT_USER_MAX_LENGTH: usize = 40;
+
+// トランク作成・置き換え時の検証
+// パスワードはキャリアが発行するため、長さと制御文字のみ確認します。
+pub fn validate_trunk(trunk: &PjsipRealtimeTrunk) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    check(&mut errors, "name", check_trunk_name(&trunk.name));
+    if matches!(trunk.transport, TransportType::Ws | TransportType::Wss) {
+        errors.add("transport", "must be udp, tcp or tls");
+    }
+    check(
+        &mut errors,
+        "server_uri",
+        parse_sip_uri(&trunk.server_uri).map(|_| ()),
+    );
+    if let Some(client_uri) = &trunk.client_uri {
+        check(
+            &mut errors,
+            "client_uri",
+            parse_sip_uri(client_uri).map(|_| ()),
+        );
+    }
+    check(
+        &mut errors,
+        "username",
+        check_sip_user(&trunk.username, TRUNK_USERNAME_MAX_LENGTH),
+    );
+    check(
+        &mut errors,
+        "password",
+        check_trunk_password(&trunk.password),
+    );
+    check(&mut errors, "context", check_context(&trunk.context));
+    if let Some(from_domain) = &trunk.from_domain {
+        check(&mut errors, "from_domain", check_domain(from_domain));
+    }
+    if let Some(from_user) = &trunk.from_user {
+        check(
+            &mut errors,
+            "from_user",
+            check_sip_user(from_user, FROM_USER_MAX_LENGTH),
+        );
+    }
+    if let Some(contact_user) = &trunk.contact_user {
+        check(
+            &mut errors,
+            "contact_user",
+            check_sip_user(contact_user, CONTACT_USER_MAX_LENGTH),
+        );
+    }
+    if let Some(outbound_proxy) = &trunk.outbound_proxy {
+        check(
+            &mut errors,
+            "outbound_proxy",
+            parse_sip_uri(outbound_proxy).map(|_| ()),
+        );
+    }
+    if let Some(expiration) = trunk.expiration {
+        check(&mut errors, "expiration", check_positive(expiration));
+    }
+    if let Some(retry_interval) = trunk.retry_interval {
+        check(
+            &mut errors,
+            "retry_interval",
+            check_positive(retry_interval),
+        );
+    }
+    if trunk.max_retries.is_some_and(|max_retries| max_retries < 0) {
+        errors.add("max_retries", "must not be negative");
+    }
+    check_matches(&mut errors, &trunk.matches);
+    errors.into_result()
+}
+
+fn check_trunk_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err(String::from("must not be empty"));
+    }
+    if name.chars().count() > TRUNK_NAME_MAX_LENGTH {
+        return Err(format!(
+            "must be at most {} characters",
+            TRUNK_NAME_MAX_LENGTH
+        ));
+    }
+    if name.chars().any(char::is_control) {
+        return Err(String::from("must not contain control characters"));
+    }
+    Ok(())
+}
+
+fn check_trunk_password(password: &str) -> Result<(), String> {
+    if password.is_empty() {
+        return Err(String::from("must not be empty"));
+    }
+    if password.chars().count() > PASSWORD_MAX_LENGTH {
+        return Err(format!(
+            "must be at most {} characters",
+            PASSWORD_MAX_LENGTH
+        ));
+    }
+    if password.chars().any(char::is_control) {
+        return Err(String::from("must not contain control characters"));
+    }
+    Ok(())
+}
+
+fn check_positive(value: i32) -> Result<(), String> {
+    if value <= 0 {
+        return Err(String::from("must be greater than 0"));
+    }
+    Ok(())
+}
+
+// "sip:" / "sips:" の URI (sip:[user@]host[:port][;params])
+// 検証済みのホスト部を返します。
+pub fn parse_sip_uri(uri: &str) -> Result<&str, String> {
+    if uri.chars().count() > SIP_URI_MAX_LENGTH {
+        return Err(format!("must be at most {} characters", SIP_URI_MAX_LENGTH));
+    }
+    let lower: String = uri.to_ascii_lowercase();
+    let scheme_length: usize = if lower.starts_with("sip:") {
+        4
+    } else if lower.starts_with("sips:") {
+        5
+    } else {
+        return Err(String::from("must start with sip: or sips:"));
+    };
+    let rest: &str = &uri[scheme_length..];
+    let rest: &str = rest.split([';', '?']).next().unwrap_or_default();
+    let host_port: &str = match rest.rsplit_once('@') {
+        Some((user, host_port)) => {
+            check_sip_user(user, TRUNK_USERNAME_MAX_LENGTH)
+                .map_err(|message| format!("user part {}", message))?;
+            host_port
+        }
+        None => rest,
+    };
+    // "[::1]:5060" / "host:5060" / "host"
+    let (host, port): (&str, Option<&str>) = if host_port.starts_with('[') {
+        match host_port.split_once(']') {
+            Some((host, "")) => (&host_port[..host.len() + 1], None),
+            Some((host, port)) => match port.strip_prefix(':') {
+                Some(port) => (&host_port[..host.len() + 1], Some(port)),
+                None => return Err(String::from("must be a valid SIP URI")),
+            },
+            None => (host_port, None),
+        }
+    } else {
+        match host_port.split_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (host_port, None),
+        }
+    };
+    check_domain(host).map_err(|message| format!("host {}", message))?;
+    if let Some(port) = port
+        && !port.parse::<u16>().is_ok_and(|port| port > 0)
+    {
+        return Err(String::from("port must be between 1 and 65535"));
+    }
+    Ok(host)
+}
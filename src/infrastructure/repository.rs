@@ -1 +1,5 @@
+pub(crate) mod api_key_repository;
+pub(crate) mod pjsip_contact_repository;
+pub(crate) mod pjsip_identify_repository;
 pub(crate) mod pjsip_realtime_repository;
+pub(crate) mod pjsip_trunk_repository;
@@ -0,0 +1,100 @@
+use crate::infrastructure::models::{
+    authentication::api_key::ApiKey,
+    errors::{
+        authentication_error::AuthenticationError, deletion_error::DeletionError,
+        registration_error::RegistrationError, retrieval_error::RetrievalError,
+    },
+};
+use sqlx::{PgPool, Row, postgres::PgRow};
+
+const API_KEY_COLUMNS: &str =
+    "id, name, key_prefix, scopes, expires_at, revoked_at, last_used_at, created_by, created_at";
+
+// registration method
+pub async fn exec_insert_api_key(
+    pool: &PgPool,
+    key: &ApiKey,
+    key_hash: &str,
+) -> Result<(), RegistrationError> {
+    sqlx::query(
+        r#"INSERT INTO api_keys (id, name, key_prefix, key_hash, scopes, expires_at, created_by, created_at)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#,
+    )
+    .bind(&key.id)
+    .bind(&key.name)
+    .bind(&key.key_prefix)
+    .bind(key_hash)
+    .bind(&key.scopes)
+    .bind(key.expires_at)
+    .bind(&key.created_by)
+    .bind(key.created_at)
+    .execute(pool)
+    .await
+    .map_err(RegistrationError::from)?;
+    Ok(())
+}
+
+// retrieval method
+pub async fn get_api_keys(pool: &PgPool) -> Result<Vec<ApiKey>, RetrievalError> {
+    let rows: Vec<PgRow> = sqlx::query(&format!(
+        "SELECT {} FROM api_keys ORDER BY created_at DESC, id DESC",
+        API_KEY_COLUMNS
+    ))
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.iter().map(api_key_from_row).collect())
+}
+
+// 有効な (失効・期限切れでない) キーを検索し、最終利用日時を更新します
+pub async fn exec_authenticate_api_key(
+    pool: &PgPool,
+    key_hash: &str,
+) -> Result<ApiKey, AuthenticationError> {
+    let row: Option<PgRow> = sqlx::query(&format!(
+        r#"UPDATE api_keys SET last_used_at = CURRENT_TIMESTAMP
+           WHERE key_hash = $1
+             AND revoked_at IS NULL
+             AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
+           RETURNING {}"#,
+        API_KEY_COLUMNS
+    ))
+    .bind(key_hash)
+    .fetch_optional(pool)
+    .await?;
+    row.as_ref()
+        .map(api_key_from_row)
+        .ok_or(AuthenticationError::InvalidApiKey)
+}
+
+// revocation method
+// 監査のため行は削除せず、revoked_at を設定します
+pub async fn exec_revoke_api_key(pool: &PgPool, key_id: &str) -> Result<(), DeletionError> {
+    if key_id.is_empty() {
+        return Err(DeletionError::IdNotSpecified);
+    }
+    let result = sqlx::query(
+        r#"UPDATE api_keys SET revoked_at = CURRENT_TIMESTAMP
+           WHERE id = $1 AND revoked_at IS NULL"#,
+    )
+    .bind(key_id)
+    .execute(pool)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(DeletionError::NotFoundRecord);
+    }
+    Ok(())
+}
+
+fn api_key_from_row(row: &PgRow) -> ApiKey {
+    ApiKey {
+        id: row.get("id"),
+        name: row.get("name"),
+        key_prefix: row.get("key_prefix"),
+        scopes: row.get("scopes"),
+        expires_at: row.get("expires_at"),
+        revoked_at: row.get("revoked_at"),
+        last_used_at: row.get("last_used_at"),
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+    }
+}
@@ -0,0 +1,213 @@
+use crate::infrastructure::models::{
+    errors::retrieval_error::RetrievalError,
+    pjsip_realtime::{
+        contact::PsContactResponse,
+        enums::pjsip_endpoint_enums::TransportType,
+        registration_status::{
+            AccountRegistration, RegistrationCounts, RegistrationGroup, RegistrationState,
+        },
+    },
+};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use std::str::FromStr;
+
+// retrieval method
+// AOR に登録中 (有効期限内) の contact を有効期限の遅い順で返します
+pub async fn get_pjsip_aor_contacts(
+    pool: &PgPool,
+    aor_id: &str,
+) -> Result<Vec<PsContactResponse>, RetrievalError> {
+    if aor_id.is_empty() {
+        return Err(RetrievalError::IdNotSpecified);
+    }
+    let query = "
+        SELECT
+            c.id,
+            c.uri,
+            c.user_agent,
+            to_timestamp(c.expiration_time) AS expiration_time,
+            c.expiration_time - EXTRACT(EPOCH FROM CURRENT_TIMESTAMP)::bigint AS expires_in,
+            c.via_addr,
+            c.via_port,
+            c.qualify_frequency,
+            c.qualify_timeout,
+            c.reg_server
+        FROM ps_contacts c
+        WHERE split_part(c.id, '^3B@', 1) = $1
+          AND c.expiration_time > EXTRACT(EPOCH FROM CURRENT_TIMESTAMP)
+        ORDER BY c.expiration_time DESC, c.id ASC
+    ";
+    let rows: Vec<PgRow> = sqlx::query(query).bind(aor_id).fetch_all(pool).await?;
+    Ok(rows
+        .iter()
+        .map(|row| PsContactResponse {
+            id: row.get("id"),
+            uri: row.get("uri"),
+            user_agent: row.get("user_agent"),
+            expiration_time: row.get("expiration_time"),
+            expires_in: row.get("expires_in"),
+            via_addr: row.get("via_addr"),
+            via_port: row.get("via_port"),
+            qualify_frequency: row.get("qualify_frequency"),
+            qualify_timeout: row.get("qualify_timeout"),
+            reg_server: row.get("reg_server"),
+        })
+        .collect())
+}
+
+// アカウントごとの登録状況 (registration summary の集計と一覧で共通)
+// AOR はアカウントと同じ id です。contact id は "<aor>^3B@<hash>" のため、
+// split_part の式インデックス (idx_ps_contacts_aor) で AOR ごとに検索します。
+// known_contacts は期限切れを含む ps_contacts の行数です。$1 は expiring_within (秒) です。
+const ACCOUNT_REGISTRATIONS_QUERY: &str = "
+    SELECT
+        a.id,
+        a.username,
+        a.transport,
+        a.context,
+        a.created_at,
+        n.epoch AS now_epoch,
+        n.expiring_within,
+        c.contacts,
+        c.known_contacts,
+        c.latest_expiration
+    FROM pjsip_realtime_accounts a
+    CROSS JOIN (
+        SELECT EXTRACT(EPOCH FROM CURRENT_TIMESTAMP)::bigint AS epoch, $1::bigint AS expiring_within
+    ) n
+    CROSS JOIN LATERAL (
+        SELECT
+            COUNT(*) FILTER (WHERE pc.expiration_time > n.epoch) AS contacts,
+            COUNT(*) AS known_contacts,
+            MAX(pc.expiration_time) FILTER (WHERE pc.expiration_time > n.epoch)
+                AS latest_expiration
+        FROM ps_contacts pc
+        WHERE split_part(pc.id, '^3B@', 1) = a.id
+    ) c
+";
+
+// transport / context の組ごとの登録状況の件数を (transport, context) 順で返します
+pub async fn get_pjsip_registration_groups(
+    pool: &PgPool,
+    expiring_within: i64,
+) -> Result<Vec<RegistrationGroup>, RetrievalError> {
+    let query = format!(
+        "
+        SELECT
+            s.transport,
+            s.context,
+            COUNT(*) AS total,
+            COUNT(*) FILTER (WHERE {registered}) AS registered,
+            COUNT(*) FILTER (WHERE {unregistered}) AS unregistered,
+            COUNT(*) FILTER (WHERE {no_contacts}) AS no_contacts,
+            COUNT(*) FILTER (WHERE {expiring_soon}) AS expiring_soon
+        FROM ({ACCOUNT_REGISTRATIONS_QUERY}) s
+        GROUP BY s.transport, s.context
+        ORDER BY s.transport ASC, s.context ASC
+    ",
+        registered = RegistrationState::Registered.condition(),
+        unregistered = RegistrationState::Unregistered.condition(),
+        no_contacts = RegistrationState::NoContacts.condition(),
+        expiring_soon = RegistrationState::ExpiringSoon.condition(),
+    );
+    let rows: Vec<PgRow> = sqlx::query(&query)
+        .bind(expiring_within)
+        .fetch_all(pool)
+        .await?;
+    rows.iter()
+        .map(|row| {
+            Ok(RegistrationGroup {
+                transport: transport_from_row(row)?,
+                context: row.get("context"),
+                counts: RegistrationCounts {
+                    total: row.get("total"),
+                    registered: row.get("registered"),
+                    unregistered: row.get("unregistered"),
+                    no_contacts: row.get("no_contacts"),
+                    expiring_soon: row.get("expiring_soon"),
+                },
+            })
+        })
+        .collect()
+}
+
+// 指定した状態のアカウントを作成日時順で返します
+// keyset pagination: cursor (前ページ最後のアカウント ID) の (created_at, id) より後ろを取得し、
+// 次ページがある場合は next_cursor を返します。
+pub async fn get_pjsip_account_registrations(
+    pool: &PgPool,
+    state: RegistrationState,
+    expiring_within: i64,
+    limit: i64,
+    cursor: Option<&str>,
+) -> Result<(Vec<AccountRegistration>, Option<String>), RetrievalError> {
+    let cursor_created_at: Option<chrono::NaiveDateTime> = match cursor {
+        Some(cursor) => Some(
+            sqlx::query_scalar("SELECT created_at FROM pjsip_realtime_accounts WHERE id = $1")
+                .bind(cursor)
+                .fetch_optional(pool)
+                .await?
+                .ok_or_else(|| RetrievalError::InvalidCursor(cursor.to_string()))?,
+        ),
+        None => None,
+    };
+
+    // 状態の条件は RegistrationState の固定値のみ埋め込みます。
+    let query = format!(
+        "
+        SELECT
+            s.id,
+            s.username,
+            s.transport,
+            s.context,
+            s.contacts,
+            s.known_contacts,
+            to_timestamp(s.latest_expiration) AS expiration_time,
+            s.latest_expiration - s.now_epoch AS expires_in
+        FROM ({ACCOUNT_REGISTRATIONS_QUERY}) s
+        WHERE ({condition})
+          AND ($3::text IS NULL OR (s.created_at, s.id) > ($2, $3))
+        ORDER BY s.created_at ASC, s.id ASC
+        LIMIT $4
+    ",
+        condition = state.condition(),
+    );
+    // 1 件多く取得して次ページの有無を判定します。
+    let rows: Vec<PgRow> = sqlx::query(&query)
+        .bind(expiring_within)
+        .bind(cursor_created_at)
+        .bind(cursor)
+        .bind(limit + 1)
+        .fetch_all(pool)
+        .await?;
+    let mut accounts: Vec<AccountRegistration> = rows
+        .iter()
+        .map(|row| {
+            let contacts: i64 = row.get("contacts");
+            Ok(AccountRegistration {
+                id: row.get("id"),
+                username: row.get("username"),
+                transport: transport_from_row(row)?,
+                context: row.get("context"),
+                registered: contacts > 0,
+                no_contacts: row.get::<i64, _>("known_contacts") == 0,
+                contacts,
+                expiration_time: row.get("expiration_time"),
+                expires_in: row.get("expires_in"),
+            })
+        })
+        .collect::<Result<_, RetrievalError>>()?;
+    let next_cursor: Option<String> = if accounts.len() as i64 > limit {
+        accounts.truncate(limit as usize);
+        accounts.last().map(|account| account.id.clone())
+    } else {
+        None
+    };
+    Ok((accounts, next_cursor))
+}
+
+// 保存済みの transport が不正な場合は udp として扱わずにエラーにします
+fn transport_from_row(row: &PgRow) -> Result<TransportType, RetrievalError> {
+    let transport_str: String = row.get("transport");
+    TransportType::from_str(&transport_str).map_err(RetrievalError::InvalidRecord)
+}
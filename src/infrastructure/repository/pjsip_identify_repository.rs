@@ -0,0 +1,151 @@
+use crate::infrastructure::models::{
+    errors::{deletion_error::DeletionError, retrieval_error::RetrievalError},
+    pjsip_realtime::sip_identify::{PjsipEndpointIdentifyResponse, PsEndpointIdIp, split_matches},
+};
+use axum::http::StatusCode;
+use sqlx::{
+    PgPool, Postgres, Row, Transaction,
+    postgres::{PgQueryResult, PgRow},
+};
+
+// registration method
+// トランクの match もこの関数で登録します
+pub async fn exec_insert_pjsip_endpoint_id_ip(
+    transaction: &mut Transaction<'_, Postgres>,
+    identify: &PsEndpointIdIp,
+) -> Result<(), sqlx::Error> {
+    let identify_insert: &'static str = r#"
+        insert into ps_endpoint_id_ips (id, endpoint, match, match_header)
+        values ($1, $2, $3, $4)"#;
+    sqlx::query(identify_insert)
+        .bind(&identify.id)
+        .bind(&identify.endpoint)
+        .bind(&identify.r#match)
+        .bind(&identify.match_header)
+        .execute(&mut **transaction)
+        .await?;
+    Ok(())
+}
+
+// deletion method
+pub async fn exec_delete_pjsip_endpoint_id_ip(
+    transaction: &mut Transaction<'_, Postgres>,
+    endpoint_id: &str,
+    identify_id: &str,
+) -> Result<StatusCode, DeletionError> {
+    if identify_id.trim().is_empty() {
+        return Err(DeletionError::IdNotSpecified);
+    }
+
+    let result: PgQueryResult =
+        sqlx::query("DELETE FROM ps_endpoint_id_ips WHERE id = $1 AND endpoint = $2")
+            .bind(identify_id)
+            .bind(endpoint_id)
+            .execute(&mut **transaction)
+            .await?;
+    if result.rows_affected() == 0 {
+        return Err(DeletionError::NotFoundRecord);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// エンドポイントの削除時に、そのエンドポイントの識別をすべて削除します
+pub async fn exec_delete_pjsip_endpoint_id_ips(
+    transaction: &mut Transaction<'_, Postgres>,
+    endpoint_id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM ps_endpoint_id_ips WHERE endpoint = $1")
+        .bind(endpoint_id)
+        .execute(&mut **transaction)
+        .await?;
+    Ok(())
+}
+
+// retrieval method
+pub async fn exists_pjsip_endpoint(pool: &PgPool, endpoint_id: &str) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM ps_endpoints WHERE id = $1)")
+        .bind(endpoint_id)
+        .fetch_one(pool)
+        .await
+}
+
+// 登録順 (id は ULID) で返します
+pub async fn get_pjsip_endpoint_id_ips(
+    pool: &PgPool,
+    endpoint_id: &str,
+) -> Result<Vec<PjsipEndpointIdentifyResponse>, RetrievalError> {
+    if endpoint_id.is_empty() {
+        return Err(RetrievalError::IdNotSpecified);
+    }
+    let query = "
+        SELECT id, endpoint, match, match_header
+        FROM ps_endpoint_id_ips
+        WHERE endpoint = $1
+        ORDER BY id ASC
+    ";
+    let rows: Vec<PgRow> = sqlx::query(query).bind(endpoint_id).fetch_all(pool).await?;
+    Ok(rows
+        .iter()
+        .map(|row| PjsipEndpointIdentifyResponse {
+            id: row.get("id"),
+            endpoint: row.get("endpoint"),
+            matches: row
+                .get::<Option<String>, _>("match")
+                .as_deref()
+                .map(split_matches)
+                .unwrap_or_default(),
+            match_header: row.get("match_header"),
+        })
+        .collect())
+}
+
+pub async fn get_pjsip_endpoint_id_ip_by_id(
+    pool: &PgPool,
+    endpoint_id: &str,
+    identify_id: &str,
+) -> Result<PjsipEndpointIdentifyResponse, RetrievalError> {
+    get_pjsip_endpoint_id_ips(pool, endpoint_id)
+        .await?
+        .into_iter()
+        .find(|identify| identify.id == identify_id)
+        .ok_or(RetrievalError::NotFoundRecord)
+}
+
+// 重なりの判定から登録までの間に他のリクエストが match を登録しないよう、
+// トランザクションの終了まで ps_endpoint_id_ips への書き込みを止めます。
+// (SHARE ROW EXCLUSIVE 同士は競合するため、同時に判定するリクエストは順に処理されます)
+pub async fn lock_pjsip_endpoint_id_ips(
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("LOCK TABLE ps_endpoint_id_ips IN SHARE ROW EXCLUSIVE MODE")
+        .execute(&mut **transaction)
+        .await?;
+    Ok(())
+}
+
+// 他のエンドポイントに登録済みの match を (endpoint, match) の組で返します
+// 重なりの判定に使います。match カラムの "," 区切りは分割します。
+pub async fn get_other_endpoint_matches(
+    transaction: &mut Transaction<'_, Postgres>,
+    endpoint_id: &str,
+) -> Result<Vec<(String, String)>, sqlx::Error> {
+    let query = "
+        SELECT endpoint, match
+        FROM ps_endpoint_id_ips
+        WHERE match IS NOT NULL AND endpoint IS DISTINCT FROM $1
+        ORDER BY endpoint ASC, id ASC
+    ";
+    let rows: Vec<PgRow> = sqlx::query(query)
+        .bind(endpoint_id)
+        .fetch_all(&mut **transaction)
+        .await?;
+    Ok(rows
+        .iter()
+        .flat_map(|row| {
+            let endpoint: String = row.get::<Option<String>, _>("endpoint").unwrap_or_default();
+            split_matches(row.get::<&str, _>("match"))
+                .into_iter()
+                .map(move |value| (endpoint.clone(), value))
+        })
+        .collect())
+}
@@ -1,23 +1,46 @@
 use crate::infrastructure::models::{
-    errors::{deletion_error::DeletionError, registration_error::RegistrationError},
+    errors::{
+        deletion_error::DeletionError, registration_error::RegistrationError,
+        retrieval_error::RetrievalError, update_error::UpdateError,
+    },
     pjsip_realtime::{
-        account::PjsipRealtimeAccountWithId,
-        enums::pjsip_endpoint_enums::TransportType,
-        sip_udp::{PsAorForUdp, PsAuthForUdp, PsEndpointForUdp},
-        sip_ws::{PsAorForWs, PsAuthForWs, PsEndpointForWs},
+        account::{
+            PjsipRealtimeAccountCredentials, PjsipRealtimeAccountResponse,
+            PjsipRealtimeAccountWithId,
+        },
+        account_csv::PjsipRealtimeAccountExport,
+        account_detail::{PjsipRealtimeAccountDetail, PsAorDetail, PsAuthDetail, PsEndpointDetail},
+        account_list::{AccountListParams, AccountPage},
+        auth::AuthCredentials,
+        enums::{
+            pjsip_auth_enums::AuthType,
+            pjsip_endpoint_enums::{RtpTimeout, TransportType},
+        },
+        sip_account::{PjsipAccountRows, PsEndpointForAccount},
+        sip_tls::TlsAccountOptions,
+        sip_ws::DtlsAccountOptions,
     },
 };
 use axum::http::StatusCode;
-use sqlx::{PgPool, Postgres, Row, Transaction, postgres::PgQueryResult};
+use sqlx::{
+    PgPool, Postgres, Row, Transaction,
+    postgres::{PgQueryResult, PgRow},
+};
+use std::str::FromStr;
 
 // registration method
-pub async fn exec_insert_udp_pjsip_account(
+// transport ごとの違いは build_pjsip_account_rows で行に反映済みのため、同じ SQL で登録します。
+// transport によって登録しないカラム (None) は NULL になります。
+pub async fn exec_insert_pjsip_account(
     transaction: &mut Transaction<'_, Postgres>,
-    account: &PjsipRealtimeAccountWithId,
-    auth: &PsAuthForUdp,
-    aor: &PsAorForUdp,
-    endpoint: &PsEndpointForUdp,
+    rows: &PjsipAccountRows,
 ) -> Result<StatusCode, RegistrationError> {
+    let PjsipAccountRows {
+        account,
+        auth,
+        aor,
+        endpoint,
+    } = rows;
     // Validate the input data
     // 事前バリデーション
     if auth.id.is_empty() || aor.id.is_empty() || endpoint.id.is_empty() {
@@ -26,21 +49,7 @@ pub async fn exec_insert_udp_pjsip_account(
         ));
     }
 
-    // check duplicate account
-    // check exist record.
-    let exists: bool = sqlx::query_scalar(
-        r#"SELECT EXISTS(SELECT 1 FROM pjsip_realtime_accounts WHERE id = $1 or username = $2)"#,
-    )
-    .bind(&account.id)
-    .bind(&account.username)
-    .fetch_one(&mut **transaction)
-    .await?;
-
-    // Check if the record count is 0 (no records exist)
-    println!("Checking account with ID {} exists: {}", account.id, exists);
-    if exists {
-        return Err(RegistrationError::DuplicateError);
-    }
+    // 重複 (id / username) はテーブルの一意制約で検出します (RegistrationError::DuplicateError)
 
     // Insert SQL statements for pjsip_realtime tables with placeholders
     // NOTE: This requires the enum definitions to have the correct `#[sqlx(type_name = "...")]` attribute.
@@ -49,20 +58,26 @@ pub async fn exec_insert_udp_pjsip_account(
         (id, username, password, transport, context, from_domain, from_user, created_at, updated_at)
         VALUES ($1, $2, $3, $4, $5, $6, $7, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)"#;
     let auth_insert: &'static str = r#"
-        insert into ps_auths (id, auth_type, password, username)
-        values ($1, $2::pjsip_auth_type_values_v2, $3, $4)"#;
+        insert into ps_auths (id, auth_type, md5_cred, password, realm, username)
+        values ($1, $2::pjsip_auth_type_values_v2, $3, $4, $5, $6)"#;
     let aor_insert: &'static str = r#"
         insert into ps_aors (id, default_expiration, max_contacts, minimum_expiration,
                              qualify_frequency, maximum_expiration, qualify_timeout,
                              remove_existing, remove_unavailable)
         values ($1, $2, $3, $4, $5, $6, $7, $8::ast_bool_values, $9::ast_bool_values)"#;
     let endpoint_insert: &'static str = r#"
-        insert into ps_endpoints (id, transport, auth, aors, context, disallow, allow, direct_media,
+        insert into ps_endpoints (id, transport, aors, auth, context, disallow, allow, direct_media,
                                   force_rport, rewrite_contact, rtp_symmetric, media_encryption,
-                                  from_domain, from_user, dtmf_mode)
-        values ($1, $2::transport_type, $3, $4, $5, $6, $7, $8::ast_bool_values,
+                                  from_domain, from_user, dtmf_mode, rtp_ipv6, ice_support, use_avpf,
+                                  webrtc, max_audio_streams, max_video_streams, rtp_timeout, rtp_timeout_hold,
+                                  dtls_verify, dtls_setup, dtls_fingerprint, dtls_auto_generate_cert,
+                                  rtcp_mux, bundle, media_encryption_optimistic)
+        values ($1, $2, $3, $4, $5, $6, $7, $8::ast_bool_values,
                 $9::ast_bool_values, $10::ast_bool_values, $11::ast_bool_values, $12::pjsip_media_encryption_values,
-                $13, $14, $15::pjsip_dtmf_mode_values_v3)"#;
+                $13, $14, $15::pjsip_dtmf_mode_values_v3, $16::ast_bool_values, $17::ast_bool_values, $18::ast_bool_values,
+                $19::ast_bool_values, $20, $21, $22, $23,
+                $24, $25::pjsip_dtls_setup_values, $26::sha_hash_values, $27::ast_bool_values,
+                $28::ast_bool_values, $29::ast_bool_values, $30::ast_bool_values)"#;
     // TODO : define result types MySqlQueryResult to PgQueryResult after migrate mysql to postgres)
 
     let account_result: PgQueryResult = sqlx::query(account_insert)
@@ -79,7 +94,9 @@ pub async fn exec_insert_udp_pjsip_account(
     let auth_result: PgQueryResult = sqlx::query(auth_insert)
         .bind(&auth.id)
         .bind(auth.auth_type.to_string())
+        .bind(&auth.md5_cred)
         .bind(&auth.password)
+        .bind(&auth.realm)
         .bind(&auth.username)
         .execute(&mut **transaction)
         .await
@@ -99,9 +116,9 @@ pub async fn exec_insert_udp_pjsip_account(
         .map_err(RegistrationError::from)?;
     let endpoint_result: PgQueryResult = sqlx::query(endpoint_insert)
         .bind(&endpoint.id)
-        .bind(endpoint.transport.to_string())
-        .bind(&endpoint.auth)
+        .bind(&endpoint.transport) // TLS はトランスポート名をそのまま登録
         .bind(&endpoint.aors)
+        .bind(&endpoint.auth)
         .bind(&endpoint.context)
         .bind(&endpoint.disallow)
         .bind(&endpoint.allow)
@@ -113,6 +130,31 @@ pub async fn exec_insert_udp_pjsip_account(
         .bind(&endpoint.from_domain)
         .bind(&endpoint.from_user)
         .bind(endpoint.dtmf_mode.to_string())
+        .bind(endpoint.rtp_ipv6.to_string())
+        .bind(endpoint.ice_support.as_ref().map(|v| v.to_string()))
+        .bind(endpoint.use_avpf.as_ref().map(|v| v.to_string()))
+        .bind(endpoint.webrtc.as_ref().map(|v| v.to_string()))
+        .bind(endpoint.max_audio_streams)
+        .bind(endpoint.max_video_streams)
+        .bind(endpoint.rtp_timeout.as_ref().map(|v| v.as_i32()))
+        .bind(endpoint.rtp_timeout_hold.as_ref().map(|v| v.as_i32()))
+        .bind(&endpoint.dtls_verify)
+        .bind(endpoint.dtls_setup.as_ref().map(|v| v.to_string()))
+        .bind(endpoint.dtls_fingerprint.as_ref().map(|v| v.to_string()))
+        .bind(
+            endpoint
+                .dtls_auto_generate_cert
+                .as_ref()
+                .map(|v| v.to_string()),
+        )
+        .bind(endpoint.rtcp_mux.as_ref().map(|v| v.to_string()))
+        .bind(endpoint.bundle.as_ref().map(|v| v.to_string()))
+        .bind(
+            endpoint
+                .media_encryption_optimistic
+                .as_ref()
+                .map(|v| v.to_string()),
+        )
         .execute(&mut **transaction)
         .await
         .map_err(RegistrationError::from)?;
@@ -128,58 +170,106 @@ pub async fn exec_insert_udp_pjsip_account(
     Ok(StatusCode::CREATED)
 }
 
-// registration method for WebSocket transport
-pub async fn exec_insert_ws_pjsip_account(
+// update methods
+// 更新対象のアカウントを行ロック付きで取得します。
+// RTP タイムアウトは ps_endpoints 側にのみ保存されているため結合して取得します。
+pub async fn get_pjsip_account_for_update(
     transaction: &mut Transaction<'_, Postgres>,
-    account: &PjsipRealtimeAccountWithId,
-    auth: &PsAuthForWs,
-    aor: &PsAorForWs,
-    endpoint: &PsEndpointForWs,
-) -> Result<StatusCode, RegistrationError> {
-    // Validate the input data
-    if auth.id.is_empty() || aor.id.is_empty() || endpoint.id.is_empty() {
-        return Err(RegistrationError::ValidationError(
-            "ID cannot be empty".to_string(),
-        ));
+    account_id: &str,
+) -> Result<PjsipRealtimeAccountWithId, UpdateError> {
+    // Validate the account_id
+    if account_id.is_empty() {
+        return Err(UpdateError::IdNotSpecified);
     }
 
-    // check duplicate account
-    let exists: bool = sqlx::query_scalar(
-        r#"SELECT EXISTS(SELECT 1 FROM pjsip_realtime_accounts WHERE id = $1 or username = $2)"#,
+    let query = "
+        SELECT
+            a.id,
+            a.username,
+            a.password,
+            a.transport,
+            a.context,
+            a.from_domain,
+            a.from_user,
+            a.created_at,
+            a.updated_at,
+            e.transport::text AS endpoint_transport,
+            e.media_encryption::text AS endpoint_media_encryption,
+            e.media_encryption_optimistic::text AS endpoint_media_encryption_optimistic,
+            e.rtp_timeout AS endpoint_rtp_timeout,
+            e.rtp_timeout_hold AS endpoint_rtp_timeout_hold,
+            e.dtls_verify AS endpoint_dtls_verify,
+            e.dtls_setup::text AS endpoint_dtls_setup,
+            e.dtls_fingerprint::text AS endpoint_dtls_fingerprint,
+            e.dtls_auto_generate_cert::text AS endpoint_dtls_auto_generate_cert,
+            e.rtcp_mux::text AS endpoint_rtcp_mux,
+            e.bundle::text AS endpoint_bundle
+        FROM pjsip_realtime_accounts a
+        LEFT JOIN ps_endpoints e ON e.id = a.id
+        WHERE a.id = $1
+        FOR UPDATE OF a
+    ";
+
+    let row: PgRow = sqlx::query(query)
+        .bind(account_id)
+        .fetch_optional(&mut **transaction)
+        .await?
+        .ok_or(UpdateError::NotFoundRecord)?;
+
+    Ok(account_from_row(&row))
+}
+
+// 更新対象アカウントの現在の認証情報 (ps_auths) を取得します。
+// md5 の場合は平文を保存していないため、password は None になります。
+pub async fn get_pjsip_auth_for_update(
+    transaction: &mut Transaction<'_, Postgres>,
+    account_id: &str,
+) -> Result<AuthCredentials, UpdateError> {
+    let row: PgRow = sqlx::query(
+        "SELECT auth_type::text AS auth_type, password, md5_cred, realm FROM ps_auths WHERE id = $1 FOR UPDATE",
     )
-    .bind(&account.id)
-    .bind(&account.username)
-    .fetch_one(&mut **transaction)
-    .await?;
+    .bind(account_id)
+    .fetch_optional(&mut **transaction)
+    .await?
+    .ok_or(UpdateError::NotFoundRecord)?;
+
+    Ok(AuthCredentials {
+        auth_type: parse_column(&row, "auth_type").unwrap_or(AuthType::Userpass),
+        password: row.get("password"),
+        md5_cred: row.get("md5_cred"),
+        realm: row.get("realm"),
+    })
+}
 
-    println!("Checking account with ID {} exists: {}", account.id, exists);
-    if exists {
-        return Err(RegistrationError::DuplicateError);
+// 同一トランスポート内での更新 (pjsip_realtime_accounts / ps_auths / ps_endpoints)
+// ps_aors はアカウント項目に依存するカラムを持たないため更新しません。
+// ps_endpoints.transport などのトランスポート固有のカラムは exec_update_pjsip_endpoint_transport で更新します。
+pub async fn exec_update_pjsip_account(
+    transaction: &mut Transaction<'_, Postgres>,
+    account: &PjsipRealtimeAccountWithId,
+    auth: &AuthCredentials,
+) -> Result<StatusCode, UpdateError> {
+    // Validate the account_id
+    if account.id.is_empty() {
+        return Err(UpdateError::IdNotSpecified);
     }
 
-    // Insert SQL statements for pjsip_realtime tables
-    let account_insert: &'static str = r#"
-        INSERT INTO pjsip_realtime_accounts
-        (id, username, password, transport, context, from_domain, from_user, created_at, updated_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)"#;
-    let auth_insert: &'static str = r#"
-        insert into ps_auths (id, auth_type, password, username)
-        values ($1, $2::pjsip_auth_type_values_v2, $3, $4)"#;
-    let aor_insert: &'static str = r#"
-        insert into ps_aors (id, default_expiration, max_contacts, minimum_expiration,
-                             maximum_expiration, remove_existing, remove_unavailable)
-        values ($1, $2, $3, $4, $5, $6::ast_bool_values, $7::ast_bool_values)"#;
-    let endpoint_insert: &'static str = r#"
-        insert into ps_endpoints (id, transport, aors, auth, context, disallow, allow, direct_media,
-                                  force_rport, rewrite_contact, rtp_symmetric, media_encryption,
-                                  from_domain, from_user, dtmf_mode, rtp_ipv6, ice_support, use_avpf,
-                                  webrtc, max_audio_streams, max_video_streams, rtp_timeout, rtp_timeout_hold)
-        values ($1, $2::transport_type, $3, $4, $5, $6, $7, $8::ast_bool_values,
-                $9::ast_bool_values, $10::ast_bool_values, $11::ast_bool_values, $12::pjsip_media_encryption_values,
-                $13, $14, $15::pjsip_dtmf_mode_values_v3, $16::ast_bool_values, $17::ast_bool_values, $18::ast_bool_values,
-                $19::ast_bool_values, $20, $21, $22, $23)"#;
+    // 他アカウントとの username の重複は一意制約で検出します (UpdateError::DuplicateError)
+    let account_update: &'static str = r#"
+        UPDATE pjsip_realtime_accounts
+        SET username = $2, password = $3, transport = $4, context = $5,
+            from_domain = $6, from_user = $7, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1"#;
+    let auth_update: &'static str = r#"
+        update ps_auths set username = $2, auth_type = $3::pjsip_auth_type_values_v2,
+                            md5_cred = $4, password = $5, realm = $6
+        where id = $1"#;
+    let endpoint_update: &'static str = r#"
+        update ps_endpoints set context = $2, from_domain = $3, from_user = $4,
+                                rtp_timeout = $5, rtp_timeout_hold = $6
+        where id = $1"#;
 
-    let account_result: PgQueryResult = sqlx::query(account_insert)
+    let account_result: PgQueryResult = sqlx::query(account_update)
         .bind(&account.id)
         .bind(&account.username)
         .bind(&account.password)
@@ -188,65 +278,86 @@ pub async fn exec_insert_ws_pjsip_account(
         .bind(&account.from_domain)
         .bind(&account.from_user)
         .execute(&mut **transaction)
-        .await
-        .map_err(RegistrationError::from)?;
-    let auth_result: PgQueryResult = sqlx::query(auth_insert)
-        .bind(&auth.id)
+        .await?;
+    if account_result.rows_affected() == 0 {
+        return Err(UpdateError::NotFoundRecord);
+    }
+    let auth_result: PgQueryResult = sqlx::query(auth_update)
+        .bind(&account.id)
+        .bind(&account.username)
         .bind(auth.auth_type.to_string())
+        .bind(&auth.md5_cred)
         .bind(&auth.password)
-        .bind(&auth.username)
+        .bind(&auth.realm)
         .execute(&mut **transaction)
-        .await
-        .map_err(RegistrationError::from)?;
-    let aor_result: PgQueryResult = sqlx::query(aor_insert)
-        .bind(&aor.id)
-        .bind(aor.default_expiration)
-        .bind(aor.max_contacts)
-        .bind(aor.minimum_expiration)
-        .bind(aor.maximum_expiration)
-        .bind(aor.remove_existing.to_string())
-        .bind(aor.remove_unavailable.to_string())
+        .await?;
+    let endpoint_result: PgQueryResult = sqlx::query(endpoint_update)
+        .bind(&account.id)
+        .bind(&account.context)
+        .bind(&account.from_domain)
+        .bind(&account.from_user)
+        .bind(account.rtp_timeout.as_ref().map(|v| v.as_i32()))
+        .bind(account.rtp_timeout_hold.as_ref().map(|v| v.as_i32()))
         .execute(&mut **transaction)
-        .await
-        .map_err(RegistrationError::from)?;
-    let endpoint_result: PgQueryResult = sqlx::query(endpoint_insert)
+        .await?;
+
+    // if any of the updates failed, return an error
+    if auth_result.rows_affected() == 0 || endpoint_result.rows_affected() == 0 {
+        return Err(UpdateError::UpdateFailed);
+    }
+    Ok(StatusCode::OK)
+}
+
+// トランスポート固有の ps_endpoints のカラムを更新します。
+// transport の変更時や TLS / DTLS のオプションの変更時に利用します。
+// codecs / dtmf_mode などのその他のカラムと ps_aors は現在の値を維持します。
+pub async fn exec_update_pjsip_endpoint_transport(
+    transaction: &mut Transaction<'_, Postgres>,
+    endpoint: &PsEndpointForAccount,
+) -> Result<StatusCode, UpdateError> {
+    let endpoint_update: &'static str = r#"
+        update ps_endpoints set transport = $2, media_encryption = $3::pjsip_media_encryption_values,
+                                media_encryption_optimistic = $4::ast_bool_values,
+                                ice_support = $5::ast_bool_values, use_avpf = $6::ast_bool_values,
+                                webrtc = $7::ast_bool_values, max_audio_streams = $8, max_video_streams = $9,
+                                dtls_verify = $10, dtls_setup = $11::pjsip_dtls_setup_values,
+                                dtls_fingerprint = $12::sha_hash_values,
+                                dtls_auto_generate_cert = $13::ast_bool_values,
+                                rtcp_mux = $14::ast_bool_values, bundle = $15::ast_bool_values
+        where id = $1"#;
+
+    let endpoint_result: PgQueryResult = sqlx::query(endpoint_update)
         .bind(&endpoint.id)
-        .bind(endpoint.transport.to_string())
-        .bind(&endpoint.aors)
-        .bind(&endpoint.auth)
-        .bind(&endpoint.context)
-        .bind(&endpoint.disallow)
-        .bind(&endpoint.allow)
-        .bind(endpoint.direct_media.to_string())
-        .bind(endpoint.force_rport.to_string())
-        .bind(endpoint.rewrite_contact.to_string())
-        .bind(endpoint.rtp_symmetric.to_string())
+        .bind(&endpoint.transport) // TLS はトランスポート名をそのまま登録
         .bind(endpoint.media_encryption.to_string())
-        .bind(&endpoint.from_domain)
-        .bind(&endpoint.from_user)
-        .bind(endpoint.dtmf_mode.to_string())
-        .bind(endpoint.rtp_ipv6.to_string())
+        .bind(
+            endpoint
+                .media_encryption_optimistic
+                .as_ref()
+                .map(|v| v.to_string()),
+        )
         .bind(endpoint.ice_support.as_ref().map(|v| v.to_string()))
         .bind(endpoint.use_avpf.as_ref().map(|v| v.to_string()))
         .bind(endpoint.webrtc.as_ref().map(|v| v.to_string()))
         .bind(endpoint.max_audio_streams)
         .bind(endpoint.max_video_streams)
-        .bind(endpoint.rtp_timeout.as_ref().map(|v| v.as_i32()))
-        .bind(endpoint.rtp_timeout_hold.as_ref().map(|v| v.as_i32()))
+        .bind(&endpoint.dtls_verify)
+        .bind(endpoint.dtls_setup.as_ref().map(|v| v.to_string()))
+        .bind(endpoint.dtls_fingerprint.as_ref().map(|v| v.to_string()))
+        .bind(
+            endpoint
+                .dtls_auto_generate_cert
+                .as_ref()
+                .map(|v| v.to_string()),
+        )
+        .bind(endpoint.rtcp_mux.as_ref().map(|v| v.to_string()))
+        .bind(endpoint.bundle.as_ref().map(|v| v.to_string()))
         .execute(&mut **transaction)
-        .await
-        .map_err(RegistrationError::from)?;
-
-    // if any of the insertions failed, return an error
-    if account_result.rows_affected() == 0
-        || auth_result.rows_affected() == 0
-        || aor_result.rows_affected() == 0
-        || endpoint_result.rows_affected() == 0
-    {
-        return Err(RegistrationError::InsertionFailed);
+        .await?;
+    if endpoint_result.rows_affected() == 0 {
+        return Err(UpdateError::UpdateFailed);
     }
-    // return OK status with a success message
-    Ok(StatusCode::CREATED)
+    Ok(StatusCode::OK)
 }
 
 pub async fn exec_delete_pjsip_account(
@@ -254,9 +365,17 @@ pub async fn exec_delete_pjsip_account(
     account_id: String,
 ) -> Result<StatusCode, DeletionError> {
     // Validate the account_id
-    if account_id.is_empty() {
+    if account_id.trim().is_empty() {
         return Err(DeletionError::IdNotSpecified);
     }
+    // pjsip_realtime_accounts.id は VARCHAR(255)
+    if account_id.len() > 255
+        || account_id
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control())
+    {
+        return Err(DeletionError::InvalidId(account_id));
+    }
 
     // check exist record.
     let exists: bool =
@@ -297,52 +416,508 @@ pub async fn exec_delete_pjsip_account(
 }
 
 // get accounts method
-pub async fn get_all_pjsip_accounts(
+pub async fn get_pjsip_accounts_page(
     pool: &PgPool,
-) -> Result<Vec<PjsipRealtimeAccountWithId>, sqlx::Error> {
-    let query = "
-        SELECT 
-            id,
-            username,
-            password,
-            transport,
-            context,
-            from_domain,
-            from_user,
-            created_at,
-            updated_at
-        FROM pjsip_realtime_accounts
-        ORDER BY created_at DESC
+    params: &AccountListParams,
+) -> Result<AccountPage<PjsipRealtimeAccountResponse>, RetrievalError> {
+    // keyset pagination: 前ページ最後のアカウントの (ソートカラム, id) より後ろを取得します。
+    let (cursor_created_at, cursor_username): (Option<chrono::NaiveDateTime>, Option<String>) =
+        match &params.cursor {
+            Some(cursor) => {
+                let row: PgRow = sqlx::query(
+                    "SELECT created_at, username FROM pjsip_realtime_accounts WHERE id = $1",
+                )
+                .bind(cursor)
+                .fetch_optional(pool)
+                .await?
+                .ok_or_else(|| RetrievalError::InvalidCursor(cursor.clone()))?;
+                (row.get("created_at"), Some(row.get("username")))
+            }
+            None => (None, None),
+        };
+
+    // LIKE のワイルドカードをエスケープして前方一致にします。
+    let username_pattern: Option<String> = params.username_prefix.as_ref().map(|prefix| {
+        let escaped = prefix
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        format!("{}%", escaped)
+    });
+
+    let filters = "
+        WHERE ($1::text IS NULL OR transport = $1)
+          AND ($2::text IS NULL OR context = $2)
+          AND ($3::text IS NULL OR from_domain = $3)
+          AND ($4::text IS NULL OR username LIKE $4 ESCAPE '\\')
+          AND ($5::timestamp IS NULL OR created_at >= $5)
+          AND ($6::timestamp IS NULL OR created_at < $6)
     ";
+    let count_query = format!("SELECT COUNT(*) FROM pjsip_realtime_accounts {}", filters);
 
-    let rows = sqlx::query(query).fetch_all(pool).await?;
-
-    let mut accounts = Vec::new();
-    for row in rows {
-        let transport_str: String = row.get("transport");
-        let transport = match transport_str.to_lowercase().as_str() {
-            "udp" => TransportType::Udp,
-            "tcp" => TransportType::Tcp,
-            "tls" => TransportType::Tls,
-            "ws" => TransportType::Ws,
-            "wss" => TransportType::Wss,
-            _ => TransportType::Udp, // default fallback
-        };
+    // ソートカラムと方向は AccountSort の固定値のみ埋め込みます。
+    let column = params.sort.column();
+    let (operator, direction) = if params.sort.is_descending() {
+        ("<", "DESC")
+    } else {
+        (">", "ASC")
+    };
+    let cursor_value = if column == "created_at" { "$8" } else { "$9" };
+    let page_query = format!(
+        "
+        SELECT
+            a.id,
+            a.username,
+            a.password,
+            a.transport,
+            a.context,
+            a.from_domain,
+            a.from_user,
+            a.created_at,
+            a.updated_at,
+            e.transport::text AS endpoint_transport,
+            e.media_encryption::text AS endpoint_media_encryption,
+            e.media_encryption_optimistic::text AS endpoint_media_encryption_optimistic,
+            e.rtp_timeout AS endpoint_rtp_timeout,
+            e.rtp_timeout_hold AS endpoint_rtp_timeout_hold,
+            e.dtls_verify AS endpoint_dtls_verify,
+            e.dtls_setup::text AS endpoint_dtls_setup,
+            e.dtls_fingerprint::text AS endpoint_dtls_fingerprint,
+            e.dtls_auto_generate_cert::text AS endpoint_dtls_auto_generate_cert,
+            e.rtcp_mux::text AS endpoint_rtcp_mux,
+            e.bundle::text AS endpoint_bundle,
+            h.auth_type::text AS auth_auth_type,
+            h.realm AS auth_realm
+        FROM (
+            SELECT *
+            FROM pjsip_realtime_accounts
+            {filters}
+              AND ($10::text IS NULL OR ({column}, id) {operator} ({cursor_value}, $10))
+            ORDER BY {column} {direction}, id {direction}
+            LIMIT $7
+        ) a
+        LEFT JOIN ps_endpoints e ON e.id = a.id
+        LEFT JOIN ps_auths h ON h.id = a.id
+        ORDER BY a.{column} {direction}, a.id {direction}
+    "
+    );
+
+    let transport: Option<String> = params.transport.as_ref().map(|t| t.to_string());
+    let created_from = params.created_from.map(|t| t.naive_utc());
+    let created_to = params.created_to.map(|t| t.naive_utc());
+    let limit: i64 = params.page_limit();
+
+    let total: i64 = sqlx::query_scalar(&count_query)
+        .bind(&transport)
+        .bind(&params.context)
+        .bind(&params.from_domain)
+        .bind(&username_pattern)
+        .bind(created_from)
+        .bind(created_to)
+        .fetch_one(pool)
+        .await?;
+
+    // 1 件多く取得して次ページの有無を判定します。
+    let rows: Vec<PgRow> = sqlx::query(&page_query)
+        .bind(&transport)
+        .bind(&params.context)
+        .bind(&params.from_domain)
+        .bind(&username_pattern)
+        .bind(created_from)
+        .bind(created_to)
+        .bind(limit + 1)
+        .bind(cursor_created_at)
+        .bind(&cursor_username)
+        .bind(&params.cursor)
+        .fetch_all(pool)
+        .await?;
+
+    let mut items: Vec<PjsipRealtimeAccountResponse> =
+        rows.iter().map(account_response_from_row).collect();
+    let next_cursor: Option<String> = if items.len() as i64 > limit {
+        items.truncate(limit as usize);
+        items.last().map(|account| account.id.clone())
+    } else {
+        None
+    };
+
+    Ok(AccountPage {
+        items,
+        next_cursor,
+        total,
+    })
+}
+
+// get single account method
+// pjsip_realtime_accounts に ps_endpoints / ps_aors / ps_auths を結合して 1 件取得します。
+pub async fn get_pjsip_account_by_id(
+    pool: &PgPool,
+    account_id: &str,
+) -> Result<PjsipRealtimeAccountDetail, RetrievalError> {
+    // Validate the account_id
+    if account_id.is_empty() {
+        return Err(RetrievalError::IdNotSpecified);
+    }
+
+    let query = format!("{} WHERE a.id = $1", ACCOUNT_DETAIL_QUERY);
+    let row: PgRow = sqlx::query(&query)
+        .bind(account_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(RetrievalError::NotFoundRecord)?;
+    Ok(account_detail_from_row(&row))
+}
+
+// 複数アカウントの結合ビューを作成日時順で取得します (ids 未指定の場合は全件)。
+pub async fn get_pjsip_account_details(
+    pool: &PgPool,
+    ids: Option<&[String]>,
+) -> Result<Vec<PjsipRealtimeAccountDetail>, RetrievalError> {
+    let query = format!(
+        "{} WHERE ($1::text[] IS NULL OR a.id = ANY($1)) ORDER BY a.created_at ASC, a.id ASC",
+        ACCOUNT_DETAIL_QUERY
+    );
+    let rows: Vec<PgRow> = sqlx::query(&query).bind(ids).fetch_all(pool).await?;
+    Ok(rows.iter().map(account_detail_from_row).collect())
+}
+
+// enum columns are read as text and parsed on the Rust side,
+// because the asterisk enum types differ from the sqlx type names.
+const ACCOUNT_DETAIL_QUERY: &str = "
+        SELECT
+            a.id,
+            a.username,
+            a.password,
+            a.transport,
+            a.context,
+            a.from_domain,
+            a.from_user,
+            a.created_at,
+            a.updated_at,
+            e.id AS endpoint_id,
+            e.transport::text AS endpoint_transport,
+            e.aors AS endpoint_aors,
+            e.auth AS endpoint_auth,
+            e.context AS endpoint_context,
+            e.disallow AS endpoint_disallow,
+            e.allow AS endpoint_allow,
+            e.direct_media::text AS endpoint_direct_media,
+            e.dtmf_mode::text AS endpoint_dtmf_mode,
+            e.force_rport::text AS endpoint_force_rport,
+            e.rewrite_contact::text AS endpoint_rewrite_contact,
+            e.rtp_ipv6::text AS endpoint_rtp_ipv6,
+            e.rtp_symmetric::text AS endpoint_rtp_symmetric,
+            e.media_encryption::text AS endpoint_media_encryption,
+            e.media_encryption_optimistic::text AS endpoint_media_encryption_optimistic,
+            e.from_domain AS endpoint_from_domain,
+            e.from_user AS endpoint_from_user,
+            e.ice_support::text AS endpoint_ice_support,
+            e.use_avpf::text AS endpoint_use_avpf,
+            e.webrtc::text AS endpoint_webrtc,
+            e.max_audio_streams AS endpoint_max_audio_streams,
+            e.max_video_streams AS endpoint_max_video_streams,
+            e.rtp_timeout AS endpoint_rtp_timeout,
+            e.rtp_timeout_hold AS endpoint_rtp_timeout_hold,
+            e.dtls_verify AS endpoint_dtls_verify,
+            e.dtls_setup::text AS endpoint_dtls_setup,
+            e.dtls_fingerprint::text AS endpoint_dtls_fingerprint,
+            e.dtls_auto_generate_cert::text AS endpoint_dtls_auto_generate_cert,
+            e.rtcp_mux::text AS endpoint_rtcp_mux,
+            e.bundle::text AS endpoint_bundle,
+            r.id AS aor_id,
+            r.default_expiration AS aor_default_expiration,
+            r.max_contacts AS aor_max_contacts,
+            r.minimum_expiration AS aor_minimum_expiration,
+            r.maximum_expiration AS aor_maximum_expiration,
+            r.qualify_frequency AS aor_qualify_frequency,
+            r.qualify_timeout::float8 AS aor_qualify_timeout,
+            r.remove_existing::text AS aor_remove_existing,
+            r.remove_unavailable::text AS aor_remove_unavailable,
+            h.id AS auth_id,
+            h.auth_type::text AS auth_auth_type,
+            h.realm AS auth_realm,
+            h.username AS auth_username,
+            EXISTS(
+                SELECT 1 FROM ps_contacts c
+                WHERE split_part(c.id, '^3B@', 1) = a.id
+                  AND c.expiration_time > EXTRACT(EPOCH FROM CURRENT_TIMESTAMP)
+            ) AS registered
+        FROM pjsip_realtime_accounts a
+        LEFT JOIN ps_endpoints e ON e.id = a.id
+        LEFT JOIN ps_aors r ON r.id = a.id
+        LEFT JOIN ps_auths h ON h.id = a.id
+";
+
+fn account_detail_from_row(row: &PgRow) -> PjsipRealtimeAccountDetail {
+    let endpoint = row
+        .get::<Option<String>, _>("endpoint_id")
+        .map(|id| PsEndpointDetail {
+            id,
+            transport: row.get("endpoint_transport"),
+            aors: row.get("endpoint_aors"),
+            auth: row.get("endpoint_auth"),
+            context: row.get("endpoint_context"),
+            disallow: row.get("endpoint_disallow"),
+            allow: row.get("endpoint_allow"),
+            direct_media: parse_column(row, "endpoint_direct_media"),
+            dtmf_mode: parse_column(row, "endpoint_dtmf_mode"),
+            force_rport: parse_column(row, "endpoint_force_rport"),
+            rewrite_contact: parse_column(row, "endpoint_rewrite_contact"),
+            rtp_ipv6: parse_column(row, "endpoint_rtp_ipv6"),
+            rtp_symmetric: parse_column(row, "endpoint_rtp_symmetric"),
+            media_encryption: parse_column(row, "endpoint_media_encryption"),
+            media_encryption_optimistic: parse_column(row, "endpoint_media_encryption_optimistic"),
+            from_domain: row.get("endpoint_from_domain"),
+            from_user: row.get("endpoint_from_user"),
+            ice_support: parse_column(row, "endpoint_ice_support"),
+            use_avpf: parse_column(row, "endpoint_use_avpf"),
+            webrtc: parse_column(row, "endpoint_webrtc"),
+            max_audio_streams: row.get("endpoint_max_audio_streams"),
+            max_video_streams: row.get("endpoint_max_video_streams"),
+            rtp_timeout: rtp_timeout_column(row, "endpoint_rtp_timeout"),
+            rtp_timeout_hold: rtp_timeout_column(row, "endpoint_rtp_timeout_hold"),
+            dtls_verify: row.get("endpoint_dtls_verify"),
+            dtls_setup: parse_column(row, "endpoint_dtls_setup"),
+            dtls_fingerprint: parse_column(row, "endpoint_dtls_fingerprint"),
+            dtls_auto_generate_cert: parse_column(row, "endpoint_dtls_auto_generate_cert"),
+            rtcp_mux: parse_column(row, "endpoint_rtcp_mux"),
+            bundle: parse_column(row, "endpoint_bundle"),
+        });
 
-        accounts.push(PjsipRealtimeAccountWithId {
-            id: row.get("id"),
-            username: row.get("username"),
-            password: row.get("password"),
-            transport,
-            context: row.get("context"),
-            from_domain: row.get("from_domain"),
-            from_user: row.get("from_user"),
-            rtp_timeout: None,
-            rtp_timeout_hold: None,
-            created_at: row.get::<chrono::NaiveDateTime, _>("created_at").and_utc(),
-            updated_at: row.get::<chrono::NaiveDateTime, _>("updated_at").and_utc(),
+    let aor = row
+        .get::<Option<String>, _>("aor_id")
+        .map(|id| PsAorDetail {
+            id,
+            default_expiration: row.get("aor_default_expiration"),
+            max_contacts: row.get("aor_max_contacts"),
+            minimum_expiration: row.get("aor_minimum_expiration"),
+            maximum_expiration: row.get("aor_maximum_expiration"),
+            qualify_frequency: row.get("aor_qualify_frequency"),
+            qualify_timeout: row.get("aor_qualify_timeout"),
+            remove_existing: parse_column(row, "aor_remove_existing"),
+            remove_unavailable: parse_column(row, "aor_remove_unavailable"),
         });
+
+    let auth = row
+        .get::<Option<String>, _>("auth_id")
+        .map(|id| PsAuthDetail {
+            id,
+            auth_type: parse_column(row, "auth_auth_type"),
+            realm: row.get("auth_realm"),
+            username: row.get("auth_username"),
+        });
+
+    PjsipRealtimeAccountDetail {
+        account: account_response_from_row(row),
+        registered: row.get("registered"),
+        endpoint,
+        aor,
+        auth,
+    }
+}
+
+// get credentials method
+// パスワードを含む認証情報を取得します。呼び出し元で参照権限を確認してください。
+pub async fn get_pjsip_account_credentials(
+    pool: &PgPool,
+    account_id: &str,
+) -> Result<PjsipRealtimeAccountCredentials, RetrievalError> {
+    // Validate the account_id
+    if account_id.is_empty() {
+        return Err(RetrievalError::IdNotSpecified);
+    }
+
+    // md5 のアカウントは ps_auths 側の md5_cred / realm を返します。
+    let query = "
+        SELECT
+            a.id,
+            a.username,
+            h.auth_type::text AS auth_type,
+            h.password,
+            h.md5_cred,
+            h.realm
+        FROM pjsip_realtime_accounts a
+        LEFT JOIN ps_auths h ON h.id = a.id
+        WHERE a.id = $1
+    ";
+
+    let row: PgRow = sqlx::query(query)
+        .bind(account_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(RetrievalError::NotFoundRecord)?;
+
+    Ok(account_credentials_from_row(&row))
+}
+
+// pjsip.conf のエクスポート (include_secrets) 用に、複数アカウントの認証情報を取得します
+pub async fn get_pjsip_accounts_credentials(
+    pool: &PgPool,
+    ids: Option<&[String]>,
+) -> Result<Vec<PjsipRealtimeAccountCredentials>, RetrievalError> {
+    let query = "
+        SELECT
+            a.id,
+            a.username,
+            h.auth_type::text AS auth_type,
+            h.password,
+            h.md5_cred,
+            h.realm
+        FROM pjsip_realtime_accounts a
+        LEFT JOIN ps_auths h ON h.id = a.id
+        WHERE ($1::text[] IS NULL OR a.id = ANY($1))
+    ";
+    let rows: Vec<PgRow> = sqlx::query(query).bind(ids).fetch_all(pool).await?;
+    Ok(rows.iter().map(account_credentials_from_row).collect())
+}
+
+fn account_credentials_from_row(row: &PgRow) -> PjsipRealtimeAccountCredentials {
+    PjsipRealtimeAccountCredentials {
+        id: row.get("id"),
+        username: row.get("username"),
+        auth_type: parse_column(row, "auth_type"),
+        password: row.get("password"),
+        md5_cred: row.get("md5_cred"),
+        realm: row.get("realm"),
     }
+}
+
+// export method
+// CSV エクスポート用にアカウントを作成日時順で取得します (ids 未指定の場合は全件、パスワードは含みません)。
+pub async fn get_pjsip_accounts_for_export(
+    pool: &PgPool,
+    ids: Option<&[String]>,
+) -> Result<Vec<PjsipRealtimeAccountExport>, RetrievalError> {
+    let query = "
+        SELECT
+            a.id,
+            a.username,
+            a.transport,
+            a.context,
+            a.from_domain,
+            a.from_user,
+            e.rtp_timeout AS endpoint_rtp_timeout,
+            e.rtp_timeout_hold AS endpoint_rtp_timeout_hold,
+            h.auth_type::text AS auth_type,
+            h.realm
+        FROM pjsip_realtime_accounts a
+        LEFT JOIN ps_endpoints e ON e.id = a.id
+        LEFT JOIN ps_auths h ON h.id = a.id
+        WHERE ($1::text[] IS NULL OR a.id = ANY($1))
+        ORDER BY a.created_at ASC, a.id ASC
+    ";
+
+    let rows: Vec<PgRow> = sqlx::query(query).bind(ids).fetch_all(pool).await?;
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let transport_str: String = row.get("transport");
+            PjsipRealtimeAccountExport {
+                id: row.get("id"),
+                username: row.get("username"),
+                transport: TransportType::from_str(&transport_str).unwrap_or(TransportType::Udp),
+                context: row.get("context"),
+                from_domain: row.get("from_domain"),
+                from_user: row.get("from_user"),
+                rtp_timeout: rtp_timeout_column(row, "endpoint_rtp_timeout"),
+                rtp_timeout_hold: rtp_timeout_column(row, "endpoint_rtp_timeout_hold"),
+                auth_type: parse_column(row, "auth_type"),
+                realm: row.get("realm"),
+            }
+        })
+        .collect())
+}
+
+// 登録済みの id / username のうち、指定したものを返します (インポート前の重複確認用)。
+pub async fn get_existing_account_keys(
+    pool: &PgPool,
+    ids: &[String],
+    usernames: &[String],
+) -> Result<Vec<(String, String)>, RetrievalError> {
+    let rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT id, username FROM pjsip_realtime_accounts WHERE id = ANY($1) OR username = ANY($2)",
+    )
+    .bind(ids)
+    .bind(usernames)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+// row mapping helpers
+// RTP タイムアウトは ps_endpoints 側に保存されているため、
+// 呼び出し側のクエリで endpoint_rtp_timeout / endpoint_rtp_timeout_hold として結合してください。
+fn account_from_row(row: &PgRow) -> PjsipRealtimeAccountWithId {
+    let transport_str: String = row.get("transport");
+    let transport = TransportType::from_str(&transport_str).unwrap_or(TransportType::Udp); // default fallback
+
+    PjsipRealtimeAccountWithId {
+        id: row.get("id"),
+        username: row.get("username"),
+        password: row.get("password"),
+        transport: transport.clone(),
+        context: row.get("context"),
+        from_domain: row.get("from_domain"),
+        from_user: row.get("from_user"),
+        rtp_timeout: rtp_timeout_column(row, "endpoint_rtp_timeout"),
+        rtp_timeout_hold: rtp_timeout_column(row, "endpoint_rtp_timeout_hold"),
+        tls: tls_options_from_row(row, &transport),
+        dtls: dtls_options_from_row(row, &transport),
+        created_at: row.get::<chrono::NaiveDateTime, _>("created_at").and_utc(),
+        updated_at: row.get::<chrono::NaiveDateTime, _>("updated_at").and_utc(),
+    }
+}
+
+// API レスポンス用のアカウント
+// auth_type / realm は結合した ps_auths (auth_auth_type / auth_realm) から取得します。
+fn account_response_from_row(row: &PgRow) -> PjsipRealtimeAccountResponse {
+    let account: PjsipRealtimeAccountWithId = account_from_row(row);
+    PjsipRealtimeAccountResponse {
+        id: account.id,
+        username: account.username,
+        transport: account.transport,
+        context: account.context,
+        from_domain: account.from_domain,
+        from_user: account.from_user,
+        rtp_timeout: account.rtp_timeout,
+        rtp_timeout_hold: account.rtp_timeout_hold,
+        auth_type: parse_column(row, "auth_auth_type"),
+        realm: row.get("auth_realm"),
+        tls: account.tls,
+        dtls: account.dtls,
+        created_at: account.created_at,
+        updated_at: account.updated_at,
+    }
+}
+
+// TLS のオプションは ps_endpoints の transport / media_encryption から復元します。
+fn tls_options_from_row(row: &PgRow, transport: &TransportType) -> Option<TlsAccountOptions> {
+    (*transport == TransportType::Tls).then(|| TlsAccountOptions {
+        transport_name: row.get("endpoint_transport"),
+        media_encryption: parse_column(row, "endpoint_media_encryption"),
+        media_encryption_optimistic: parse_column(row, "endpoint_media_encryption_optimistic"),
+    })
+}
+
+// DTLS のオプションは ps_endpoints の dtls_* / rtcp_mux / bundle から復元します。
+fn dtls_options_from_row(row: &PgRow, transport: &TransportType) -> Option<DtlsAccountOptions> {
+    matches!(transport, TransportType::Ws | TransportType::Wss).then(|| DtlsAccountOptions {
+        dtls_verify: row.get("endpoint_dtls_verify"),
+        dtls_setup: parse_column(row, "endpoint_dtls_setup"),
+        dtls_fingerprint: parse_column(row, "endpoint_dtls_fingerprint"),
+        dtls_auto_generate_cert: parse_column(row, "endpoint_dtls_auto_generate_cert"),
+        rtcp_mux: parse_column(row, "endpoint_rtcp_mux"),
+        bundle: parse_column(row, "endpoint_bundle"),
+    })
+}
+
+// enum columns selected as text; unknown values are treated as NULL
+fn parse_column<T: FromStr>(row: &PgRow, column: &str) -> Option<T> {
+    row.get::<Option<String>, _>(column)
+        .and_then(|value| T::from_str(&value).ok())
+}
 
-    Ok(accounts)
+fn rtp_timeout_column(row: &PgRow, column: &str) -> Option<RtpTimeout> {
+    row.get::<Option<i32>, _>(column)
+        .and_then(|value| RtpTimeout::try_from(value).ok())
 }
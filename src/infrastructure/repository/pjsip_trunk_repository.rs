@@ -0,0 +1,297 @@
+use crate::infrastructure::models::{
+    errors::{
+        deletion_error::DeletionError, registration_error::RegistrationError,
+        retrieval_error::RetrievalError, update_error::UpdateError,
+    },
+    pjsip_realtime::{
+        enums::pjsip_endpoint_enums::TransportType,
+        sip_trunk::{PjsipRealtimeTrunk, PjsipRealtimeTrunkResponse, PjsipTrunkRows},
+    },
+};
+use crate::infrastructure::repository::pjsip_identify_repository::exec_insert_pjsip_endpoint_id_ip;
+use axum::http::StatusCode;
+use sqlx::{
+    PgPool, Postgres, Row, Transaction,
+    postgres::{PgQueryResult, PgRow},
+};
+use std::str::FromStr;
+
+// registration method
+// 名前の重複は一意制約で検出します (RegistrationError::DuplicateError(Some("name")))
+pub async fn exec_insert_pjsip_trunk(
+    transaction: &mut Transaction<'_, Postgres>,
+    trunk_id: &str,
+    trunk: &PjsipRealtimeTrunk,
+    rows: &PjsipTrunkRows,
+) -> Result<StatusCode, RegistrationError> {
+    if trunk_id.is_empty() {
+        return Err(RegistrationError::ValidationError(
+            "ID cannot be empty".to_string(),
+        ));
+    }
+
+    let trunk_insert: &'static str = r#"
+        INSERT INTO pjsip_realtime_trunks
+        (id, name, transport, server_uri, context, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)"#;
+    let trunk_result: PgQueryResult = sqlx::query(trunk_insert)
+        .bind(trunk_id)
+        .bind(&trunk.name)
+        .bind(trunk.transport.to_string())
+        .bind(&trunk.server_uri)
+        .bind(&trunk.context)
+        .execute(&mut **transaction)
+        .await
+        .map_err(RegistrationError::from)?;
+    if trunk_result.rows_affected() == 0 {
+        return Err(RegistrationError::InsertionFailed);
+    }
+    insert_pjsip_trunk_rows(transaction, rows)
+        .await
+        .map_err(RegistrationError::from)?;
+    Ok(StatusCode::CREATED)
+}
+
+// update method
+// ps_* の行は削除して登録し直します。
+// ps_endpoint_id_ips はトランクの match (match_header のない行) のみ置き換え、
+// identifies API で追加した match_header の行は残します。
+pub async fn exec_update_pjsip_trunk(
+    transaction: &mut Transaction<'_, Postgres>,
+    trunk_id: &str,
+    trunk: &PjsipRealtimeTrunk,
+    rows: &PjsipTrunkRows,
+) -> Result<StatusCode, UpdateError> {
+    if trunk_id.is_empty() {
+        return Err(UpdateError::IdNotSpecified);
+    }
+
+    let trunk_update: &'static str = r#"
+        UPDATE pjsip_realtime_trunks
+        SET name = $2, transport = $3, server_uri = $4, context = $5,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1"#;
+    let trunk_result: PgQueryResult = sqlx::query(trunk_update)
+        .bind(trunk_id)
+        .bind(&trunk.name)
+        .bind(trunk.transport.to_string())
+        .bind(&trunk.server_uri)
+        .bind(&trunk.context)
+        .execute(&mut **transaction)
+        .await?;
+    if trunk_result.rows_affected() == 0 {
+        return Err(UpdateError::NotFoundRecord);
+    }
+    sqlx::query("DELETE FROM ps_endpoint_id_ips WHERE endpoint = $1 AND match_header IS NULL")
+        .bind(trunk_id)
+        .execute(&mut **transaction)
+        .await?;
+    delete_pjsip_trunk_rows(transaction, trunk_id).await?;
+    insert_pjsip_trunk_rows(transaction, rows).await?;
+    Ok(StatusCode::OK)
+}
+
+// deletion method
+pub async fn exec_delete_pjsip_trunk(
+    transaction: &mut Transaction<'_, Postgres>,
+    trunk_id: &str,
+) -> Result<StatusCode, DeletionError> {
+    if trunk_id.trim().is_empty() {
+        return Err(DeletionError::IdNotSpecified);
+    }
+
+    let trunk_result: PgQueryResult =
+        sqlx::query("DELETE FROM pjsip_realtime_trunks WHERE id = $1")
+            .bind(trunk_id)
+            .execute(&mut **transaction)
+            .await?;
+    if trunk_result.rows_affected() == 0 {
+        return Err(DeletionError::NotFoundRecord);
+    }
+    sqlx::query("DELETE FROM ps_endpoint_id_ips WHERE endpoint = $1")
+        .bind(trunk_id)
+        .execute(&mut **transaction)
+        .await?;
+    delete_pjsip_trunk_rows(transaction, trunk_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn insert_pjsip_trunk_rows(
+    transaction: &mut Transaction<'_, Postgres>,
+    rows: &PjsipTrunkRows,
+) -> Result<(), sqlx::Error> {
+    let registration_insert: &'static str = r#"
+        insert into ps_registrations (id, auth_rejection_permanent, client_uri, contact_user,
+                                      expiration, max_retries, outbound_auth, outbound_proxy,
+                                      retry_interval, forbidden_retry_interval, server_uri,
+                                      transport, line, endpoint)
+        values ($1, $2::ast_bool_values, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12,
+                $13::ast_bool_values, $14)"#;
+    let auth_insert: &'static str = r#"
+        insert into ps_auths (id, auth_type, password, realm, username)
+        values ($1, $2::pjsip_auth_type_values_v2, $3, $4, $5)"#;
+    let aor_insert: &'static str = r#"
+        insert into ps_aors (id, contact, max_contacts, qualify_frequency)
+        values ($1, $2, $3, $4)"#;
+    let endpoint_insert: &'static str = r#"
+        insert into ps_endpoints (id, transport, aors, outbound_auth, context, disallow, allow,
+                                  direct_media, dtmf_mode, force_rport, rewrite_contact,
+                                  rtp_symmetric, from_domain, from_user, outbound_proxy)
+        values ($1, $2, $3, $4, $5, $6, $7, $8::ast_bool_values, $9::pjsip_dtmf_mode_values_v3,
+                $10::ast_bool_values, $11::ast_bool_values, $12::ast_bool_values, $13, $14, $15)"#;
+
+    let registration = &rows.registration;
+    sqlx::query(registration_insert)
+        .bind(&registration.id)
+        .bind(registration.auth_rejection_permanent.to_string())
+        .bind(&registration.client_uri)
+        .bind(&registration.contact_user)
+        .bind(registration.expiration)
+        .bind(registration.max_retries)
+        .bind(&registration.outbound_auth)
+        .bind(&registration.outbound_proxy)
+        .bind(registration.retry_interval)
+        .bind(registration.forbidden_retry_interval)
+        .bind(&registration.server_uri)
+        .bind(&registration.transport)
+        .bind(registration.line.to_string())
+        .bind(&registration.endpoint)
+        .execute(&mut **transaction)
+        .await?;
+    let auth = &rows.auth;
+    sqlx::query(auth_insert)
+        .bind(&auth.id)
+        .bind(auth.auth_type.to_string())
+        .bind(&auth.password)
+        .bind(&auth.realm)
+        .bind(&auth.username)
+        .execute(&mut **transaction)
+        .await?;
+    let aor = &rows.aor;
+    sqlx::query(aor_insert)
+        .bind(&aor.id)
+        .bind(&aor.contact)
+        .bind(aor.max_contacts)
+        .bind(aor.qualify_frequency)
+        .execute(&mut **transaction)
+        .await?;
+    let endpoint = &rows.endpoint;
+    sqlx::query(endpoint_insert)
+        .bind(&endpoint.id)
+        .bind(&endpoint.transport)
+        .bind(&endpoint.aors)
+        .bind(&endpoint.outbound_auth)
+        .bind(&endpoint.context)
+        .bind(&endpoint.disallow)
+        .bind(&endpoint.allow)
+        .bind(endpoint.direct_media.to_string())
+        .bind(endpoint.dtmf_mode.to_string())
+        .bind(endpoint.force_rport.to_string())
+        .bind(endpoint.rewrite_contact.to_string())
+        .bind(endpoint.rtp_symmetric.to_string())
+        .bind(&endpoint.from_domain)
+        .bind(&endpoint.from_user)
+        .bind(&endpoint.outbound_proxy)
+        .execute(&mut **transaction)
+        .await?;
+    for identify in &rows.identifies {
+        exec_insert_pjsip_endpoint_id_ip(transaction, identify).await?;
+    }
+    Ok(())
+}
+
+// ps_endpoint_id_ips は呼び出し元で削除します
+async fn delete_pjsip_trunk_rows(
+    transaction: &mut Transaction<'_, Postgres>,
+    trunk_id: &str,
+) -> Result<(), sqlx::Error> {
+    for table in ["ps_registrations", "ps_endpoints", "ps_aors", "ps_auths"] {
+        sqlx::query(&format!("DELETE FROM {} WHERE id = $1", table))
+            .bind(trunk_id)
+            .execute(&mut **transaction)
+            .await?;
+    }
+    Ok(())
+}
+
+// retrieval method
+// trunk_id を指定しない場合は全件を作成日時順で返します
+// matches は match_header のない行のみです (PUT で置き換える範囲と同じ)
+pub async fn get_pjsip_trunks(
+    pool: &PgPool,
+    trunk_id: Option<&str>,
+) -> Result<Vec<PjsipRealtimeTrunkResponse>, RetrievalError> {
+    let query = "
+        SELECT
+            t.id,
+            t.name,
+            t.transport,
+            t.server_uri,
+            t.context,
+            t.created_at,
+            t.updated_at,
+            r.client_uri,
+            r.contact_user,
+            r.outbound_proxy,
+            r.expiration,
+            r.retry_interval,
+            r.max_retries,
+            h.username,
+            h.realm,
+            e.from_domain,
+            e.from_user,
+            ARRAY(
+                SELECT m.value
+                FROM ps_endpoint_id_ips i
+                CROSS JOIN LATERAL unnest(string_to_array(i.match, ','))
+                    WITH ORDINALITY AS m(value, position)
+                WHERE i.endpoint = t.id AND i.match_header IS NULL
+                ORDER BY i.id, m.position
+            ) AS matches
+        FROM pjsip_realtime_trunks t
+        LEFT JOIN ps_registrations r ON r.id = t.id
+        LEFT JOIN ps_auths h ON h.id = t.id
+        LEFT JOIN ps_endpoints e ON e.id = t.id
+        WHERE ($1::text IS NULL OR t.id = $1)
+        ORDER BY t.created_at ASC, t.id ASC
+    ";
+    let rows: Vec<PgRow> = sqlx::query(query).bind(trunk_id).fetch_all(pool).await?;
+    Ok(rows.iter().map(trunk_from_row).collect())
+}
+
+pub async fn get_pjsip_trunk_by_id(
+    pool: &PgPool,
+    trunk_id: &str,
+) -> Result<PjsipRealtimeTrunkResponse, RetrievalError> {
+    if trunk_id.is_empty() {
+        return Err(RetrievalError::IdNotSpecified);
+    }
+    get_pjsip_trunks(pool, Some(trunk_id))
+        .await?
+        .pop()
+        .ok_or(RetrievalError::NotFoundRecord)
+}
+
+fn trunk_from_row(row: &PgRow) -> PjsipRealtimeTrunkResponse {
+    let transport_str: String = row.get("transport");
+    PjsipRealtimeTrunkResponse {
+        id: row.get("id"),
+        name: row.get("name"),
+        transport: TransportType::from_str(&transport_str).unwrap_or(TransportType::Udp),
+        server_uri: row.get("server_uri"),
+        client_uri: row.get("client_uri"),
+        username: row.get("username"),
+        realm: row.get("realm"),
+        context: row.get("context"),
+        from_domain: row.get("from_domain"),
+        from_user: row.get("from_user"),
+        contact_user: row.get("contact_user"),
+        outbound_proxy: row.get("outbound_proxy"),
+        expiration: row.get("expiration"),
+        retry_interval: row.get("retry_interval"),
+        max_retries: row.get("max_retries"),
+        matches: row.get("matches"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
@@ -1,4 +1,6 @@
 mod application;
+mod cli;
+mod config;
 mod infrastructure;
 mod restapi;
 
@@ -6,15 +8,35 @@ mod restapi;
 mod tests;
 
 use axum::Router;
+use config::AppConfig;
 use dotenvy::dotenv;
+use infrastructure::auth::jwt_authenticator::JwtAuthenticator;
 use sqlx::{PgPool, postgres::PgPoolOptions};
 use std::env;
+use std::sync::Arc;
 use tokio::net::TcpListener;
 use tracing::{debug, info};
 
 #[derive(Clone)]
 struct AppState {
     pjsip_db: PgPool,
+    config: Arc<AppConfig>,
+    authenticator: Arc<JwtAuthenticator>,
+}
+
+impl AppState {
+    // 設定は環境変数から読み込みます
+    fn new(pjsip_db: PgPool) -> Self {
+        AppState::with_config(pjsip_db, AppConfig::from_env())
+    }
+
+    fn with_config(pjsip_db: PgPool, config: AppConfig) -> Self {
+        AppState {
+            pjsip_db,
+            authenticator: Arc::new(JwtAuthenticator::new(&config)),
+            config: Arc::new(config),
+        }
+    }
 }
 
 async fn create_pjsip_pool() -> Result<PgPool, sqlx::Error> {
@@ -75,6 +97,12 @@ async fn main() {
     // configurations
     dotenv().ok();
 
+    // subcommands
+    let args: Vec<String> = env::args().collect();
+    if args.get(1).map(String::as_str) == Some(cli::IMPORT_PJSIP_CONF_COMMAND) {
+        std::process::exit(cli::run_import_pjsip_conf(&args[2..]).await);
+    }
+
     info!("Starting AI Talker API...");
     info!("Loading database configuration...");
 
@@ -89,7 +117,7 @@ async fn main() {
     match create_pjsip_pool().await {
         Ok(pool) => {
             info!("Database connection pool created successfully");
-            let state = AppState { pjsip_db: pool };
+            let state = AppState::new(pool);
 
             let router: Router = restapi::routes::root::create_router(state.clone());
 
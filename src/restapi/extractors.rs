@@ -0,0 +1,50 @@
+use axum::Json;
+use axum::extract::{FromRequest, FromRequestParts, Query, Request};
+use axum::http::request::Parts;
+use serde::de::DeserializeOwned;
+
+use crate::infrastructure::models::errors::api_error::{ApiError, ErrorCode};
+
+// axum::Json と同じですが、不正なボディを problem+json (invalid_request_body) で返します。
+// ステータスは axum の判定 (400 / 415 / 422) をそのまま使います。
+pub struct ApiJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match Json::<T>::from_request(req, state).await {
+            Ok(Json(value)) => Ok(ApiJson(value)),
+            Err(rejection) => Err(ApiError::new(
+                ErrorCode::InvalidRequestBody,
+                rejection.body_text(),
+            )
+            .with_status(rejection.status())),
+        }
+    }
+}
+
+// axum::extract::Query と同じですが、不正なクエリを problem+json (invalid_query) で返します。
+pub struct ApiQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ApiQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match Query::<T>::from_request_parts(parts, state).await {
+            Ok(Query(value)) => Ok(ApiQuery(value)),
+            Err(rejection) => Err(
+                ApiError::new(ErrorCode::InvalidQuery, rejection.body_text())
+                    .with_status(rejection.status()),
+            ),
+        }
+    }
+}
@@ -1,2 +1,5 @@
+pub(crate) mod api_key_handler;
 pub(crate) mod heart_beat_handler;
+pub(crate) mod pjsip_identify_handler;
 pub(crate) mod pjsip_realtime_handler;
+pub(crate) mod pjsip_trunk_handler;
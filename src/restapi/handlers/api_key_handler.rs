@@ -0,0 +1,41 @@
+use axum::Extension;
+use axum::extract::Path;
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+
+use crate::AppState;
+use crate::application::authorization::authorize;
+use crate::application::repository::api_keys::{issue_api_key, list_api_keys, revoke_api_key};
+use crate::infrastructure::models::authentication::{
+    api_key::IssueApiKeyRequest, authorization_policy::Permission, principal::Principal,
+};
+use crate::infrastructure::models::errors::api_error::ApiError;
+use crate::restapi::extractors::ApiJson;
+
+pub async fn issue_api_key_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    ApiJson(payload): ApiJson<IssueApiKeyRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::ManageApiKeys)?;
+    let created_by: Option<String> = principal.as_deref().map(Principal::subject);
+    let issued = issue_api_key(state, payload, created_by).await?;
+    Ok((StatusCode::CREATED, Json(issued)))
+}
+
+pub async fn list_api_keys_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::ManageApiKeys)?;
+    let keys = list_api_keys(state).await?;
+    Ok((StatusCode::OK, Json(keys)))
+}
+
+pub async fn revoke_api_key_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path(key_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::ManageApiKeys)?;
+    revoke_api_key(state, key_id).await
+}
@@ -0,0 +1,45 @@
+use axum::Extension;
+use axum::extract::Path;
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+
+use crate::AppState;
+use crate::application::authorization::authorize;
+use crate::application::repository::pjsip_identifies::{
+    create_pjsip_endpoint_identify, delete_pjsip_endpoint_identify, list_pjsip_endpoint_identifies,
+};
+use crate::infrastructure::models::authentication::{
+    authorization_policy::Permission, principal::Principal,
+};
+use crate::infrastructure::models::errors::api_error::ApiError;
+use crate::infrastructure::models::pjsip_realtime::sip_identify::PjsipEndpointIdentify;
+use crate::restapi::extractors::ApiJson;
+
+pub async fn get_pjsip_endpoint_identifies_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path(endpoint_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::ReadIdentifies)?;
+    let identifies = list_pjsip_endpoint_identifies(state, endpoint_id).await?;
+    Ok((StatusCode::OK, Json(identifies)))
+}
+
+pub async fn create_pjsip_endpoint_identify_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path(endpoint_id): Path<String>,
+    ApiJson(payload): ApiJson<PjsipEndpointIdentify>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::ManageIdentifies)?;
+    let identify = create_pjsip_endpoint_identify(state, endpoint_id, payload).await?;
+    Ok((StatusCode::CREATED, Json(identify)))
+}
+
+pub async fn delete_pjsip_endpoint_identify_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path((endpoint_id, identify_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::ManageIdentifies)?;
+    delete_pjsip_endpoint_identify(state, endpoint_id, identify_id).await
+}
@@ -1,115 +1,201 @@
+use axum::Extension;
 use axum::extract::Path;
+use axum::extract::multipart::{Multipart, MultipartRejection};
+use axum::http::HeaderMap;
+use axum::http::header;
 use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
-use serde_json::Value;
+use serde::Deserialize;
 
 use crate::AppState;
+use crate::application::authorization::authorize;
+use crate::application::repository::pjsip_contacts::{
+    get_pjsip_account_contacts, get_pjsip_registration_summary,
+};
 use crate::application::repository::pjsip_realtime::{
-    create_udp_pjsip_account, create_ws_pjsip_account,
-    delete_pjsip_account, get_pjsip_accounts,
+    create_pjsip_account, create_pjsip_accounts_bulk, delete_pjsip_account,
+    export_pjsip_account_conf, export_pjsip_accounts, get_pjsip_account, get_pjsip_accounts,
+    import_pjsip_accounts, import_pjsip_conf, reveal_pjsip_account_credentials,
+    update_pjsip_account,
+};
+use crate::infrastructure::models::authentication::{
+    authorization_policy::Permission, principal::Principal,
 };
+use crate::infrastructure::models::errors::api_error::{ApiError, ErrorCode};
 use crate::infrastructure::models::pjsip_realtime::{
     account::{
-        PjsipRealtimeAccount, PjsipRealtimeAccountWithExternalId, PjsipRealtimeAccountWithId,
+        PjsipRealtimeAccount, PjsipRealtimeAccountPatch, PjsipRealtimeAccountWithExternalId,
     },
-    enums::pjsip_endpoint_enums::{RtpTimeout, TransportType},
+    account_bulk::{BulkCreateParams, BulkPjsipRealtimeAccount},
+    account_csv::{ExportParams, ImportParams},
+    account_list::AccountListParams,
+    pjsip_conf::{PJSIP_CONF_CONTENT_TYPE, PjsipConfImportParams},
+    registration_status::RegistrationSummaryParams,
 };
+use crate::restapi::extractors::{ApiJson, ApiQuery};
 
-pub async fn get_pjsip_accounts_handler(state: State<AppState>) -> impl IntoResponse {
-    match get_pjsip_accounts(state).await {
-        Ok(accounts) => (StatusCode::OK, Json(accounts)),
-        Err(e) => {
-            eprintln!("Database error: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(Vec::<PjsipRealtimeAccountWithId>::new()),
-            )
-        }
-    }
+pub async fn get_pjsip_accounts_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    ApiQuery(params): ApiQuery<AccountListParams>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::ListAccounts)?;
+    let page = get_pjsip_accounts(state, &params).await?;
+    Ok((StatusCode::OK, Json(page)))
 }
 
-pub async fn create_pjsip_account_handler(
+// 全アカウントの登録状況の集計 (status を指定するとそのアカウントも返します)
+pub async fn get_pjsip_registration_summary_handler(
     state: State<AppState>,
-    Json(payload): Json<PjsipRealtimeAccount>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let account = payload;
-    let account_id: Option<String> = None;
-    // payload.transportがTransportTypeのいずれかの値であることを確認し、個別の処理に振り分け（スタブ）
-    match create_pjsip_account(state.clone(), account_id, &account).await {
-        Ok((status, json_response)) => {
-            // ここでjson_responseがserde_json::Value型であることを想定
-            // responseは (StatusCode, Json<Value>) 型を想定
-            let id = json_response
-                .get("id")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default();
-            println!("Created account {} with ID: {}", account.username, id);
-
-            // PjsipRealtimeAccountWithId型の変数を作成してidと引数データをまとめる
-            // Apply default values to match database layer behavior
-            let rtp_timeout = Some(account.rtp_timeout.unwrap_or(RtpTimeout::Thirty));
-            let rtp_timeout_hold = Some(account.rtp_timeout_hold.unwrap_or(RtpTimeout::ThreeHundred));
-            
-            let now = chrono::Utc::now();
-            let response_account = PjsipRealtimeAccountWithId {
-                id: String::from(id),
-                username: account.username,
-                password: account.password,
-                context: account.context,
-                transport: account.transport,
-                from_domain: account.from_domain,
-                from_user: account.from_user,
-                rtp_timeout,
-                rtp_timeout_hold,
-                created_at: now,
-                updated_at: now,
-            };
-            Ok((status, Json(response_account)))
-        }
-        Err((status, json_response)) => {
-            eprintln!("Failed to create account: {:?}", json_response);
-            match status {
-                // duplicate key error
-                StatusCode::CONFLICT => Err((
-                    StatusCode::CONFLICT,
-                    Json(serde_json::json!(
-                        "Account with this ID or username already exists"
-                    )),
-                )),
-                _ => Err((
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(serde_json::json!("Failed to create account")),
-                )),
-            }
-        }
-    }
+    principal: Option<Extension<Principal>>,
+    ApiQuery(params): ApiQuery<RegistrationSummaryParams>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::ListAccounts)?;
+    let summary = get_pjsip_registration_summary(state, &params).await?;
+    Ok((StatusCode::OK, Json(summary)))
 }
 
-pub async fn create_pjsip_account_with_external_id_handler(
+pub async fn get_pjsip_account_handler(
     state: State<AppState>,
-    Json(payload): Json<PjsipRealtimeAccountWithExternalId>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    // Validate the pjsip realtime account ID (should be a valid ULID or UUID format)
-    if payload.id.is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"error": "ID cannot be empty"})),
-        ));
-    }
+    principal: Option<Extension<Principal>>,
+    Path(account_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::GetAccount)?;
+    let account = get_pjsip_account(state, account_id).await?;
+    Ok((StatusCode::OK, Json(account)))
+}
+
+// 登録中の端末 (ps_contacts) を返します
+pub async fn get_pjsip_account_contacts_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path(account_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::GetAccount)?;
+    let contacts = get_pjsip_account_contacts(state, account_id).await?;
+    Ok((StatusCode::OK, Json(contacts)))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ExportConfParams {
+    // パスワードの出力は credentials の参照と同じ権限と reveal トークンが必要
+    #[serde(default)]
+    pub include_secrets: bool,
+}
 
-    // Validate username
-    if payload.username.is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"error": "Username cannot be empty"})),
-        ));
+// endpoint / auth / aor を静的な pjsip.conf の形式で返します
+// パスワードは include_secrets=true の場合のみ含みます。
+pub async fn export_pjsip_account_conf_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path(account_id): Path<String>,
+    ApiQuery(params): ApiQuery<ExportConfParams>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::GetAccount)?;
+    if params.include_secrets {
+        authorize(&state, principal.as_deref(), Permission::RevealCredentials)?;
     }
+    let requested_by: Option<String> = principal.as_deref().map(Principal::subject);
+    let body: String = export_pjsip_account_conf(
+        state,
+        account_id,
+        params.include_secrets,
+        reveal_token(&headers),
+        requested_by.as_deref(),
+    )
+    .await?;
+    Ok((
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, PJSIP_CONF_CONTENT_TYPE)],
+        body,
+    ))
+}
+
+// 認証情報の参照に必要なトークンを受け取るヘッダー
+pub const CREDENTIALS_REVEAL_TOKEN_HEADER: &str = "x-reveal-token";
+
+pub async fn get_pjsip_account_credentials_handler(
+    state: State<AppState>,
+    Path(account_id): Path<String>,
+    principal: Option<Extension<Principal>>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::RevealCredentials)?;
+    let reveal_token: Option<&str> = reveal_token(&headers);
+    // 認証が無効な場合は呼び出し元がありません
+    let requested_by: Option<String> = principal.as_deref().map(Principal::subject);
+    let credentials =
+        reveal_pjsip_account_credentials(state, account_id, reveal_token, requested_by.as_deref())
+            .await?;
+    Ok((StatusCode::OK, Json(credentials)))
+}
+
+fn reveal_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(CREDENTIALS_REVEAL_TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok())
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateAccountParams {
+    // transport の変更は端末の接続方法が変わるため、明示的な指定が必要
+    #[serde(default)]
+    pub allow_transport_change: bool,
+}
+
+pub async fn replace_pjsip_account_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path(account_id): Path<String>,
+    ApiQuery(params): ApiQuery<UpdateAccountParams>,
+    ApiJson(payload): ApiJson<PjsipRealtimeAccount>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::UpdateAccount)?;
+    let patch: PjsipRealtimeAccountPatch = payload.into();
+    let account =
+        update_pjsip_account(state, account_id, &patch, params.allow_transport_change).await?;
+    Ok((StatusCode::OK, Json(account)))
+}
+
+pub async fn patch_pjsip_account_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path(account_id): Path<String>,
+    ApiQuery(params): ApiQuery<UpdateAccountParams>,
+    ApiJson(payload): ApiJson<PjsipRealtimeAccountPatch>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::UpdateAccount)?;
+    let account =
+        update_pjsip_account(state, account_id, &payload, params.allow_transport_change).await?;
+    Ok((StatusCode::OK, Json(account)))
+}
+
+pub async fn create_pjsip_account_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    ApiJson(payload): ApiJson<PjsipRealtimeAccount>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::CreateAccount)?;
+    let account_id: String = create_pjsip_account(state.clone(), None, &payload).await?;
+    tracing::info!(
+        "Created account {} with ID: {}",
+        payload.username,
+        account_id
+    );
+    // 登録した内容 (既定値を含む) をデータベースから読み直して返します (パスワードは返却しない)
+    let account = get_pjsip_account(state, account_id).await?;
+    Ok((StatusCode::CREATED, Json(account)))
+}
 
+pub async fn create_pjsip_account_with_external_id_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    ApiJson(payload): ApiJson<PjsipRealtimeAccountWithExternalId>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::CreateAccount)?;
+    // ID (ULID / UUID) を含む項目の検証は作成処理で行います (422)
     let new_account_id: Option<String> = payload.id.clone().into();
-    
-    // Debug: Print received RTP timeout values
-    println!("Received rtp_timeout: {:?}", payload.rtp_timeout);
-    println!("Received rtp_timeout_hold: {:?}", payload.rtp_timeout_hold);
-    
+
     let account: PjsipRealtimeAccount = PjsipRealtimeAccount {
         username: payload.username,
         password: payload.password,
@@ -119,112 +205,139 @@ pub async fn create_pjsip_account_with_external_id_handler(
         from_user: payload.from_user,
         rtp_timeout: payload.rtp_timeout,
         rtp_timeout_hold: payload.rtp_timeout_hold,
+        auth_type: payload.auth_type,
+        realm: payload.realm,
+        tls: payload.tls,
+        dtls: payload.dtls,
     };
-    match create_pjsip_account(state.clone(), new_account_id, &account).await {
-        Ok((status, json_response)) => {
-            // ここでjson_responseがserde_json::Value型であることを想定
-            // responseは (StatusCode, Json<Value>) 型を想定
-            let id = json_response
-                .get("id")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default();
-            println!("Created account {} with ID: {}", account.username, id);
-
-            // PjsipRealtimeAccountWithId型の変数を作成してidと引数データをまとめる
-            // Apply default values to match database layer behavior
-            let rtp_timeout = Some(account.rtp_timeout.unwrap_or(RtpTimeout::Thirty));
-            let rtp_timeout_hold = Some(account.rtp_timeout_hold.unwrap_or(RtpTimeout::ThreeHundred));
-            
-            let now = chrono::Utc::now();
-            let response_account = PjsipRealtimeAccountWithId {
-                id: String::from(id),
-                username: account.username,
-                password: account.password,
-                context: account.context,
-                transport: account.transport,
-                from_domain: account.from_domain,
-                from_user: account.from_user,
-                rtp_timeout,
-                rtp_timeout_hold,
-                created_at: now,
-                updated_at: now,
-            };
-            Ok((status, Json(response_account)))
-        }
-        Err((status, json_response)) => {
-            eprintln!("Failed to create account: {:?}", json_response);
-            match status {
-                // duplicate key error
-                StatusCode::CONFLICT => Err((
-                    StatusCode::CONFLICT,
-                    Json(serde_json::json!(
-                        "Account with this ID or username already exists"
-                    )),
-                )),
-                _ => Err((
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(serde_json::json!("Failed to create account")),
-                )),
-            }
-        }
-    }
+    let account_id: String = create_pjsip_account(state.clone(), new_account_id, &account).await?;
+    tracing::info!(
+        "Created account {} with ID: {}",
+        account.username,
+        account_id
+    );
+    let account = get_pjsip_account(state, account_id).await?;
+    Ok((StatusCode::CREATED, Json(account)))
 }
 
-pub async fn delete_pjsip_account_handler(
+// 一括登録 (?mode=atomic | best_effort)
+pub async fn create_pjsip_accounts_bulk_handler(
     state: State<AppState>,
-    Path(account_id): axum::extract::Path<String>,
-) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
-    // TODO validate account_id
-
-    if let Err(e) = delete_pjsip_account(state.clone(), account_id).await {
-        eprintln!("Failed to delete account: {:?}", e);
-        return Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!("Failed to delete account")),
-        ));
+    principal: Option<Extension<Principal>>,
+    ApiQuery(params): ApiQuery<BulkCreateParams>,
+    ApiJson(payload): ApiJson<Vec<BulkPjsipRealtimeAccount>>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::CreateAccount)?;
+    let (status, response) = create_pjsip_accounts_bulk(state, payload, params.mode).await?;
+    Ok((status, Json(response)))
+}
+
+pub async fn export_pjsip_accounts_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    ApiQuery(params): ApiQuery<ExportParams>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::ListAccounts)?;
+    if params.include_secrets {
+        authorize(&state, principal.as_deref(), Permission::RevealCredentials)?;
     }
-    Ok(StatusCode::NO_CONTENT)
+    let requested_by: Option<String> = principal.as_deref().map(Principal::subject);
+    let body: Vec<u8> = export_pjsip_accounts(
+        state,
+        params.format,
+        params.id_list(),
+        params.include_secrets,
+        reveal_token(&headers),
+        requested_by.as_deref(),
+    )
+    .await?;
+    let content_type: &str = params.format.content_type();
+    let disposition: String = format!("attachment; filename=\"{}\"", params.format.file_name());
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    ))
 }
 
-// private functions for handling pjsip accounts
-async fn create_pjsip_account(
+// インポートするファイル (CSV / pjsip.conf) は multipart/form-data の file フィールドで受け取ります
+pub const IMPORT_FILE_FIELD_NAME: &str = "file";
+
+pub async fn import_pjsip_accounts_handler(
     state: State<AppState>,
-    account_id: Option<String>,
-    account: &PjsipRealtimeAccount,
-) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
-    // payload.transportがTransportTypeのいずれかの値であることを確認し、個別の処理に振り分け（スタブ）
-    match account.transport {
-        TransportType::Udp => {
-            // UDP用の処理（スタブ）
-            create_udp_pjsip_account(state.clone(), account_id, account).await
-        }
-        TransportType::Tcp => {
-            // TCP用の処理（スタブ）
-            println!("Creating TCP account: {:?}", account.username);
-            Err((
-                StatusCode::NOT_IMPLEMENTED,
-                Json(serde_json::json!("TCP transport not implemented")),
-            ))
-        }
-        TransportType::Tls => {
-            // TLS用の処理（スタブ）
-            println!("Creating TLS account: {:?}", account.username);
-            Err((
-                StatusCode::NOT_IMPLEMENTED,
-                Json(serde_json::json!("TLS transport not implemented")),
-            ))
-        }
-        TransportType::Ws => {
-            // WebSocket用の処理
-            create_ws_pjsip_account(state.clone(), account_id, account).await
+    principal: Option<Extension<Principal>>,
+    ApiQuery(params): ApiQuery<ImportParams>,
+    multipart: Result<Multipart, MultipartRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::CreateAccount)?;
+    let data: Vec<u8> = read_multipart_file(multipart).await?;
+    let (status, report) = import_pjsip_accounts(state, &data, params.dry_run).await?;
+    Ok((status, Json(report)))
+}
+
+pub async fn import_pjsip_conf_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    ApiQuery(params): ApiQuery<PjsipConfImportParams>,
+    multipart: Result<Multipart, MultipartRejection>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::CreateAccount)?;
+    let data: Vec<u8> = read_multipart_file(multipart).await?;
+    let text: String = String::from_utf8(data).map_err(|_| {
+        ApiError::new(
+            ErrorCode::InvalidRequestBody,
+            "pjsip.conf must be UTF-8 text",
+        )
+    })?;
+    let (status, report) = import_pjsip_conf(state, &text, &params).await?;
+    Ok((status, Json(report)))
+}
+
+// multipart/form-data の file フィールドの内容を読み込みます
+async fn read_multipart_file(
+    multipart: Result<Multipart, MultipartRejection>,
+) -> Result<Vec<u8>, ApiError> {
+    let invalid_body = |detail: String| ApiError::new(ErrorCode::InvalidRequestBody, detail);
+    let mut multipart: Multipart = multipart.map_err(|e| invalid_body(e.body_text()))?;
+    let mut data: Option<Vec<u8>> = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| invalid_body(e.body_text()))?
+    {
+        if field.name() == Some(IMPORT_FILE_FIELD_NAME) {
+            let bytes = field
+                .bytes()
+                .await
+                .map_err(|e| invalid_body(e.body_text()))?;
+            data = Some(bytes.to_vec());
         }
-        TransportType::Wss => {
-            // Secure WebSocket用の処理（スタブ）
-            println!("Creating WSS account: {:?}", account.username);
-            Err((
-                StatusCode::NOT_IMPLEMENTED,
-                Json(serde_json::json!("WSS transport not implemented")),
-            ))
-        } //_ => {} // もし他のバリアントが存在する場合はここで対応
     }
+    data.ok_or_else(|| {
+        invalid_body(format!(
+            "Missing multipart field: {}",
+            IMPORT_FILE_FIELD_NAME
+        ))
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DeleteAccountParams {
+    // 定期的なクリーンアップ処理向け: 既に削除済みでも 204 を返す
+    #[serde(default)]
+    pub if_exists: bool,
+}
+
+pub async fn delete_pjsip_account_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path(account_id): axum::extract::Path<String>,
+    ApiQuery(params): ApiQuery<DeleteAccountParams>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::DeleteAccount)?;
+    delete_pjsip_account(state, account_id, params.if_exists).await
 }
@@ -0,0 +1,64 @@
+use axum::Extension;
+use axum::extract::Path;
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+
+use crate::AppState;
+use crate::application::authorization::authorize;
+use crate::application::repository::pjsip_trunks::{
+    create_pjsip_trunk, delete_pjsip_trunk, get_pjsip_trunk, list_pjsip_trunks, replace_pjsip_trunk,
+};
+use crate::infrastructure::models::authentication::{
+    authorization_policy::Permission, principal::Principal,
+};
+use crate::infrastructure::models::errors::api_error::ApiError;
+use crate::infrastructure::models::pjsip_realtime::sip_trunk::PjsipRealtimeTrunk;
+use crate::restapi::extractors::ApiJson;
+
+pub async fn get_pjsip_trunks_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::ReadTrunks)?;
+    let trunks = list_pjsip_trunks(state).await?;
+    Ok((StatusCode::OK, Json(trunks)))
+}
+
+pub async fn get_pjsip_trunk_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path(trunk_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::ReadTrunks)?;
+    let trunk = get_pjsip_trunk(state, trunk_id).await?;
+    Ok((StatusCode::OK, Json(trunk)))
+}
+
+pub async fn create_pjsip_trunk_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    ApiJson(payload): ApiJson<PjsipRealtimeTrunk>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::ManageTrunks)?;
+    let trunk = create_pjsip_trunk(state, payload).await?;
+    Ok((StatusCode::CREATED, Json(trunk)))
+}
+
+pub async fn replace_pjsip_trunk_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path(trunk_id): Path<String>,
+    ApiJson(payload): ApiJson<PjsipRealtimeTrunk>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::ManageTrunks)?;
+    let trunk = replace_pjsip_trunk(state, trunk_id, payload).await?;
+    Ok((StatusCode::OK, Json(trunk)))
+}
+
+pub async fn delete_pjsip_trunk_handler(
+    state: State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path(trunk_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    authorize(&state, principal.as_deref(), Permission::ManageTrunks)?;
+    delete_pjsip_trunk(state, trunk_id).await
+}
@@ -0,0 +1,62 @@
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::AppState;
+use crate::application::repository::api_keys::authenticate_api_key;
+use crate::infrastructure::models::authentication::principal::Principal;
+use crate::infrastructure::models::errors::{
+    api_error::ApiError, authentication_error::AuthenticationError,
+};
+
+// API キーを受け取るヘッダー
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+// Authorization: Bearer <JWT> または X-API-Key を検証し、呼び出し元を extensions に格納します。
+// ハンドラは Extension<Principal> で呼び出し元を、Bearer の場合は Extension<JwtClaims> でクレームも受け取れます。
+pub async fn require_authentication(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if state.authenticator.is_disabled() {
+        return next.run(request).await;
+    }
+
+    // 両方指定された場合は Bearer トークンを優先します
+    let result = if let Some(token) = bearer_token(request.headers()) {
+        state.authenticator.verify(token).await.map(|claims| {
+            request.extensions_mut().insert(claims.clone());
+            Principal::User(claims)
+        })
+    } else if let Some(api_key) = api_key(request.headers()) {
+        authenticate_api_key(&state, api_key)
+            .await
+            .map(Principal::ApiKey)
+    } else {
+        Err(AuthenticationError::MissingToken)
+    };
+    match result {
+        Ok(principal) => {
+            request.extensions_mut().insert(principal);
+            next.run(request).await
+        }
+        Err(e) => {
+            tracing::debug!("Rejected credentials: {}", e);
+            ApiError::from(e).into_response()
+        }
+    }
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    let value: &str = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    let token: &str = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+}
+
+fn api_key(headers: &HeaderMap) -> Option<&str> {
+    let value: &str = headers.get(API_KEY_HEADER)?.to_str().ok()?.trim();
+    (!value.is_empty()).then_some(value)
+}
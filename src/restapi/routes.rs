@@ -1,3 +1,4 @@
+pub mod api_key_router;
 pub mod heart_beat_router;
 pub mod pjsip_realtime_router;
 pub mod root;
@@ -0,0 +1,17 @@
+use axum::{
+    Router,
+    routing::{delete, get},
+};
+
+use crate::AppState;
+use crate::restapi::handlers::api_key_handler::{
+    issue_api_key_handler, list_api_keys_handler, revoke_api_key_handler,
+};
+
+pub fn api_key_router(state: AppState) -> Router {
+    // base path is /api/v1/api_keys/
+    Router::new()
+        .route("/", get(list_api_keys_handler).post(issue_api_key_handler))
+        .route("/{key_id}", delete(revoke_api_key_handler))
+        .with_state(state)
+}
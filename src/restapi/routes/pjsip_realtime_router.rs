@@ -4,9 +4,22 @@ use axum::{
 };
 // use axum::extract::State;
 use crate::AppState;
+use crate::restapi::handlers::pjsip_identify_handler::{
+    create_pjsip_endpoint_identify_handler, delete_pjsip_endpoint_identify_handler,
+    get_pjsip_endpoint_identifies_handler,
+};
 use crate::restapi::handlers::pjsip_realtime_handler::{
     create_pjsip_account_handler, create_pjsip_account_with_external_id_handler,
-    delete_pjsip_account_handler, get_pjsip_accounts_handler,
+    create_pjsip_accounts_bulk_handler, delete_pjsip_account_handler,
+    export_pjsip_account_conf_handler, export_pjsip_accounts_handler,
+    get_pjsip_account_contacts_handler, get_pjsip_account_credentials_handler,
+    get_pjsip_account_handler, get_pjsip_accounts_handler, get_pjsip_registration_summary_handler,
+    import_pjsip_accounts_handler, import_pjsip_conf_handler, patch_pjsip_account_handler,
+    replace_pjsip_account_handler,
+};
+use crate::restapi::handlers::pjsip_trunk_handler::{
+    create_pjsip_trunk_handler, delete_pjsip_trunk_handler, get_pjsip_trunk_handler,
+    get_pjsip_trunks_handler, replace_pjsip_trunk_handler,
 };
 
 pub fn pjsip_realtime_router(state: AppState) -> Router {
@@ -14,13 +27,57 @@ pub fn pjsip_realtime_router(state: AppState) -> Router {
     Router::new()
         .route("/accounts", get(get_pjsip_accounts_handler))
         .route("/accounts", post(create_pjsip_account_handler))
+        .route("/accounts/bulk", post(create_pjsip_accounts_bulk_handler))
+        .route("/accounts/export", get(export_pjsip_accounts_handler))
+        .route("/accounts/import", post(import_pjsip_accounts_handler))
+        .route(
+            "/accounts/import/pjsip_conf",
+            post(import_pjsip_conf_handler),
+        )
+        .route(
+            "/accounts/registrations",
+            get(get_pjsip_registration_summary_handler),
+        )
         .route(
             "/accounts_with_id",
             post(create_pjsip_account_with_external_id_handler),
         )
         .route(
             "/accounts/{account_id}",
-            delete(delete_pjsip_account_handler),
+            get(get_pjsip_account_handler)
+                .put(replace_pjsip_account_handler)
+                .patch(patch_pjsip_account_handler)
+                .delete(delete_pjsip_account_handler),
+        )
+        .route(
+            "/accounts/{account_id}/contacts",
+            get(get_pjsip_account_contacts_handler),
+        )
+        .route(
+            "/accounts/{account_id}/credentials",
+            get(get_pjsip_account_credentials_handler),
+        )
+        .route(
+            "/accounts/{account_id}/pjsip.conf",
+            get(export_pjsip_account_conf_handler),
+        )
+        .route(
+            "/trunks",
+            get(get_pjsip_trunks_handler).post(create_pjsip_trunk_handler),
+        )
+        .route(
+            "/trunks/{trunk_id}",
+            get(get_pjsip_trunk_handler)
+                .put(replace_pjsip_trunk_handler)
+                .delete(delete_pjsip_trunk_handler),
+        )
+        .route(
+            "/endpoints/{endpoint_id}/identifies",
+            get(get_pjsip_endpoint_identifies_handler).post(create_pjsip_endpoint_identify_handler),
+        )
+        .route(
+            "/endpoints/{endpoint_id}/identifies/{identify_id}",
+            delete(delete_pjsip_endpoint_identify_handler),
         )
         .with_state(state)
 }
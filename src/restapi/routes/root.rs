@@ -1,12 +1,21 @@
-use axum::{Router, routing::get};
+use axum::{Router, middleware, routing::get};
 
 use crate::AppState;
 use crate::restapi::handlers::heart_beat_handler::heart_beat;
+use crate::restapi::middleware::auth_middleware::require_authentication;
+use crate::restapi::routes::api_key_router::api_key_router;
 use crate::restapi::routes::pjsip_realtime_router::pjsip_realtime_router;
 
 pub fn create_router(state: AppState) -> Router {
-    Router::new().route("/", get(heart_beat)).nest(
-        "/api/v1/pjsip_realtime",
-        pjsip_realtime_router(state.clone()),
-    )
+    // ヘルスチェック (/) 以外は Bearer トークンまたは API キーが必要です
+    let api_v1: Router = Router::new()
+        .nest("/pjsip_realtime", pjsip_realtime_router(state.clone()))
+        .nest("/api_keys", api_key_router(state.clone()))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_authentication,
+        ));
+    Router::new()
+        .route("/", get(heart_beat))
+        .nest("/api/v1", api_v1)
 }
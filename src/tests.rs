@@ -1,2 +1,2 @@
 pub mod restapi;
-pub mod serialization_test;
\ No newline at end of file
+pub mod serialization_test;
@@ -0,0 +1,3 @@
+// unit tests
+pub mod api_key_authentication;
+pub mod manage_api_keys;
@@ -0,0 +1,216 @@
+use crate::config::AppConfig;
+use crate::tests::restapi::api::v1::api_keys::manage_api_keys::reset_api_keys;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::reset_pjsip_realtime_database;
+use crate::tests::restapi::api::v1::pjsip_realtime::auth_helper::{
+    test_oidc_config, token_with_roles,
+};
+use crate::{AppState, create_pjsip_pool};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use dotenvy::from_filename;
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use sqlx::{Error, PgPool, Pool, Postgres};
+use tower::ServiceExt;
+
+const ACCOUNTS_URI: &str = "/api/v1/pjsip_realtime/accounts";
+
+async fn setup_test_state(oidc_configured: bool) -> AppState {
+    // Config file
+    from_filename(".env.test").ok();
+
+    let create_pjsip_pool_result: Result<Pool<Postgres>, Error> = create_pjsip_pool().await;
+    let pjsip_db: PgPool = match create_pjsip_pool_result {
+        Ok(pool) => pool,
+        Err(e) => {
+            tracing::error!("Failed to create PJSIP database connection pool: {}", e);
+            panic!("Failed to create PJSIP database connection pool");
+        }
+    };
+    AppState::with_config(
+        pjsip_db,
+        AppConfig {
+            oidc: oidc_configured.then(test_oidc_config),
+            ..AppConfig::default()
+        },
+    )
+}
+
+// 管理者のトークンで API キーを発行します
+async fn issue_key(app: &Router, scopes: &[&str]) -> Value {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/api_keys")
+        .header(
+            header::AUTHORIZATION,
+            format!("Bearer {}", token_with_roles(&["pjsip:admin"])),
+        )
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            json!({ "name": "dialer-bot", "scopes": scopes }).to_string(),
+        ))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
+
+async fn send_with_key(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    api_key: &str,
+    payload: Option<Value>,
+) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("X-API-Key", api_key)
+        .header("Content-Type", "application/json")
+        .body(payload.map_or_else(Body::empty, |p| Body::from(p.to_string())))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[serial]
+#[tokio::test]
+async fn test_api_key_authentication() {
+    /*
+    curl -H "X-API-Key: ${API_KEY}" http://localhost:3000/api/v1/pjsip_realtime/accounts
+    */
+    let state: AppState = setup_test_state(true).await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_api_keys(&state.pjsip_db).await;
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let issued = issue_key(&app, &["pjsip:write"]).await;
+    let api_key = issued["api_key"].as_str().unwrap();
+
+    let (status, _) = send_with_key(&app, "GET", ACCOUNTS_URI, api_key, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send_with_key(
+        &app,
+        "POST",
+        ACCOUNTS_URI,
+        api_key,
+        Some(json!({
+            "username": "api_key_user",
+            "password": "test_password",
+            "transport": "udp",
+            "context": "from-sipproxy",
+            "from_domain": "default_domain",
+            "from_user": "api_key_user",
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // scopes are enforced like roles
+    let (status, response_json) =
+        send_with_key(&app, "GET", "/api/v1/api_keys", api_key, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(response_json["permission"], "manage_api_keys");
+
+    let last_used_at: Option<chrono::DateTime<chrono::Utc>> =
+        sqlx::query_scalar("SELECT last_used_at FROM api_keys WHERE id = $1")
+            .bind(issued["id"].as_str().unwrap())
+            .fetch_one(&state.pjsip_db)
+            .await
+            .unwrap();
+    assert!(last_used_at.is_some());
+
+    // unknown key
+    let (status, response_json) =
+        send_with_key(&app, "GET", ACCOUNTS_URI, "pjk_unknown", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(response_json["code"], "invalid_api_key");
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+    reset_api_keys(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_api_key_revoked_or_expired() {
+    let state: AppState = setup_test_state(true).await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_api_keys(&state.pjsip_db).await;
+
+    let revoked = issue_key(&app, &["pjsip:read"]).await;
+    let request = Request::builder()
+        .method("DELETE")
+        .uri(format!(
+            "/api/v1/api_keys/{}",
+            revoked["id"].as_str().unwrap()
+        ))
+        .header(
+            header::AUTHORIZATION,
+            format!("Bearer {}", token_with_roles(&["pjsip:admin"])),
+        )
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let (status, _) = send_with_key(
+        &app,
+        "GET",
+        ACCOUNTS_URI,
+        revoked["api_key"].as_str().unwrap(),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let expired = issue_key(&app, &["pjsip:read"]).await;
+    sqlx::query(
+        "UPDATE api_keys SET expires_at = CURRENT_TIMESTAMP - INTERVAL '1 minute' WHERE id = $1",
+    )
+    .bind(expired["id"].as_str().unwrap())
+    .execute(&state.pjsip_db)
+    .await
+    .unwrap();
+    let (status, _) = send_with_key(
+        &app,
+        "GET",
+        ACCOUNTS_URI,
+        expired["api_key"].as_str().unwrap(),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    reset_api_keys(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_api_key_without_oidc_config() {
+    // API keys work even when OIDC is not configured
+    let state: AppState = setup_test_state(true).await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_api_keys(&state.pjsip_db).await;
+    let issued = issue_key(&app, &["pjsip:read"]).await;
+
+    let key_only_state: AppState = setup_test_state(false).await;
+    let key_only_app: Router = crate::restapi::routes::root::create_router(key_only_state);
+    let (status, _) = send_with_key(
+        &key_only_app,
+        "GET",
+        ACCOUNTS_URI,
+        issued["api_key"].as_str().unwrap(),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    reset_api_keys(&state.pjsip_db).await;
+}
@@ -0,0 +1,177 @@
+use crate::config::AppConfig;
+use crate::tests::restapi::api::v1::pjsip_realtime::auth_helper::{
+    test_oidc_config, token_with_roles,
+};
+use crate::{AppState, create_pjsip_pool};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use dotenvy::from_filename;
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use sqlx::{Error, PgPool, Pool, Postgres};
+use tower::ServiceExt;
+
+const API_KEYS_URI: &str = "/api/v1/api_keys";
+
+async fn setup_test_state() -> AppState {
+    // Config file
+    from_filename(".env.test").ok();
+
+    let create_pjsip_pool_result: Result<Pool<Postgres>, Error> = create_pjsip_pool().await;
+    let pjsip_db: PgPool = match create_pjsip_pool_result {
+        Ok(pool) => pool,
+        Err(e) => {
+            tracing::error!("Failed to create PJSIP database connection pool: {}", e);
+            panic!("Failed to create PJSIP database connection pool");
+        }
+    };
+    AppState::with_config(
+        pjsip_db,
+        AppConfig {
+            oidc: Some(test_oidc_config()),
+            ..AppConfig::default()
+        },
+    )
+}
+
+pub(crate) async fn reset_api_keys(pool: &PgPool) {
+    sqlx::query("DELETE FROM api_keys")
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: &str,
+    payload: Option<Value>,
+) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .header("Content-Type", "application/json")
+        .body(payload.map_or_else(Body::empty, |p| Body::from(p.to_string())))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[serial]
+#[tokio::test]
+async fn test_issue_list_and_revoke_api_key() {
+    /*
+    curl -X POST -H "Authorization: Bearer ${ACCESS_TOKEN}" -H "Content-Type: application/json" \
+      -d '{"name": "dialer-bot", "scopes": ["pjsip:read"]}' \
+      http://localhost:3000/api/v1/api_keys
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_api_keys(&state.pjsip_db).await;
+
+    let admin_token = token_with_roles(&["pjsip:admin"]);
+    let (status, issued) = send(
+        &app,
+        "POST",
+        API_KEYS_URI,
+        &admin_token,
+        Some(json!({
+            "name": "dialer-bot",
+            "scopes": ["pjsip:write", "pjsip:read", "pjsip:read"],
+            "expires_at": "2999-01-01T00:00:00Z",
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let api_key = issued["api_key"].as_str().unwrap();
+    assert!(api_key.starts_with("pjk_"));
+    assert!(api_key.starts_with(issued["key_prefix"].as_str().unwrap()));
+    assert_eq!(issued["name"], "dialer-bot");
+    assert_eq!(issued["scopes"], json!(["pjsip:read", "pjsip:write"]));
+    assert_eq!(issued["created_by"], "operator");
+    assert!(issued["revoked_at"].is_null());
+
+    // only the hash is stored
+    let key_hash: String = sqlx::query_scalar("SELECT key_hash FROM api_keys WHERE id = $1")
+        .bind(issued["id"].as_str().unwrap())
+        .fetch_one(&state.pjsip_db)
+        .await
+        .unwrap();
+    assert_ne!(key_hash, api_key);
+    assert_eq!(key_hash.len(), 64);
+
+    // listing never returns the key or its hash
+    let (status, keys) = send(&app, "GET", API_KEYS_URI, &admin_token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(keys.as_array().unwrap().len(), 1);
+    assert_eq!(keys[0]["id"], issued["id"]);
+    assert!(keys[0].get("api_key").is_none());
+    assert!(keys[0].get("key_hash").is_none());
+
+    let key_uri = format!("{}/{}", API_KEYS_URI, issued["id"].as_str().unwrap());
+    let (status, _) = send(&app, "DELETE", &key_uri, &admin_token, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, keys) = send(&app, "GET", API_KEYS_URI, &admin_token, None).await;
+    assert!(!keys[0]["revoked_at"].is_null());
+
+    // already revoked
+    let (status, response_json) = send(&app, "DELETE", &key_uri, &admin_token, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(response_json["code"], "not_found");
+
+    reset_api_keys(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_issue_api_key_validation() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_api_keys(&state.pjsip_db).await;
+
+    let admin_token = token_with_roles(&["pjsip:admin"]);
+    let payloads = [
+        json!({ "name": "", "scopes": ["pjsip:read"] }),
+        json!({ "name": "no-scopes", "scopes": [] }),
+        json!({ "name": "blank-scope", "scopes": ["pjsip read"] }),
+        json!({ "name": "expired", "scopes": ["pjsip:read"], "expires_at": "2000-01-01T00:00:00Z" }),
+    ];
+    for payload in payloads {
+        let (status, response_json) = send(
+            &app,
+            "POST",
+            API_KEYS_URI,
+            &admin_token,
+            Some(payload.clone()),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "payload: {}", payload);
+        assert_eq!(response_json["code"], "validation_failed");
+    }
+
+    // managing keys requires pjsip:admin
+    let write_token = token_with_roles(&["pjsip:write"]);
+    let (status, response_json) = send(
+        &app,
+        "POST",
+        API_KEYS_URI,
+        &write_token,
+        Some(json!({ "name": "dialer-bot", "scopes": ["pjsip:admin"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(response_json["permission"], "manage_api_keys");
+    let (status, _) = send(&app, "GET", API_KEYS_URI, &write_token, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    reset_api_keys(&state.pjsip_db).await;
+}
@@ -1,8 +1,31 @@
 // unit test helper functions
 pub(crate) mod account_helper;
+pub(crate) mod auth_helper;
 
 // unit tests
+pub mod account_contacts;
+pub mod account_realm;
+pub mod authentication;
+pub mod authorization;
+pub mod bulk_create_accounts;
 pub mod create_account;
 pub mod create_account_with_external_id;
+pub mod create_md5_account;
+pub mod create_tcp_account;
+pub mod create_tls_account;
+pub mod create_wss_account;
 pub mod delete_account;
+pub mod duplicate_account;
+pub mod error_responses;
+pub mod export_pjsip_conf;
+pub mod get_account;
+pub mod get_account_credentials;
 pub mod get_accounts;
+pub mod get_accounts_page;
+pub mod identifies;
+pub mod import_export_accounts;
+pub mod import_pjsip_conf;
+pub mod registration_summary;
+pub mod trunks;
+pub mod update_account;
+pub mod validate_account;
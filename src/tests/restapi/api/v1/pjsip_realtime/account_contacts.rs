@@ -0,0 +1,105 @@
+use crate::AppState;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    TestAccountOptions, create_test_account, insert_ps_contact, reset_pjsip_realtime_database,
+    setup_test_state,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use tower::ServiceExt;
+
+async fn get_json(app: &Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method("GET")
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[serial]
+#[tokio::test]
+async fn test_get_pjsip_realtime_account_contacts() {
+    /*
+    curl -X GET http://localhost:3000/api/v1/pjsip_realtime/accounts/{account_id}/contacts
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let account_id = create_test_account(&app, "phone_1", TestAccountOptions::default()).await;
+    let other_id = create_test_account(&app, "phone_2", TestAccountOptions::default()).await;
+    let account_uri = format!("/api/v1/pjsip_realtime/accounts/{}", account_id);
+
+    // 登録前
+    let (status, contacts) = get_json(&app, &format!("{}/contacts", account_uri)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(contacts, json!([]));
+    let (_, account) = get_json(&app, &account_uri).await;
+    assert_eq!(account["registered"], false);
+
+    let contact_id = insert_ps_contact(
+        &state.pjsip_db,
+        &account_id,
+        "sip:phone_1@192.168.1.10:5060",
+        "Yealink SIP-T46U",
+        3600,
+    )
+    .await;
+    // 有効期限切れの contact と他のアカウントの contact は含めません
+    insert_ps_contact(
+        &state.pjsip_db,
+        &account_id,
+        "sip:phone_1@192.168.1.11:5060",
+        "Old Phone",
+        -60,
+    )
+    .await;
+    insert_ps_contact(
+        &state.pjsip_db,
+        &other_id,
+        "sip:phone_2@192.168.1.12:5060",
+        "Other Phone",
+        3600,
+    )
+    .await;
+
+    let (status, contacts) = get_json(&app, &format!("{}/contacts", account_uri)).await;
+    assert_eq!(status, StatusCode::OK);
+    let contacts = contacts.as_array().unwrap();
+    assert_eq!(contacts.len(), 1);
+    let contact = &contacts[0];
+    assert_eq!(contact["id"], contact_id.as_str());
+    assert_eq!(contact["uri"], "sip:phone_1@192.168.1.10:5060");
+    assert_eq!(contact["user_agent"], "Yealink SIP-T46U");
+    assert_eq!(contact["via_addr"], "198.51.100.20");
+    assert_eq!(contact["via_port"], 5060);
+    assert_eq!(contact["qualify_frequency"], 60);
+    assert_eq!(contact["qualify_timeout"], 3.0);
+    assert_eq!(contact["reg_server"], "asterisk-1");
+    let expires_in = contact["expires_in"].as_i64().unwrap();
+    assert!((3590..=3600).contains(&expires_in));
+    assert!(contact["expiration_time"].as_str().is_some());
+
+    let (_, account) = get_json(&app, &account_uri).await;
+    assert_eq!(account["registered"], true);
+
+    let (status, response_json) = get_json(
+        &app,
+        "/api/v1/pjsip_realtime/accounts/unknown_account/contacts",
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(response_json["code"], "not_found");
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
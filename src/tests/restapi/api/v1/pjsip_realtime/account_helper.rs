@@ -1,7 +1,59 @@
-use sqlx::PgPool;
+use crate::config::AppConfig;
+use crate::{AppState, create_pjsip_pool};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use dotenvy::from_filename;
+use http_body_util::BodyExt; // for `collect`
+use md5::{Digest, Md5};
+use serde_json::{Value, json};
+use sqlx::{Error, PgPool, Pool, Postgres};
+use tower::ServiceExt;
+
+// test helper function to create the application state
+pub(crate) async fn setup_test_state() -> AppState {
+    AppState::new(create_test_pool().await)
+}
+
+pub(crate) async fn setup_test_state_with_config(config: AppConfig) -> AppState {
+    AppState::with_config(create_test_pool().await, config)
+}
+
+async fn create_test_pool() -> PgPool {
+    // Config file
+    from_filename(".env.test").ok();
+
+    let create_pjsip_pool_result: Result<Pool<Postgres>, Error> = create_pjsip_pool().await;
+    match create_pjsip_pool_result {
+        Ok(pool) => pool,
+        Err(e) => {
+            tracing::error!("Failed to create PJSIP database connection pool: {}", e);
+            panic!("Failed to create PJSIP database connection pool");
+        }
+    }
+}
 
 // test helper function to create a test app
 pub(crate) async fn reset_pjsip_realtime_database(pool: &PgPool) {
+    sqlx::query("DELETE FROM pjsip_realtime_trunks")
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM ps_registrations")
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM ps_endpoint_id_ips")
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM ps_contacts")
+        .execute(pool)
+        .await
+        .unwrap();
     sqlx::query("DELETE FROM pjsip_realtime_accounts")
         .execute(pool)
         .await
@@ -19,3 +71,71 @@ pub(crate) async fn reset_pjsip_realtime_database(pool: &PgPool) {
         .await
         .unwrap();
 }
+
+// Asterisk が REGISTER を受けた時と同じ形式で ps_contacts に登録します
+// expires_in が負の場合は有効期限切れの contact になります。
+pub(crate) async fn insert_ps_contact(
+    pool: &PgPool,
+    aor_id: &str,
+    contact_uri: &str,
+    user_agent: &str,
+    expires_in: i64,
+) -> String {
+    let contact_id = format!("{}^3B@{:x}", aor_id, Md5::digest(contact_uri.as_bytes()));
+    sqlx::query(
+        r#"
+        INSERT INTO ps_contacts (id, uri, expiration_time, qualify_frequency, qualify_timeout,
+                                 user_agent, via_addr, via_port, endpoint, reg_server)
+        VALUES ($1, $2, EXTRACT(EPOCH FROM CURRENT_TIMESTAMP)::bigint + $3, 60, 3.0,
+                $4, '198.51.100.20', 5060, $5, 'asterisk-1')"#,
+    )
+    .bind(&contact_id)
+    .bind(contact_uri)
+    .bind(expires_in)
+    .bind(user_agent)
+    .bind(aor_id)
+    .execute(pool)
+    .await
+    .unwrap();
+    contact_id
+}
+
+// create_test_account の省略可能なパラメータ
+// 指定しない項目は udp / from-sipproxy / test_password を使います。
+#[derive(Default)]
+pub(crate) struct TestAccountOptions<'a> {
+    pub transport: Option<&'a str>,
+    pub context: Option<&'a str>,
+    pub password: Option<&'a str>,
+    pub extra: Option<Value>, // payload に追加するフィールド (rtp_timeout など)
+}
+
+// POST /accounts でアカウントを作成し、作成されたアカウントの id を返します
+pub(crate) async fn create_test_account(
+    app: &Router,
+    username: &str,
+    options: TestAccountOptions<'_>,
+) -> String {
+    let mut payload: Value = json!({
+        "username": username,
+        "password": options.password.unwrap_or("test_password"),
+        "transport": options.transport.unwrap_or("udp"),
+        "context": options.context.unwrap_or("from-sipproxy"),
+        "from_domain": "default_domain",
+        "from_user": username,
+    });
+    if let Some(Value::Object(extra)) = options.extra {
+        payload.as_object_mut().unwrap().extend(extra);
+    }
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/pjsip_realtime/accounts")
+        .header("Content-Type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let created: Value = serde_json::from_slice(&body).unwrap();
+    created["id"].as_str().unwrap().to_string()
+}
@@ -0,0 +1,236 @@
+use crate::AppState;
+use crate::config::AppConfig;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    reset_pjsip_realtime_database, setup_test_state,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use sqlx::{PgPool, Row};
+use tower::ServiceExt;
+
+async fn send_json(app: &Router, method: &str, uri: &str, payload: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+async fn stored_realm_and_md5_cred(
+    pool: &PgPool,
+    account_id: &str,
+) -> (Option<String>, Option<String>) {
+    let row = sqlx::query("SELECT realm, md5_cred FROM ps_auths WHERE id = $1")
+        .bind(account_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    (row.get("realm"), row.get("md5_cred"))
+}
+
+fn account_payload(username: &str) -> Value {
+    json!({
+        "username": username,
+        "password": "secret_password",
+        "transport": "udp",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": username,
+    })
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_pjsip_realtime_account_with_realm() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    // explicit realm is stored for userpass accounts
+    let mut payload = account_payload("realm_userpass_user");
+    payload["realm"] = json!("sip.example.com");
+    let (status, response_json) =
+        send_json(&app, "POST", "/api/v1/pjsip_realtime/accounts", payload).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(response_json["auth_type"], "userpass");
+    assert_eq!(response_json["realm"], "sip.example.com");
+    let account_id = response_json["id"].as_str().unwrap().to_string();
+    let (realm, md5_cred) = stored_realm_and_md5_cred(&state.pjsip_db, &account_id).await;
+    assert_eq!(realm.as_deref(), Some("sip.example.com"));
+    assert_eq!(md5_cred, None);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/api/v1/pjsip_realtime/accounts/{}", account_id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let detail: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(detail["auth"]["realm"], "sip.example.com");
+    assert_eq!(detail["realm"], "sip.example.com");
+
+    // the account list also returns the auth type and realm
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/v1/pjsip_realtime/accounts?username_prefix=realm_userpass")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let page: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(page["items"][0]["auth_type"], "userpass");
+    assert_eq!(page["items"][0]["realm"], "sip.example.com");
+
+    // realm defaults to PJSIP_DEFAULT_REALM
+    let (status, response_json) = send_json(
+        &app,
+        "POST",
+        "/api/v1/pjsip_realtime/accounts",
+        account_payload("realm_default_user"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let account_id = response_json["id"].as_str().unwrap().to_string();
+    let (realm, _) = stored_realm_and_md5_cred(&state.pjsip_db, &account_id).await;
+    assert_eq!(realm.as_deref(), Some("asterisk"));
+
+    // md5 digest is computed with the requested realm
+    let mut payload = account_payload("realm_md5_user");
+    payload["realm"] = json!("sip.example.com");
+    payload["auth_type"] = json!("md5");
+    let (status, response_json) =
+        send_json(&app, "POST", "/api/v1/pjsip_realtime/accounts", payload).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let account_id = response_json["id"].as_str().unwrap().to_string();
+    let (realm, md5_cred) = stored_realm_and_md5_cred(&state.pjsip_db, &account_id).await;
+    assert_eq!(realm.as_deref(), Some("sip.example.com"));
+    // MD5("realm_md5_user:sip.example.com:secret_password")
+    assert_eq!(
+        md5_cred.as_deref(),
+        Some("537d8e9f55d8be1d746d5fe75c9b918b")
+    );
+
+    // empty realm is rejected
+    let mut payload = account_payload("realm_empty_user");
+    payload["realm"] = json!(" ");
+    let (status, response_json) =
+        send_json(&app, "POST", "/api/v1/pjsip_realtime/accounts", payload).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["errors"][0]["field"], "realm");
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_md5_pjsip_realtime_account_requires_realm() {
+    let state: AppState = setup_test_state().await;
+    // no PJSIP_DEFAULT_REALM
+    let no_realm_state = AppState::with_config(
+        state.pjsip_db.clone(),
+        AppConfig {
+            auth_disabled: true,
+            ..AppConfig::default()
+        },
+    );
+    let app: Router = crate::restapi::routes::root::create_router(no_realm_state);
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let mut payload = account_payload("realm_required_user");
+    payload["auth_type"] = json!("md5");
+    let (status, response_json) = send_json(
+        &app,
+        "POST",
+        "/api/v1/pjsip_realtime/accounts",
+        payload.clone(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["code"], "invalid_fields");
+    assert_eq!(response_json["errors"][0]["field"], "realm");
+
+    // userpass accounts do not need a realm
+    let (status, response_json) = send_json(
+        &app,
+        "POST",
+        "/api/v1/pjsip_realtime/accounts",
+        account_payload("realm_optional_user"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let account_id = response_json["id"].as_str().unwrap().to_string();
+    let (realm, _) = stored_realm_and_md5_cred(&state.pjsip_db, &account_id).await;
+    assert_eq!(realm, None);
+
+    payload["realm"] = json!("sip.example.com");
+    let (status, _) = send_json(&app, "POST", "/api/v1/pjsip_realtime/accounts", payload).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_update_pjsip_realtime_account_realm() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let mut payload = account_payload("realm_md5_user");
+    payload["realm"] = json!("sip.example.com");
+    payload["auth_type"] = json!("md5");
+    let (status, response_json) =
+        send_json(&app, "POST", "/api/v1/pjsip_realtime/accounts", payload).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let account_id = response_json["id"].as_str().unwrap().to_string();
+    let uri = format!("/api/v1/pjsip_realtime/accounts/{}", account_id);
+
+    // moving an md5 account to another realm needs the password
+    let (status, response_json) = send_json(
+        &app,
+        "PATCH",
+        &uri,
+        json!({ "realm": "tenant-b.example.com" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["errors"][0]["field"], "password");
+
+    let (status, response_json) = send_json(
+        &app,
+        "PATCH",
+        &uri,
+        json!({ "realm": "tenant-b.example.com", "password": "secret_password" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["auth"]["realm"], "tenant-b.example.com");
+    let (realm, md5_cred) = stored_realm_and_md5_cred(&state.pjsip_db, &account_id).await;
+    assert_eq!(realm.as_deref(), Some("tenant-b.example.com"));
+    // MD5("realm_md5_user:tenant-b.example.com:secret_password")
+    assert_eq!(
+        md5_cred.as_deref(),
+        Some("43359719eb73aa76e76b463d3944222d")
+    );
+
+    // other changes keep the realm
+    let (status, response_json) =
+        send_json(&app, "PATCH", &uri, json!({ "context": "users" })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["auth"]["realm"], "tenant-b.example.com");
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
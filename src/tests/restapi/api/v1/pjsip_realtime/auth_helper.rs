@@ -0,0 +1,69 @@
+use crate::config::{JwksSource, OidcConfig};
+
+use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
+use serde_json::{Value, json};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// テスト用にローカルで生成した鍵 (test_jwks.json は test_rsa_private.pem の公開鍵)
+pub(crate) const TEST_PRIVATE_KEY: &[u8] =
+    include_bytes!("../../../../fixtures/auth/test_rsa_private.pem");
+// JWKS に含まれない鍵
+pub(crate) const OTHER_PRIVATE_KEY: &[u8] =
+    include_bytes!("../../../../fixtures/auth/other_rsa_private.pem");
+pub(crate) const TEST_KID: &str = "test-key-1";
+pub(crate) const TEST_ISSUER: &str = "https://keycloak.test/realms/asterisk";
+pub(crate) const TEST_AUDIENCE: &str = "pjsip-realtime-api";
+
+pub(crate) fn fixture_path(name: &str) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "src/tests/fixtures/auth", name]
+        .iter()
+        .collect()
+}
+
+// test_jwks.json で検証する OIDC 設定
+pub(crate) fn test_oidc_config() -> OidcConfig {
+    OidcConfig {
+        issuer: String::from(TEST_ISSUER),
+        audiences: vec![String::from(TEST_AUDIENCE)],
+        jwks_source: JwksSource::File(fixture_path("test_jwks.json")),
+        leeway_secs: 0,
+    }
+}
+
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+// realm ロールを持つ有効なクレーム
+pub(crate) fn claims_with_roles(roles: &[&str]) -> Value {
+    json!({
+        "sub": "2f1c6a52-4b8e-4a0c-9d55-0c8f3f0d7e11",
+        "iss": TEST_ISSUER,
+        "aud": TEST_AUDIENCE,
+        "exp": now() + 300,
+        "iat": now(),
+        "preferred_username": "operator",
+        "scope": "openid profile",
+        "realm_access": { "roles": roles },
+    })
+}
+
+pub(crate) fn sign(claims: &Value, private_key: &[u8]) -> String {
+    let mut header: Header = Header::new(Algorithm::RS256);
+    header.kid = Some(String::from(TEST_KID));
+    encode(
+        &header,
+        claims,
+        &EncodingKey::from_rsa_pem(private_key).unwrap(),
+    )
+    .unwrap()
+}
+
+// テスト用の鍵で署名したトークン
+pub(crate) fn token_with_roles(roles: &[&str]) -> String {
+    sign(&claims_with_roles(roles), TEST_PRIVATE_KEY)
+}
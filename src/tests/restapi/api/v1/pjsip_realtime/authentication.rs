@@ -0,0 +1,191 @@
+use crate::AppState;
+use crate::config::AppConfig;
+use crate::infrastructure::models::authentication::claims::JwtClaims;
+use crate::restapi::middleware::auth_middleware::require_authentication;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::setup_test_state_with_config;
+use crate::tests::restapi::api::v1::pjsip_realtime::auth_helper::{
+    OTHER_PRIVATE_KEY, TEST_KID, TEST_PRIVATE_KEY, claims_with_roles, now, sign, test_oidc_config,
+};
+
+use axum::{
+    Extension, Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+    middleware,
+    routing::get,
+};
+use http_body_util::BodyExt; // for `collect`
+use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
+use serde_json::{Value, json};
+use serial_test::serial;
+use tower::ServiceExt;
+
+async fn setup_test_state() -> AppState {
+    setup_test_state_with_config(AppConfig {
+        oidc: Some(test_oidc_config()),
+        ..AppConfig::default()
+    })
+    .await
+}
+
+fn valid_claims() -> Value {
+    claims_with_roles(&["pjsip:read"])
+}
+
+async fn list_accounts(app: &Router, token: Option<&str>) -> (StatusCode, Option<String>, Value) {
+    let mut builder = Request::builder()
+        .method("GET")
+        .uri("/api/v1/pjsip_realtime/accounts");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let response = app
+        .clone()
+        .oneshot(builder.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let www_authenticate = response
+        .headers()
+        .get(header::WWW_AUTHENTICATE)
+        .map(|v| v.to_str().unwrap().to_string());
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (
+        status,
+        www_authenticate,
+        serde_json::from_slice(&body).unwrap_or(Value::Null),
+    )
+}
+
+#[serial]
+#[tokio::test]
+async fn test_authentication_accepts_valid_token() {
+    /*
+    curl -H "Authorization: Bearer ${ACCESS_TOKEN}" \
+      http://localhost:3000/api/v1/pjsip_realtime/accounts
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state);
+
+    let token = sign(&valid_claims(), TEST_PRIVATE_KEY);
+    let (status, _, response_json) = list_accounts(&app, Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(response_json["items"].is_array());
+}
+
+#[serial]
+#[tokio::test]
+async fn test_authentication_requires_token() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state);
+
+    let (status, www_authenticate, response_json) = list_accounts(&app, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(www_authenticate.as_deref(), Some("Bearer"));
+    assert_eq!(response_json["code"], "unauthenticated");
+
+    // health check is not protected
+    let request = Request::builder()
+        .method("GET")
+        .uri("/")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[serial]
+#[tokio::test]
+async fn test_authentication_rejects_invalid_tokens() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state);
+
+    let mut expired = valid_claims();
+    expired["exp"] = json!(now() - 60);
+    let mut wrong_audience = valid_claims();
+    wrong_audience["aud"] = json!("another-client");
+    let mut wrong_issuer = valid_claims();
+    wrong_issuer["iss"] = json!("https://keycloak.test/realms/other");
+    let mut missing_exp = valid_claims();
+    missing_exp.as_object_mut().unwrap().remove("exp");
+
+    let tokens = [
+        ("expired", sign(&expired, TEST_PRIVATE_KEY)),
+        ("wrong audience", sign(&wrong_audience, TEST_PRIVATE_KEY)),
+        ("wrong issuer", sign(&wrong_issuer, TEST_PRIVATE_KEY)),
+        ("missing exp", sign(&missing_exp, TEST_PRIVATE_KEY)),
+        ("unknown signer", sign(&valid_claims(), OTHER_PRIVATE_KEY)),
+        (
+            "symmetric algorithm",
+            encode(
+                &Header {
+                    kid: Some(String::from(TEST_KID)),
+                    ..Header::new(Algorithm::HS256)
+                },
+                &valid_claims(),
+                &EncodingKey::from_secret(b"secret"),
+            )
+            .unwrap(),
+        ),
+        ("malformed", String::from("not-a-jwt")),
+    ];
+    for (case, token) in tokens {
+        let (status, www_authenticate, response_json) = list_accounts(&app, Some(&token)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "case: {}", case);
+        assert_eq!(
+            www_authenticate.as_deref(),
+            Some("Bearer error=\"invalid_token\""),
+            "case: {}",
+            case
+        );
+        assert_eq!(response_json["code"], "invalid_token", "case: {}", case);
+    }
+}
+
+#[serial]
+#[tokio::test]
+async fn test_authentication_without_oidc_config_rejects_requests() {
+    let state: AppState = setup_test_state().await;
+    let unconfigured_state = AppState::with_config(state.pjsip_db.clone(), AppConfig::default());
+    let app: Router = crate::restapi::routes::root::create_router(unconfigured_state);
+
+    let token = sign(&valid_claims(), TEST_PRIVATE_KEY);
+    let (status, _, _) = list_accounts(&app, Some(&token)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[serial]
+#[tokio::test]
+async fn test_authentication_exposes_claims_to_handlers() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = Router::new()
+        .route(
+            "/whoami",
+            get(|Extension(claims): Extension<JwtClaims>| async move {
+                axum::Json(json!({
+                    "sub": claims.sub,
+                    "subject": claims.subject(),
+                    "roles": claims.realm_access.map(|access| access.roles),
+                }))
+            }),
+        )
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_authentication,
+        ));
+
+    let token = sign(&valid_claims(), TEST_PRIVATE_KEY);
+    let request = Request::builder()
+        .method("GET")
+        .uri("/whoami")
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let response_json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(response_json["sub"], valid_claims()["sub"]);
+    assert_eq!(response_json["subject"], "operator");
+    assert_eq!(response_json["roles"], json!(["pjsip:read"]));
+}
@@ -0,0 +1,267 @@
+use crate::AppState;
+use crate::config::AppConfig;
+use crate::infrastructure::models::authentication::authorization_policy::{
+    AuthorizationPolicy, Permission,
+};
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    reset_pjsip_realtime_database, setup_test_state_with_config,
+};
+use crate::tests::restapi::api::v1::pjsip_realtime::auth_helper::{
+    TEST_AUDIENCE, TEST_PRIVATE_KEY, claims_with_roles, fixture_path, sign, test_oidc_config,
+    token_with_roles,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use tower::ServiceExt;
+
+async fn setup_test_state(policy: AuthorizationPolicy) -> AppState {
+    setup_test_state_with_config(AppConfig {
+        credentials_reveal_token: Some(String::from("test-reveal-token")),
+        oidc: Some(test_oidc_config()),
+        authorization_policy: policy,
+        ..AppConfig::default()
+    })
+    .await
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: &str,
+    payload: Option<Value>,
+) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .header("Content-Type", "application/json")
+        .header("X-Reveal-Token", "test-reveal-token")
+        .body(payload.map_or_else(Body::empty, |p| Body::from(p.to_string())))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn account_payload(username: &str) -> Value {
+    json!({
+        "username": username,
+        "password": "test_password",
+        "transport": "udp",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": username,
+    })
+}
+
+const ACCOUNTS_URI: &str = "/api/v1/pjsip_realtime/accounts";
+
+#[serial]
+#[tokio::test]
+async fn test_authorization_read_role() {
+    let state: AppState = setup_test_state(AuthorizationPolicy::default()).await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let token = token_with_roles(&["pjsip:read"]);
+    let (status, _) = send(&app, "GET", ACCOUNTS_URI, &token, None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, response_json) = send(
+        &app,
+        "POST",
+        ACCOUNTS_URI,
+        &token,
+        Some(account_payload("read_only_user")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(response_json["code"], "missing_role");
+    assert_eq!(response_json["permission"], "create_account");
+    assert_eq!(
+        response_json["required_roles"],
+        json!(["pjsip:write", "pjsip:admin"])
+    );
+
+    // no role at all
+    let token = token_with_roles(&[]);
+    let (status, response_json) = send(&app, "GET", ACCOUNTS_URI, &token, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(response_json["permission"], "list_accounts");
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_authorization_write_and_admin_roles() {
+    let state: AppState = setup_test_state(AuthorizationPolicy::default()).await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let write_token = token_with_roles(&["pjsip:write"]);
+    let (status, created) = send(
+        &app,
+        "POST",
+        ACCOUNTS_URI,
+        &write_token,
+        Some(account_payload("write_role_user")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let account_uri = format!("{}/{}", ACCOUNTS_URI, created["id"].as_str().unwrap());
+
+    let (status, _) = send(
+        &app,
+        "PATCH",
+        &account_uri,
+        &write_token,
+        Some(json!({ "context": "users" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // reveal and delete require pjsip:admin
+    let (status, response_json) = send(
+        &app,
+        "GET",
+        &format!("{}/credentials", account_uri),
+        &write_token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(response_json["permission"], "reveal_credentials");
+    assert!(response_json.get("password").is_none());
+    // the pjsip.conf export includes the password only with reveal_credentials
+    let conf_uri = format!("{}/pjsip.conf", account_uri);
+    let (status, _) = send(&app, "GET", &conf_uri, &write_token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, response_json) = send(
+        &app,
+        "GET",
+        &format!("{}?include_secrets=true", conf_uri),
+        &write_token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(response_json["permission"], "reveal_credentials");
+    let (status, _) = send(&app, "DELETE", &account_uri, &write_token, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let admin_token = token_with_roles(&["pjsip:admin"]);
+    let (status, response_json) = send(
+        &app,
+        "GET",
+        &format!("{}/credentials", account_uri),
+        &admin_token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["password"], "test_password");
+    let (status, _) = send(
+        &app,
+        "GET",
+        &format!("{}?include_secrets=true", conf_uri),
+        &admin_token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, "DELETE", &account_uri, &admin_token, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_authorization_client_roles() {
+    let state: AppState = setup_test_state(AuthorizationPolicy::default()).await;
+    let app: Router = crate::restapi::routes::root::create_router(state);
+
+    // client roles of the configured audience are accepted
+    let mut claims = claims_with_roles(&[]);
+    claims["resource_access"] = json!({ TEST_AUDIENCE: { "roles": ["pjsip:read"] } });
+    let token = sign(&claims, TEST_PRIVATE_KEY);
+    let (status, _) = send(&app, "GET", ACCOUNTS_URI, &token, None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // client roles of other clients are ignored
+    let mut claims = claims_with_roles(&[]);
+    claims["resource_access"] = json!({ "another-client": { "roles": ["pjsip:read"] } });
+    let token = sign(&claims, TEST_PRIVATE_KEY);
+    let (status, _) = send(&app, "GET", ACCOUNTS_URI, &token, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[serial]
+#[tokio::test]
+async fn test_authorization_policy_file() {
+    let policy = AuthorizationPolicy::from_file(&fixture_path("authz_policy.json")).unwrap();
+    // operations not listed in the file keep the default roles
+    assert_eq!(
+        policy.allowed_roles(Permission::ListAccounts),
+        AuthorizationPolicy::default().allowed_roles(Permission::ListAccounts)
+    );
+    assert_eq!(
+        policy.allowed_roles(Permission::DeleteAccount),
+        [String::from("pjsip:owner")]
+    );
+
+    let state: AppState = setup_test_state(policy).await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let (_, created) = send(
+        &app,
+        "POST",
+        ACCOUNTS_URI,
+        &token_with_roles(&["pjsip:write"]),
+        Some(account_payload("policy_file_user")),
+    )
+    .await;
+    let account_uri = format!("{}/{}", ACCOUNTS_URI, created["id"].as_str().unwrap());
+
+    let (status, response_json) = send(
+        &app,
+        "DELETE",
+        &account_uri,
+        &token_with_roles(&["pjsip:admin"]),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(response_json["required_roles"], json!(["pjsip:owner"]));
+
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        &account_uri,
+        &token_with_roles(&["pjsip:owner"]),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[test]
+fn test_authorization_policy_rejects_unknown_operations() {
+    let result = serde_json::from_value::<AuthorizationPolicy>(json!({
+        "remove_account": ["pjsip:admin"],
+    }));
+    assert!(result.is_err());
+}
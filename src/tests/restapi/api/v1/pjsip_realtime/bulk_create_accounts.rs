@@ -0,0 +1,201 @@
+use crate::AppState;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    reset_pjsip_realtime_database, setup_test_state,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+async fn bulk_create(app: &Router, query: &str, payload: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/api/v1/pjsip_realtime/accounts/bulk{}", query))
+        .header("Content-Type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+async fn count_accounts(pool: &PgPool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM pjsip_realtime_accounts")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+fn account(username: &str, transport: &str) -> Value {
+    json!({
+        "username": username,
+        "password": "test_password",
+        "transport": transport,
+        "context": "from-sipproxy",
+        "from_domain": "sip.example.com",
+        "from_user": username,
+    })
+}
+
+#[serial]
+#[tokio::test]
+async fn test_bulk_create_pjsip_realtime_accounts_atomic() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let mut with_id = account("bulk_user_3", "ws");
+    with_id["id"] = json!("01JZ0K6Q4W8Y3N2V5T7R9P1M3B");
+    let payload = json!([
+        account("bulk_user_1", "udp"),
+        account("bulk_user_2", "tls"),
+        with_id
+    ]);
+    let (status, response_json) = bulk_create(&app, "", payload).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(response_json["mode"], "atomic");
+    assert_eq!(response_json["created"], 3);
+    assert_eq!(response_json["failed"], 0);
+    let results = response_json["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    for (index, result) in results.iter().enumerate() {
+        assert_eq!(result["index"], index);
+        assert_eq!(result["status"], 201);
+        assert_eq!(result["username"], format!("bulk_user_{}", index + 1));
+        assert!(result.get("error").is_none());
+    }
+    assert_eq!(results[2]["id"], "01JZ0K6Q4W8Y3N2V5T7R9P1M3B");
+    assert_eq!(count_accounts(&state.pjsip_db).await, 3);
+
+    let transport: String =
+        sqlx::query_scalar("SELECT transport::text FROM ps_endpoints WHERE id = $1")
+            .bind(results[1]["id"].as_str().unwrap())
+            .fetch_one(&state.pjsip_db)
+            .await
+            .unwrap();
+    assert_eq!(transport, "tls");
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_bulk_create_pjsip_realtime_accounts_atomic_rolls_back() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    // 同じリクエスト内の username の重複
+    let payload = json!([
+        account("bulk_user_1", "udp"),
+        account("bulk_user_2", "udp"),
+        account("bulk_user_1", "tcp")
+    ]);
+    let (status, response_json) = bulk_create(&app, "?mode=atomic", payload).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(response_json["code"], "duplicate");
+    assert_eq!(response_json["field"], "username");
+    assert_eq!(response_json["index"], 2);
+    assert_eq!(count_accounts(&state.pjsip_db).await, 0);
+
+    // 検証エラーは登録前にすべての項目分をまとめて返します
+    let mut invalid_password = account("bulk_user_1", "udp");
+    invalid_password["password"] = json!("short");
+    let mut invalid_domain = account("bulk_user_3", "udp");
+    invalid_domain["from_domain"] = json!("not a domain");
+    let payload = json!([
+        invalid_password,
+        account("bulk_user_2", "udp"),
+        invalid_domain
+    ]);
+    let (status, response_json) = bulk_create(&app, "", payload).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["code"], "invalid_fields");
+    let fields: Vec<&str> = response_json["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(fields, vec!["[0].password", "[2].from_domain"]);
+    assert_eq!(count_accounts(&state.pjsip_db).await, 0);
+}
+
+#[serial]
+#[tokio::test]
+async fn test_bulk_create_pjsip_realtime_accounts_best_effort() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let mut invalid = account("bulk_user_2", "udp");
+    invalid["context"] = json!("");
+    let mut invalid_tls = account("bulk_user_5", "tls");
+    invalid_tls["tls"] = json!({ "transport_name": " " });
+    let payload = json!([
+        account("bulk_user_1", "udp"),
+        invalid,
+        account("bulk_user_1", "tcp"),
+        account("bulk_user_4", "wss"),
+        invalid_tls
+    ]);
+    let (status, response_json) = bulk_create(&app, "?mode=best_effort", payload).await;
+    assert_eq!(status, StatusCode::MULTI_STATUS);
+    assert_eq!(response_json["mode"], "best_effort");
+    assert_eq!(response_json["created"], 2);
+    assert_eq!(response_json["failed"], 3);
+
+    let results = response_json["results"].as_array().unwrap();
+    let statuses: Vec<u64> = results
+        .iter()
+        .map(|r| r["status"].as_u64().unwrap())
+        .collect();
+    assert_eq!(statuses, vec![201, 422, 409, 201, 422]);
+    assert!(results[0]["id"].is_string());
+    assert!(results[1].get("id").is_none());
+    assert_eq!(results[1]["error"]["code"], "invalid_fields");
+    assert_eq!(results[1]["error"]["errors"][0]["field"], "context");
+    assert_eq!(results[2]["error"]["code"], "duplicate");
+    assert_eq!(results[2]["error"]["field"], "username");
+    assert_eq!(results[4]["error"]["code"], "invalid_fields");
+    assert_eq!(
+        results[4]["error"]["errors"][0]["field"],
+        "tls.transport_name"
+    );
+    assert_eq!(count_accounts(&state.pjsip_db).await, 2);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_bulk_create_pjsip_realtime_accounts_invalid_request() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    let (status, response_json) = bulk_create(&app, "", json!([])).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(response_json["code"], "validation_failed");
+
+    let (status, response_json) = bulk_create(
+        &app,
+        "?mode=partial",
+        json!([account("bulk_user_1", "udp")]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(response_json["code"], "invalid_query");
+
+    let (status, response_json) =
+        bulk_create(&app, "", json!({ "username": "not_an_array" })).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["code"], "invalid_request_body");
+}
@@ -32,7 +32,7 @@ async fn test_create_pjsip_realtime_account() {
             panic!("Failed to create PJSIP database connection pool");
         }
     };
-    let state = AppState { pjsip_db };
+    let state = AppState::new(pjsip_db);
     let app = crate::restapi::routes::root::create_router(state.clone());
 
     // reset database before test
@@ -73,11 +73,14 @@ async fn test_create_pjsip_realtime_account() {
         payload["transport"], response_json["transport"]
     );
     assert_eq!(response_json["username"], payload["username"]);
-    assert_eq!(response_json["password"], payload["password"]);
+    assert!(response_json.get("password").is_none());
     assert_eq!(response_json["transport"], payload["transport"]);
     assert_eq!(response_json["context"], payload["context"]);
     assert_eq!(response_json["from_domain"], payload["from_domain"]);
     assert_eq!(response_json["from_user"], payload["from_user"]);
+    // the stored account is read back, including the ps_* defaults
+    assert_eq!(response_json["rtp_timeout"], 30);
+    assert_eq!(response_json["endpoint"]["allow"], "ulaw,opus");
 
     // reset database after test
     reset_pjsip_realtime_database(&state.pjsip_db).await;
@@ -14,10 +14,9 @@ mod tests {
     use tower::ServiceExt;
 
     use crate::{
-        AppState,
-        create_pjsip_pool,
+        AppState, create_pjsip_pool,
         infrastructure::models::pjsip_realtime::{
-            account::PjsipRealtimeAccountWithId, enums::pjsip_endpoint_enums::TransportType,
+            account::PjsipRealtimeAccountResponse, enums::pjsip_endpoint_enums::TransportType,
         },
         restapi::routes::pjsip_realtime_router::pjsip_realtime_router,
     };
@@ -37,20 +36,20 @@ mod tests {
         };
 
         // Clean up any existing test data
-        let _ = sqlx::query("DELETE FROM pjsip_realtime_accounts WHERE id = '01HX1234567890ABCDEFGHIJK9' OR username = 'external_id_test_user'")
+        let _ = sqlx::query("DELETE FROM pjsip_realtime_accounts WHERE id = '01HX1234567890ABCDEFGHJK09' OR username = 'external_id_test_user'")
             .execute(&pool)
             .await;
-        let _ = sqlx::query("DELETE FROM ps_auths WHERE id = '01HX1234567890ABCDEFGHIJK9' OR username = 'external_id_test_user'")
+        let _ = sqlx::query("DELETE FROM ps_auths WHERE id = '01HX1234567890ABCDEFGHJK09' OR username = 'external_id_test_user'")
             .execute(&pool)
             .await;
-        let _ = sqlx::query("DELETE FROM ps_aors WHERE id = '01HX1234567890ABCDEFGHIJK9' OR username = 'external_id_test_user'")
+        let _ = sqlx::query("DELETE FROM ps_aors WHERE id = '01HX1234567890ABCDEFGHJK09' OR username = 'external_id_test_user'")
             .execute(&pool)
             .await;
-        let _ = sqlx::query("DELETE FROM ps_endpoints WHERE id = '01HX1234567890ABCDEFGHIJK9' OR username = 'external_id_test_user'")
+        let _ = sqlx::query("DELETE FROM ps_endpoints WHERE id = '01HX1234567890ABCDEFGHJK09' OR username = 'external_id_test_user'")
             .execute(&pool)
             .await;
 
-        let state: AppState = AppState { pjsip_db: pool };
+        let state: AppState = AppState::new(pool);
         pjsip_realtime_router(state)
     }
 
@@ -61,7 +60,7 @@ mod tests {
         // Create database pool using the same method as other tests
         let create_pjsip_pool_result: Result<Pool<Postgres>, Error> = create_pjsip_pool().await;
         if let Ok(pool) = create_pjsip_pool_result {
-            let _ = sqlx::query("DELETE FROM pjsip_realtime_accounts WHERE id = '01HX1234567890ABCDEFGHIJK9' OR username = 'external_id_test_user'")
+            let _ = sqlx::query("DELETE FROM pjsip_realtime_accounts WHERE id = '01HX1234567890ABCDEFGHJK09' OR username = 'external_id_test_user'")
                 .execute(&pool)
                 .await;
         }
@@ -76,7 +75,7 @@ mod tests {
         let app: Router = setup_test_app().await;
 
         let request_body: Value = serde_json::json!({
-            "id": "01HX1234567890ABCDEFGHIJK9",
+            "id": "01HX1234567890ABCDEFGHJK09",
             "username": "external_id_test_user",
             "password": "test_password_123",
             "transport": "udp",
@@ -97,9 +96,9 @@ mod tests {
         assert_eq!(response.status(), StatusCode::CREATED);
 
         let body: Bytes = response.into_body().collect().await.unwrap().to_bytes();
-        let created_account: PjsipRealtimeAccountWithId = serde_json::from_slice(&body).unwrap();
+        let created_account: PjsipRealtimeAccountResponse = serde_json::from_slice(&body).unwrap();
 
-        assert_eq!(created_account.id, "01HX1234567890ABCDEFGHIJK9");
+        assert_eq!(created_account.id, "01HX1234567890ABCDEFGHJK09");
         assert_eq!(created_account.username, "external_id_test_user");
         assert_eq!(created_account.transport, TransportType::Udp);
         assert_eq!(
@@ -139,17 +138,15 @@ mod tests {
 
         let response: Response<Body> = app.oneshot(request).await.unwrap();
 
-        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
 
         let body: Bytes = response.into_body().collect().await.unwrap().to_bytes();
         let error_response: Value = serde_json::from_slice(&body).unwrap();
 
-        assert!(error_response.get("error").is_some());
-        assert!(
-            error_response["error"]
-                .as_str()
-                .unwrap()
-                .contains("ID cannot be empty")
+        assert_eq!(error_response["code"], "invalid_fields");
+        assert_eq!(
+            error_response["errors"],
+            serde_json::json!([{ "field": "id", "message": "must not be empty" }])
         );
     }
 
@@ -158,7 +155,7 @@ mod tests {
         let app: Router = setup_test_app().await;
 
         let request_body: Value = serde_json::json!({
-            "id": "01HX1234567890DUPLICATE_ID",
+            "id": "01HX1234567890ABCDEFGHJKMN",
             "username": "duplicate_test_user_1",
             "password": "test_password_123",
             "transport": "udp",
@@ -181,7 +178,7 @@ mod tests {
 
         // Second request with same ID - should fail
         let request_body2 = serde_json::json!({
-            "id": "01HX1234567890DUPLICATE_ID",
+            "id": "01HX1234567890ABCDEFGHJKMN",
             "username": "duplicate_test_user_2",
             "password": "test_password_456",
             "transport": "udp",
@@ -0,0 +1,282 @@
+use crate::AppState;
+use crate::config::AppConfig;
+use crate::infrastructure::models::pjsip_realtime::enums::pjsip_auth_enums::AuthType;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    reset_pjsip_realtime_database, setup_test_state,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use sqlx::{PgPool, Row};
+use tower::ServiceExt;
+
+async fn send_json(app: &Router, method: &str, uri: &str, payload: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+async fn stored_auth(
+    pool: &PgPool,
+    account_id: &str,
+) -> (String, Option<String>, Option<String>, Option<String>) {
+    let row = sqlx::query(
+        "SELECT auth_type::text AS auth_type, md5_cred, password, realm FROM ps_auths WHERE id = $1",
+    )
+    .bind(account_id)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    (
+        row.get("auth_type"),
+        row.get("md5_cred"),
+        row.get("password"),
+        row.get("realm"),
+    )
+}
+
+async fn stored_account_password(pool: &PgPool, account_id: &str) -> Option<String> {
+    sqlx::query_scalar("SELECT password FROM pjsip_realtime_accounts WHERE id = $1")
+        .bind(account_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_md5_pjsip_realtime_account() {
+    /*
+    curl -X POST -H "Content-Type: application/json" \
+      -d '{"username" : "md5_user" , "auth_type": "md5", ... }' \
+      http://localhost:3000/api/v1/pjsip_realtime/accounts/
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let (status, response_json) = send_json(
+        &app,
+        "POST",
+        "/api/v1/pjsip_realtime/accounts",
+        json!({
+            "username": "md5_test_user",
+            "password": "secret_password",
+            "transport": "udp",
+            "context": "from-sipproxy",
+            "from_domain": "default_domain",
+            "from_user": "md5_test_user",
+            "auth_type": "md5",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let account_id = response_json["id"].as_str().unwrap().to_string();
+
+    // MD5("md5_test_user:asterisk:secret_password")
+    let (auth_type, md5_cred, password, realm) = stored_auth(&state.pjsip_db, &account_id).await;
+    assert_eq!(auth_type, "md5");
+    assert_eq!(
+        md5_cred.as_deref(),
+        Some("f385beda90cde7af7d51948e9e58a1a6")
+    );
+    assert_eq!(password, None);
+    assert_eq!(realm.as_deref(), Some("asterisk"));
+    // no plaintext copy in pjsip_realtime_accounts
+    assert_eq!(
+        stored_account_password(&state.pjsip_db, &account_id).await,
+        None
+    );
+
+    // the account view reports the auth type
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/api/v1/pjsip_realtime/accounts/{}", account_id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let detail: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(detail["auth"]["auth_type"], "md5");
+
+    // reveal returns the digest instead of the password
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/api/v1/pjsip_realtime/accounts/{}/credentials",
+            account_id
+        ))
+        .header("X-Reveal-Token", "test-reveal-token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let credentials: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(credentials["auth_type"], "md5");
+    assert_eq!(credentials["md5_cred"], "f385beda90cde7af7d51948e9e58a1a6");
+    assert_eq!(credentials["realm"], "asterisk");
+    assert!(credentials.get("password").is_none());
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_md5_pjsip_realtime_account_by_server_default() {
+    let state: AppState = setup_test_state().await;
+    let md5_state = AppState::with_config(
+        state.pjsip_db.clone(),
+        AppConfig {
+            default_auth_type: AuthType::Md5,
+            default_realm: Some(String::from("asterisk")),
+            auth_disabled: true,
+            ..AppConfig::default()
+        },
+    );
+    let app: Router = crate::restapi::routes::root::create_router(md5_state);
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let payload: Value = json!({
+        "username": "default_md5_user",
+        "password": "secret_password",
+        "transport": "ws",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "default_md5_user",
+    });
+    let (status, response_json) =
+        send_json(&app, "POST", "/api/v1/pjsip_realtime/accounts", payload).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let account_id = response_json["id"].as_str().unwrap().to_string();
+    let (auth_type, md5_cred, password, _) = stored_auth(&state.pjsip_db, &account_id).await;
+    assert_eq!(auth_type, "md5");
+    assert!(md5_cred.is_some());
+    assert_eq!(password, None);
+
+    // explicit userpass overrides the server default
+    let payload: Value = json!({
+        "username": "explicit_userpass_user",
+        "password": "secret_password",
+        "transport": "udp",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "explicit_userpass_user",
+        "auth_type": "userpass",
+    });
+    let (status, response_json) =
+        send_json(&app, "POST", "/api/v1/pjsip_realtime/accounts", payload).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let account_id = response_json["id"].as_str().unwrap().to_string();
+    let (auth_type, md5_cred, password, _) = stored_auth(&state.pjsip_db, &account_id).await;
+    assert_eq!(auth_type, "userpass");
+    assert_eq!(md5_cred, None);
+    assert_eq!(password.as_deref(), Some("secret_password"));
+
+    // unsupported auth type
+    let payload: Value = json!({
+        "username": "oauth_user",
+        "password": "secret_password",
+        "transport": "udp",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "oauth_user",
+        "auth_type": "google_oauth",
+    });
+    let (status, response_json) =
+        send_json(&app, "POST", "/api/v1/pjsip_realtime/accounts", payload).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["code"], "invalid_fields");
+    assert_eq!(response_json["errors"][0]["field"], "auth_type");
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_update_md5_pjsip_realtime_account() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    // userpass account converted to md5 keeps working without re-sending the password
+    let (status, response_json) = send_json(
+        &app,
+        "POST",
+        "/api/v1/pjsip_realtime/accounts",
+        json!({
+            "username": "md5_test_user",
+            "password": "secret_password",
+            "transport": "udp",
+            "context": "from-sipproxy",
+            "from_domain": "default_domain",
+            "from_user": "md5_test_user",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let account_id = response_json["id"].as_str().unwrap().to_string();
+    let uri = format!("/api/v1/pjsip_realtime/accounts/{}", account_id);
+
+    let (status, _) = send_json(&app, "PATCH", &uri, json!({ "auth_type": "md5" })).await;
+    assert_eq!(status, StatusCode::OK);
+    let (auth_type, md5_cred, password, _) = stored_auth(&state.pjsip_db, &account_id).await;
+    assert_eq!(auth_type, "md5");
+    assert_eq!(
+        md5_cred.as_deref(),
+        Some("f385beda90cde7af7d51948e9e58a1a6")
+    );
+    assert_eq!(password, None);
+    assert_eq!(
+        stored_account_password(&state.pjsip_db, &account_id).await,
+        None
+    );
+
+    // fields unrelated to the digest can be changed without the password
+    let (status, _) = send_json(&app, "PATCH", &uri, json!({ "context": "users" })).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, unchanged_md5_cred, _, _) = stored_auth(&state.pjsip_db, &account_id).await;
+    assert_eq!(unchanged_md5_cred, md5_cred);
+
+    // renaming needs the password to recompute the digest
+    let (status, response_json) = send_json(
+        &app,
+        "PATCH",
+        &uri,
+        json!({ "username": "md5_renamed_user" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["code"], "invalid_fields");
+    assert_eq!(response_json["errors"][0]["field"], "password");
+
+    let (status, _) = send_json(
+        &app,
+        "PATCH",
+        &uri,
+        json!({ "username": "md5_renamed_user", "password": "new_password" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, md5_cred, _, _) = stored_auth(&state.pjsip_db, &account_id).await;
+    // MD5("md5_renamed_user:asterisk:new_password")
+    assert_eq!(
+        md5_cred.as_deref(),
+        Some("91f2d140b3054f0a6f5e748847d5858b")
+    );
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
@@ -0,0 +1,135 @@
+use crate::AppState;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    reset_pjsip_realtime_database, setup_test_state,
+};
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::Value;
+use serial_test::serial;
+use sqlx::Row;
+use tower::ServiceExt;
+
+#[serial]
+#[tokio::test]
+async fn test_create_tcp_pjsip_realtime_account() {
+    /*
+    curl -X POST -H "Content-Type: application/json" \
+      -d '{"username" : "tcp_user" , "transport": "tcp", ... }' \
+      http://localhost:3000/api/v1/pjsip_realtime/accounts/
+    */
+    let state: AppState = setup_test_state().await;
+    let app = crate::restapi::routes::root::create_router(state.clone());
+
+    // reset database before test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    // Define the JSON payload
+    let payload: Value = serde_json::json!({
+        "username": "tcp_test_user",
+        "password": "test_password",
+        "transport": "tcp",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "default_user",
+    });
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/pjsip_realtime/accounts")
+        .header("Content-Type", "application/json")
+        .header("Accept", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    // Assert the response status code
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // Assert the response body
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let response_json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(response_json["username"], payload["username"]);
+    assert_eq!(response_json["transport"], payload["transport"]);
+    assert_eq!(response_json["context"], payload["context"]);
+    assert_eq!(response_json["from_domain"], payload["from_domain"]);
+    assert_eq!(response_json["from_user"], payload["from_user"]);
+    assert_eq!(response_json["rtp_timeout"], 30);
+    assert_eq!(response_json["rtp_timeout_hold"], 300);
+
+    // Assert the stored ps_endpoints / ps_aors rows
+    let account_id = response_json["id"].as_str().unwrap();
+    let endpoint = sqlx::query(
+        "SELECT transport, rtp_timeout, rtp_timeout_hold FROM ps_endpoints WHERE id = $1",
+    )
+    .bind(account_id)
+    .fetch_one(&state.pjsip_db)
+    .await
+    .unwrap();
+    assert_eq!(endpoint.get::<String, _>("transport"), "tcp");
+    assert_eq!(endpoint.get::<Option<i32>, _>("rtp_timeout"), Some(30));
+    assert_eq!(
+        endpoint.get::<Option<i32>, _>("rtp_timeout_hold"),
+        Some(300)
+    );
+
+    let max_contacts: Option<i32> =
+        sqlx::query_scalar("SELECT max_contacts FROM ps_aors WHERE id = $1")
+            .bind(account_id)
+            .fetch_one(&state.pjsip_db)
+            .await
+            .unwrap();
+    assert_eq!(max_contacts, Some(1));
+
+    // reset database after test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_tcp_pjsip_realtime_account_with_external_id() {
+    let state: AppState = setup_test_state().await;
+    let app = crate::restapi::routes::root::create_router(state.clone());
+
+    // reset database before test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let payload: Value = serde_json::json!({
+        "id": "01HX1234567890TCPACCXNT001",
+        "username": "tcp_external_id_user",
+        "password": "test_password",
+        "transport": "tcp",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "tcp_external_id_user",
+        "rtp_timeout": 60,
+        "rtp_timeout_hold": 600,
+    });
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/pjsip_realtime/accounts_with_id")
+        .header("Content-Type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let response_json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(response_json["id"], payload["id"]);
+    assert_eq!(response_json["transport"], "tcp");
+
+    let rtp_timeout: Option<i32> =
+        sqlx::query_scalar("SELECT rtp_timeout FROM ps_endpoints WHERE id = $1")
+            .bind("01HX1234567890TCPACCXNT001")
+            .fetch_one(&state.pjsip_db)
+            .await
+            .unwrap();
+    assert_eq!(rtp_timeout, Some(60));
+
+    // reset database after test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
@@ -0,0 +1,201 @@
+use crate::AppState;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    reset_pjsip_realtime_database, setup_test_state,
+};
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use tower::ServiceExt;
+
+async fn send_json(app: &Router, method: &str, uri: String, payload: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_tls_pjsip_realtime_account() {
+    /*
+    curl -X POST -H "Content-Type: application/json" \
+      -d '{"username" : "tls_user" , "transport": "tls", ... }' \
+      http://localhost:3000/api/v1/pjsip_realtime/accounts/
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    // reset database before test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let payload: Value = json!({
+        "username": "tls_test_user",
+        "password": "test_password",
+        "transport": "tls",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "tls_test_user",
+    });
+    let (status, response_json) = send_json(
+        &app,
+        "POST",
+        String::from("/api/v1/pjsip_realtime/accounts"),
+        payload.clone(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(response_json["username"], payload["username"]);
+    assert_eq!(response_json["transport"], "tls");
+
+    // endpoint defaults: TLS transport name "tls" and SRTP (SDES)
+    let account_id = response_json["id"].as_str().unwrap();
+    let (status, detail) = send_json(
+        &app,
+        "GET",
+        format!("/api/v1/pjsip_realtime/accounts/{}", account_id),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(detail["endpoint"]["transport"], "tls");
+    assert_eq!(detail["endpoint"]["media_encryption"], "sdes");
+    assert!(detail["endpoint"]["media_encryption_optimistic"].is_null());
+    assert_eq!(detail["aor"]["max_contacts"], 1);
+    assert_eq!(detail["tls"]["transport_name"], "tls");
+    assert_eq!(detail["tls"]["media_encryption"], "sdes");
+    assert!(detail.get("dtls").is_none());
+
+    // reset database after test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_tls_pjsip_realtime_account_with_options() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    // reset database before test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let payload: Value = json!({
+        "username": "tls_option_user",
+        "password": "test_password",
+        "transport": "tls",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "tls_option_user",
+        "tls": {
+            "transport_name": "transport-tls",
+            "media_encryption_optimistic": "yes",
+        },
+    });
+    let (status, response_json) = send_json(
+        &app,
+        "POST",
+        String::from("/api/v1/pjsip_realtime/accounts"),
+        payload,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let uri = format!(
+        "/api/v1/pjsip_realtime/accounts/{}",
+        response_json["id"].as_str().unwrap()
+    );
+    let (status, detail) = send_json(&app, "GET", uri.clone(), Value::Null).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(detail["endpoint"]["transport"], "transport-tls");
+    assert_eq!(detail["endpoint"]["media_encryption"], "sdes");
+    assert_eq!(detail["endpoint"]["media_encryption_optimistic"], "yes");
+
+    // updates within the same transport keep the TLS transport name
+    let (status, detail) = send_json(&app, "PATCH", uri, json!({ "context": "users" })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(detail["endpoint"]["context"], "users");
+    assert_eq!(detail["endpoint"]["transport"], "transport-tls");
+
+    // reset database after test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_tls_pjsip_realtime_account_empty_transport_name() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    // reset database before test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let payload: Value = json!({
+        "username": "tls_invalid_user",
+        "password": "test_password",
+        "transport": "tls",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "tls_invalid_user",
+        "tls": { "transport_name": " " },
+    });
+    let (status, response_json) = send_json(
+        &app,
+        "POST",
+        String::from("/api/v1/pjsip_realtime/accounts"),
+        payload,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["code"], "invalid_fields");
+    assert_eq!(response_json["errors"][0]["field"], "tls.transport_name");
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pjsip_realtime_accounts")
+        .fetch_one(&state.pjsip_db)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_udp_pjsip_realtime_account_with_tls_options() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    // reset database before test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let payload: Value = json!({
+        "username": "tls_options_udp_user",
+        "password": "test_password",
+        "transport": "udp",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "tls_options_udp_user",
+        "tls": { "transport_name": "transport-tls" },
+    });
+    let (status, response_json) = send_json(
+        &app,
+        "POST",
+        String::from("/api/v1/pjsip_realtime/accounts"),
+        payload,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["code"], "invalid_fields");
+    assert_eq!(response_json["errors"][0]["field"], "tls");
+    assert_eq!(
+        response_json["errors"][0]["message"],
+        "only allowed for transport tls"
+    );
+}
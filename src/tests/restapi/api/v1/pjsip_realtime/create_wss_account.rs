@@ -0,0 +1,207 @@
+use crate::AppState;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    reset_pjsip_realtime_database, setup_test_state,
+};
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use tower::ServiceExt;
+
+async fn send_json(app: &Router, method: &str, uri: String, payload: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_wss_pjsip_realtime_account() {
+    /*
+    curl -X POST -H "Content-Type: application/json" \
+      -d '{"username" : "wss_user" , "transport": "wss", ... }' \
+      http://localhost:3000/api/v1/pjsip_realtime/accounts/
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    // reset database before test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let payload: Value = json!({
+        "username": "wss_test_user",
+        "password": "test_password",
+        "transport": "wss",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "wss_test_user",
+    });
+    let (status, response_json) = send_json(
+        &app,
+        "POST",
+        String::from("/api/v1/pjsip_realtime/accounts"),
+        payload.clone(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(response_json["username"], payload["username"]);
+    assert_eq!(response_json["transport"], "wss");
+
+    // WebRTC defaults with DTLS-SRTP
+    let (status, detail) = send_json(
+        &app,
+        "GET",
+        format!(
+            "/api/v1/pjsip_realtime/accounts/{}",
+            response_json["id"].as_str().unwrap()
+        ),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(detail["endpoint"]["transport"], "wss");
+    assert_eq!(detail["endpoint"]["media_encryption"], "dtls");
+    assert_eq!(detail["endpoint"]["webrtc"], "yes");
+    assert_eq!(detail["endpoint"]["dtls_verify"], "fingerprint");
+    assert_eq!(detail["endpoint"]["dtls_setup"], "actpass");
+    assert_eq!(detail["endpoint"]["dtls_fingerprint"], "SHA-256");
+    assert_eq!(detail["endpoint"]["dtls_auto_generate_cert"], "yes");
+    assert_eq!(detail["dtls"]["dtls_verify"], "fingerprint");
+    assert_eq!(detail["dtls"]["bundle"], "yes");
+    assert!(detail.get("tls").is_none());
+    assert_eq!(detail["endpoint"]["rtcp_mux"], "yes");
+    assert_eq!(detail["endpoint"]["bundle"], "yes");
+
+    // reset database after test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_wss_pjsip_realtime_account_with_dtls_options() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    // reset database before test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let payload: Value = json!({
+        "username": "wss_dtls_user",
+        "password": "test_password",
+        "transport": "wss",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "wss_dtls_user",
+        "dtls": {
+            "dtls_verify": "no",
+            "dtls_setup": "passive",
+            "dtls_fingerprint": "SHA-1",
+            "bundle": "no",
+        },
+    });
+    let (status, response_json) = send_json(
+        &app,
+        "POST",
+        String::from("/api/v1/pjsip_realtime/accounts"),
+        payload,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, detail) = send_json(
+        &app,
+        "GET",
+        format!(
+            "/api/v1/pjsip_realtime/accounts/{}",
+            response_json["id"].as_str().unwrap()
+        ),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(detail["endpoint"]["dtls_verify"], "no");
+    assert_eq!(detail["endpoint"]["dtls_setup"], "passive");
+    assert_eq!(detail["endpoint"]["dtls_fingerprint"], "SHA-1");
+    assert_eq!(detail["endpoint"]["bundle"], "no");
+    // options not given keep the WebRTC defaults
+    assert_eq!(detail["endpoint"]["rtcp_mux"], "yes");
+    assert_eq!(detail["endpoint"]["dtls_auto_generate_cert"], "yes");
+
+    // reset database after test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_wss_pjsip_realtime_account_invalid_dtls_verify() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    // reset database before test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let payload: Value = json!({
+        "username": "wss_invalid_user",
+        "password": "test_password",
+        "transport": "wss",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "wss_invalid_user",
+        "dtls": { "dtls_verify": "sometimes" },
+    });
+    let (status, response_json) = send_json(
+        &app,
+        "POST",
+        String::from("/api/v1/pjsip_realtime/accounts"),
+        payload,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["code"], "invalid_fields");
+    assert_eq!(response_json["errors"][0]["field"], "dtls.dtls_verify");
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_tcp_pjsip_realtime_account_with_dtls_options() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    // reset database before test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let payload: Value = json!({
+        "username": "dtls_options_tcp_user",
+        "password": "test_password",
+        "transport": "tcp",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "dtls_options_tcp_user",
+        "dtls": { "dtls_setup": "passive" },
+    });
+    let (status, response_json) = send_json(
+        &app,
+        "POST",
+        String::from("/api/v1/pjsip_realtime/accounts"),
+        payload,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["code"], "invalid_fields");
+    assert_eq!(response_json["errors"][0]["field"], "dtls");
+    assert_eq!(
+        response_json["errors"][0]["message"],
+        "only allowed for transport ws or wss"
+    );
+}
@@ -1,4 +1,4 @@
-use crate::infrastructure::models::pjsip_realtime::account::PjsipRealtimeAccountWithId;
+use crate::infrastructure::models::pjsip_realtime::account::PjsipRealtimeAccountResponse;
 use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::reset_pjsip_realtime_database;
 use crate::{AppState, create_pjsip_pool};
 
@@ -34,7 +34,7 @@ async fn test_delete_pjsip_realtime_account() {
             panic!("Failed to create PJSIP database connection pool");
         }
     };
-    let state: AppState = AppState { pjsip_db };
+    let state: AppState = AppState::new(pjsip_db);
     let app: Router = crate::restapi::routes::root::create_router(state.clone());
 
     // reset database before test
@@ -65,7 +65,7 @@ async fn test_delete_pjsip_realtime_account() {
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let response_json: Value = serde_json::from_slice(&body).unwrap();
 
-    let account: PjsipRealtimeAccountWithId =
+    let account: PjsipRealtimeAccountResponse =
         serde_json::from_value(response_json.clone()).unwrap();
     let account_id: String = account.id.clone();
 
@@ -85,3 +85,67 @@ async fn test_delete_pjsip_realtime_account() {
     // reset database after test
     reset_pjsip_realtime_database(&state.pjsip_db).await;
 }
+
+async fn delete_account(app: &Router, uri: &str) -> (StatusCode, Option<Value>) {
+    let request = Request::builder()
+        .method("DELETE")
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).ok())
+}
+
+#[serial]
+#[tokio::test]
+async fn test_delete_pjsip_realtime_account_not_found() {
+    from_filename(".env.test").ok();
+    let pjsip_db: PgPool = create_pjsip_pool().await.unwrap();
+    let state: AppState = AppState::new(pjsip_db);
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let uri = "/api/v1/pjsip_realtime/accounts/01HXNOTEXISTACCOUNT0000000";
+    let (status, response_json) = delete_account(&app, uri).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(response_json.unwrap()["code"], "not_found");
+
+    // idempotent mode
+    let (status, response_json) = delete_account(&app, &format!("{}?if_exists=true", uri)).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(response_json.is_none());
+
+    let (status, _) = delete_account(&app, &format!("{}?if_exists=false", uri)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[serial]
+#[tokio::test]
+async fn test_delete_pjsip_realtime_account_invalid_id() {
+    from_filename(".env.test").ok();
+    let pjsip_db: PgPool = create_pjsip_pool().await.unwrap();
+    let state: AppState = AppState::new(pjsip_db);
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    let invalid_ids = [String::from("%20"), String::from("a%20b"), "x".repeat(256)];
+    for account_id in invalid_ids {
+        let uri = format!("/api/v1/pjsip_realtime/accounts/{}", account_id);
+        let (status, response_json) = delete_account(&app, &uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "id: {}", account_id);
+        assert_eq!(response_json.unwrap()["code"], "validation_failed");
+
+        // if_exists では不正な ID は許容しない
+        let (status, _) = delete_account(&app, &format!("{}?if_exists=true", uri)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "id: {}", account_id);
+    }
+
+    let (status, response_json) = delete_account(
+        &app,
+        "/api/v1/pjsip_realtime/accounts/01HXNOTEXISTACCOUNT0000000?if_exists=maybe",
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(response_json.unwrap()["code"], "invalid_query");
+}
@@ -0,0 +1,125 @@
+use crate::AppState;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    reset_pjsip_realtime_database, setup_test_state,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use tower::ServiceExt;
+
+async fn create_account(app: &Router, uri: &str, payload: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+fn account_payload(username: &str, transport: &str) -> Value {
+    json!({
+        "username": username,
+        "password": "test_password",
+        "transport": transport,
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": username,
+    })
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_pjsip_realtime_account_duplicate_username() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let uri = "/api/v1/pjsip_realtime/accounts";
+    let (status, _) = create_account(&app, uri, account_payload("duplicate_user", "udp")).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // transport が異なっても username は重複
+    for transport in ["udp", "tcp", "tls", "ws"] {
+        let (status, response_json) =
+            create_account(&app, uri, account_payload("duplicate_user", transport)).await;
+        assert_eq!(status, StatusCode::CONFLICT, "transport: {}", transport);
+        assert_eq!(response_json["code"], "duplicate");
+        assert_eq!(response_json["field"], "username");
+    }
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_pjsip_realtime_account_duplicate_id() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let uri = "/api/v1/pjsip_realtime/accounts_with_id";
+    let mut payload = account_payload("duplicate_id_user_1", "udp");
+    payload["id"] = json!("01HXDVP1CATEACC0VNT1D00000");
+    let (status, _) = create_account(&app, uri, payload.clone()).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    payload["username"] = json!("duplicate_id_user_2");
+    let (status, response_json) = create_account(&app, uri, payload).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(response_json["code"], "duplicate");
+    assert_eq!(response_json["field"], "id");
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_pjsip_realtime_account_concurrent_duplicates() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    // 同じ username を同時に作成しても 500 にならず、1 件だけ作成される
+    let uri = "/api/v1/pjsip_realtime/accounts";
+    let mut tasks = tokio::task::JoinSet::new();
+    for _ in 0..8 {
+        let app = app.clone();
+        tasks.spawn(
+            async move { create_account(&app, uri, account_payload("race_user", "udp")).await },
+        );
+    }
+    let results: Vec<(StatusCode, Value)> = tasks.join_all().await;
+
+    let created = results
+        .iter()
+        .filter(|(status, _)| *status == StatusCode::CREATED)
+        .count();
+    assert_eq!(created, 1);
+    for (status, response_json) in results
+        .iter()
+        .filter(|(status, _)| *status != StatusCode::CREATED)
+    {
+        assert_eq!(*status, StatusCode::CONFLICT);
+        assert_eq!(response_json["field"], "username");
+    }
+
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM pjsip_realtime_accounts WHERE username = $1")
+            .bind("race_user")
+            .fetch_one(&state.pjsip_db)
+            .await
+            .unwrap();
+    assert_eq!(count, 1);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
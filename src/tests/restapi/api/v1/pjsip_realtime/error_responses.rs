@@ -0,0 +1,102 @@
+use crate::AppState;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::setup_test_state;
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::Value;
+use serial_test::serial;
+use tower::ServiceExt;
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    body: Option<&str>,
+) -> (StatusCode, Option<String>, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|v| v.to_str().unwrap().to_string());
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, content_type, serde_json::from_slice(&body).unwrap())
+}
+
+#[serial]
+#[tokio::test]
+async fn test_error_response_is_problem_json() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state);
+
+    let (status, content_type, problem) = send(
+        &app,
+        "GET",
+        "/api/v1/pjsip_realtime/accounts/01HXNOTEXISTACCOUNT0000000",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(content_type.as_deref(), Some("application/problem+json"));
+    assert_eq!(problem["type"], "about:blank");
+    assert_eq!(problem["title"], "Not Found");
+    assert_eq!(problem["status"], 404);
+    assert_eq!(problem["code"], "not_found");
+    assert!(problem["detail"].is_string());
+}
+
+#[serial]
+#[tokio::test]
+async fn test_error_response_for_invalid_request_body() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state);
+
+    // JSON として不正
+    let (status, content_type, problem) =
+        send(&app, "POST", "/api/v1/pjsip_realtime/accounts", Some("{")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(content_type.as_deref(), Some("application/problem+json"));
+    assert_eq!(problem["status"], 400);
+    assert_eq!(problem["code"], "invalid_request_body");
+
+    // 必須項目の欠落
+    let (status, content_type, problem) = send(
+        &app,
+        "POST",
+        "/api/v1/pjsip_realtime/accounts",
+        Some(r#"{"username": "missing_fields"}"#),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(content_type.as_deref(), Some("application/problem+json"));
+    assert_eq!(problem["status"], 422);
+    assert_eq!(problem["code"], "invalid_request_body");
+}
+
+#[serial]
+#[tokio::test]
+async fn test_error_response_for_invalid_query() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state);
+
+    let (status, content_type, problem) = send(
+        &app,
+        "GET",
+        "/api/v1/pjsip_realtime/accounts?limit=abc",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(content_type.as_deref(), Some("application/problem+json"));
+    assert_eq!(problem["code"], "invalid_query");
+}
@@ -0,0 +1,258 @@
+use crate::AppState;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    TestAccountOptions, create_test_account, reset_pjsip_realtime_database, setup_test_state,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use http_body_util::BodyExt; // for `collect`
+use md5::{Digest, Md5};
+use serde_json::json;
+use serial_test::serial;
+use tower::ServiceExt;
+
+async fn get_text(app: &Router, uri: &str) -> (StatusCode, Option<String>, String) {
+    get_text_with_reveal_token(app, uri, None).await
+}
+
+async fn get_text_with_reveal_token(
+    app: &Router,
+    uri: &str,
+    token: Option<&str>,
+) -> (StatusCode, Option<String>, String) {
+    let mut builder = Request::builder().method("GET").uri(uri);
+    if let Some(token) = token {
+        builder = builder.header("X-Reveal-Token", token);
+    }
+    let request = builder.body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(|v| v.to_str().unwrap().to_string());
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (
+        status,
+        content_type,
+        String::from_utf8(body.to_vec()).unwrap(),
+    )
+}
+
+fn rtp_timeout_options(transport: &str) -> TestAccountOptions<'_> {
+    TestAccountOptions {
+        transport: Some(transport),
+        extra: Some(json!({"rtp_timeout": 60, "rtp_timeout_hold": 600})),
+        ..Default::default()
+    }
+}
+
+#[serial]
+#[tokio::test]
+async fn test_export_pjsip_realtime_account_pjsip_conf() {
+    /*
+    curl -X GET http://localhost:3000/api/v1/pjsip_realtime/accounts/{account_id}/pjsip.conf
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let account_id = create_test_account(&app, "conf_user_1", rtp_timeout_options("udp")).await;
+    let (status, content_type, text) = get_text(
+        &app,
+        &format!("/api/v1/pjsip_realtime/accounts/{}/pjsip.conf", account_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type.as_deref(), Some("text/plain; charset=utf-8"));
+
+    // endpoint / auth / aor の順に、アカウント ID をセクション名として出力します
+    let endpoint = format!("[{}]\ntype=endpoint\ntransport=udp\n", account_id);
+    let auth = format!("[{}]\ntype=auth\nauth_type=userpass\n", account_id);
+    let aor = format!("[{}]\ntype=aor\n", account_id);
+    let endpoint_at = text.find(&endpoint).unwrap();
+    let auth_at = text.find(&auth).unwrap();
+    let aor_at = text.find(&aor).unwrap();
+    assert!(endpoint_at < auth_at && auth_at < aor_at);
+
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines.contains(&"context=from-sipproxy"));
+    assert!(lines.contains(&"disallow=all"));
+    assert!(lines.contains(&"direct_media=no"));
+    assert!(lines.contains(&"rtp_timeout=60"));
+    assert!(lines.contains(&"rtp_timeout_hold=600"));
+    assert!(lines.contains(&"username=conf_user_1"));
+    assert!(lines.contains(&"max_contacts=5"));
+    // パスワードは出力されません
+    assert!(lines.iter().all(|line| !line.starts_with("password=")));
+    assert!(lines.contains(&"; password is not exported"));
+
+    let (status, _, _) = get_text(
+        &app,
+        "/api/v1/pjsip_realtime/accounts/01HXNOTEXISTACCOUNT0000000/pjsip.conf",
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_export_pjsip_realtime_accounts_pjsip_conf() {
+    /*
+    curl -X GET "http://localhost:3000/api/v1/pjsip_realtime/accounts/export?format=pjsip_conf&ids={id1},{id2}"
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let udp_id = create_test_account(&app, "conf_user_udp", rtp_timeout_options("udp")).await;
+    let wss_id = create_test_account(&app, "conf_user_wss", rtp_timeout_options("wss")).await;
+    let tcp_id = create_test_account(&app, "conf_user_tcp", rtp_timeout_options("tcp")).await;
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/v1/pjsip_realtime/accounts/export?format=pjsip_conf")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/plain; charset=utf-8"
+    );
+    assert_eq!(
+        response.headers()[header::CONTENT_DISPOSITION],
+        "attachment; filename=\"pjsip.conf\""
+    );
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let text = String::from_utf8(body.to_vec()).unwrap();
+    for id in [&udp_id, &wss_id, &tcp_id] {
+        assert!(text.contains(&format!("[{}]\ntype=endpoint\n", id)));
+    }
+    // WebRTC のオプション (enum の表記のまま出力します)
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines.contains(&"webrtc=yes"));
+    assert!(lines.contains(&"media_encryption=dtls"));
+    assert!(lines.contains(&"dtls_fingerprint=SHA-256"));
+
+    // ids を指定した場合はそのアカウントのみ
+    let (status, _, text) = get_text(
+        &app,
+        &format!(
+            "/api/v1/pjsip_realtime/accounts/export?format=pjsip_conf&ids={},{}",
+            udp_id, tcp_id
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(text.contains(&format!("[{}]\ntype=endpoint\n", udp_id)));
+    assert!(text.contains(&format!("[{}]\ntype=endpoint\n", tcp_id)));
+    assert!(!text.contains(&wss_id));
+
+    // CSV でも ids で絞り込めます
+    let (status, _, text) = get_text(
+        &app,
+        &format!("/api/v1/pjsip_realtime/accounts/export?ids={}", wss_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(text.lines().count(), 2);
+    assert!(text.lines().nth(1).unwrap().starts_with(&wss_id));
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_export_pjsip_realtime_accounts_pjsip_conf_with_secrets() {
+    /*
+    curl -X GET -H "X-Reveal-Token: ..." \
+      "http://localhost:3000/api/v1/pjsip_realtime/accounts/{account_id}/pjsip.conf?include_secrets=true"
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let userpass_id = create_test_account(
+        &app,
+        "conf_secret_user",
+        TestAccountOptions {
+            password: Some("secret_password"),
+            ..Default::default()
+        },
+    )
+    .await;
+    let md5_id = create_test_account(
+        &app,
+        "conf_md5_user",
+        TestAccountOptions {
+            password: Some("secret_password"),
+            extra: Some(json!({"auth_type": "md5", "realm": "sip.example.com"})),
+            ..Default::default()
+        },
+    )
+    .await;
+    let uri = format!(
+        "/api/v1/pjsip_realtime/accounts/{}/pjsip.conf?include_secrets=true",
+        userpass_id
+    );
+
+    // credentials の参照と同じ reveal トークンが必要です
+    let (status, _, _) = get_text(&app, &uri).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _, _) = get_text_with_reveal_token(&app, &uri, Some("wrong-token")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _, text) = get_text_with_reveal_token(&app, &uri, Some("test-reveal-token")).await;
+    assert_eq!(status, StatusCode::OK);
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines.contains(&"password=secret_password"));
+    assert!(!lines.contains(&"; password is not exported"));
+
+    // 一括エクスポートでは md5 のアカウントは md5_cred を出力します
+    let (status, _, text) = get_text_with_reveal_token(
+        &app,
+        "/api/v1/pjsip_realtime/accounts/export?format=pjsip_conf&include_secrets=true",
+        Some("test-reveal-token"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines.contains(&"password=secret_password"));
+    // MD5("conf_md5_user:sip.example.com:secret_password")
+    let md5_cred = format!(
+        "md5_cred={:x}",
+        Md5::digest("conf_md5_user:sip.example.com:secret_password")
+    );
+    assert!(lines.contains(&md5_cred.as_str()));
+    assert!(text.contains(&format!("[{}]\ntype=auth\n", md5_id)));
+    assert!(lines.iter().all(|line| !line.ends_with("is not exported")));
+
+    // CSV には出力できません
+    let (status, _, _) = get_text_with_reveal_token(
+        &app,
+        "/api/v1/pjsip_realtime/accounts/export?include_secrets=true",
+        Some("test-reveal-token"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_export_pjsip_realtime_accounts_invalid_format() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    let (status, content_type, _) =
+        get_text(&app, "/api/v1/pjsip_realtime/accounts/export?format=ini").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(content_type.as_deref(), Some("application/problem+json"));
+}
@@ -0,0 +1,102 @@
+use crate::AppState;
+use crate::infrastructure::models::pjsip_realtime::account::PjsipRealtimeAccountResponse;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    reset_pjsip_realtime_database, setup_test_state,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use tower::ServiceExt;
+
+#[serial]
+#[tokio::test]
+async fn test_get_pjsip_realtime_account() {
+    /*
+    curl -X GET http://localhost:3000/api/v1/pjsip_realtime/accounts/{account_id}
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    // reset database before test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    // Create an account to fetch
+    let payload: Value = json!({
+        "username": "get_test_user",
+        "password": "test_password",
+        "transport": "ws",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "get_test_user",
+        "rtp_timeout": 60,
+        "rtp_timeout_hold": 600,
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/pjsip_realtime/accounts")
+        .header("Content-Type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let created: PjsipRealtimeAccountResponse = serde_json::from_slice(&body).unwrap();
+
+    // Fetch the account by ID
+    let get_request = Request::builder()
+        .method("GET")
+        .uri(format!("/api/v1/pjsip_realtime/accounts/{}", created.id))
+        .body(Body::empty())
+        .unwrap();
+    let get_response = app.oneshot(get_request).await.unwrap();
+    assert_eq!(get_response.status(), StatusCode::OK);
+
+    let body = get_response.into_body().collect().await.unwrap().to_bytes();
+    let response_json: Value = serde_json::from_slice(&body).unwrap();
+
+    // account columns
+    assert_eq!(response_json["id"], created.id.as_str());
+    assert_eq!(response_json["username"], payload["username"]);
+    assert_eq!(response_json["transport"], payload["transport"]);
+    assert_eq!(response_json["context"], payload["context"]);
+    assert_eq!(response_json["rtp_timeout"], 60);
+    assert_eq!(response_json["rtp_timeout_hold"], 600);
+
+    // joined ps_endpoints / ps_aors / ps_auths
+    assert_eq!(response_json["endpoint"]["id"], created.id.as_str());
+    assert_eq!(response_json["endpoint"]["transport"], "ws");
+    assert_eq!(response_json["endpoint"]["context"], payload["context"]);
+    assert!(!response_json["endpoint"]["webrtc"].is_null());
+    assert_eq!(response_json["aor"]["id"], created.id.as_str());
+    assert_eq!(response_json["aor"]["max_contacts"], 1);
+    assert_eq!(response_json["auth"]["id"], created.id.as_str());
+    assert_eq!(response_json["auth"]["username"], payload["username"]);
+
+    // reset database after test
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_get_pjsip_realtime_account_not_found() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/v1/pjsip_realtime/accounts/01HXNOTEXISTACCOUNT0000000")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let error_response: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error_response["code"], "not_found");
+}
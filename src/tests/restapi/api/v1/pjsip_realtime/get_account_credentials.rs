@@ -0,0 +1,130 @@
+use crate::AppState;
+use crate::config::AppConfig;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    TestAccountOptions, create_test_account, reset_pjsip_realtime_database, setup_test_state,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::Value;
+use serial_test::serial;
+use tower::ServiceExt;
+
+async fn get_credentials(
+    app: &Router,
+    account_id: &str,
+    token: Option<&str>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder().method("GET").uri(format!(
+        "/api/v1/pjsip_realtime/accounts/{}/credentials",
+        account_id
+    ));
+    if let Some(token) = token {
+        builder = builder.header("X-Reveal-Token", token);
+    }
+    let response = app
+        .clone()
+        .oneshot(builder.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[serial]
+#[tokio::test]
+async fn test_get_pjsip_realtime_account_credentials() {
+    /*
+    curl -X GET -H "X-Reveal-Token: ..." \
+      http://localhost:3000/api/v1/pjsip_realtime/accounts/{account_id}/credentials
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let account_id = create_test_account(
+        &app,
+        "reveal_test_user",
+        TestAccountOptions {
+            password: Some("secret_password"),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    // the regular single account view does not include the password
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/api/v1/pjsip_realtime/accounts/{}", account_id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let detail: Value = serde_json::from_slice(&body).unwrap();
+    assert!(detail.get("password").is_none());
+
+    let (status, response_json) =
+        get_credentials(&app, &account_id, Some("test-reveal-token")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["id"], account_id.as_str());
+    assert_eq!(response_json["username"], "reveal_test_user");
+    assert_eq!(response_json["password"], "secret_password");
+
+    // unknown account
+    let (status, _) = get_credentials(
+        &app,
+        "01HXNOTEXISTACCOUNT0000000",
+        Some("test-reveal-token"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_get_pjsip_realtime_account_credentials_forbidden() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let account_id = create_test_account(
+        &app,
+        "reveal_forbidden_user",
+        TestAccountOptions {
+            password: Some("secret_password"),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    // missing token
+    let (status, response_json) = get_credentials(&app, &account_id, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(response_json["code"], "invalid_reveal_token");
+    assert!(response_json.get("password").is_none());
+
+    // wrong token
+    let (status, _) = get_credentials(&app, &account_id, Some("wrong-token")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // reveal is disabled when no token is configured
+    let disabled_state = AppState::with_config(
+        state.pjsip_db.clone(),
+        AppConfig {
+            auth_disabled: true,
+            ..AppConfig::default()
+        },
+    );
+    let disabled_app: Router = crate::restapi::routes::root::create_router(disabled_state);
+    let (status, _) = get_credentials(&disabled_app, &account_id, Some("test-reveal-token")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
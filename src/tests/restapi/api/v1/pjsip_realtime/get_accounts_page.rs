@@ -0,0 +1,264 @@
+use crate::AppState;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    TestAccountOptions, create_test_account, reset_pjsip_realtime_database, setup_test_state,
+};
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use tower::ServiceExt;
+
+async fn get_page(app: &Router, query: &str) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/api/v1/pjsip_realtime/accounts?{}", query))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn usernames(page: &Value) -> Vec<String> {
+    page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["username"].as_str().unwrap().to_string())
+        .collect()
+}
+
+async fn seed_accounts(app: &Router) {
+    create_test_account(
+        app,
+        "page_user_a",
+        TestAccountOptions {
+            transport: Some("udp"),
+            context: Some("from-sipproxy"),
+            ..Default::default()
+        },
+    )
+    .await;
+    create_test_account(
+        app,
+        "page_user_b",
+        TestAccountOptions {
+            transport: Some("tcp"),
+            context: Some("from-sipproxy"),
+            ..Default::default()
+        },
+    )
+    .await;
+    create_test_account(
+        app,
+        "page_user_c",
+        TestAccountOptions {
+            transport: Some("udp"),
+            context: Some("users"),
+            ..Default::default()
+        },
+    )
+    .await;
+    create_test_account(
+        app,
+        "page_other_d",
+        TestAccountOptions {
+            transport: Some("tcp"),
+            context: Some("users"),
+            ..Default::default()
+        },
+    )
+    .await;
+    create_test_account(
+        app,
+        "page_user_e",
+        TestAccountOptions {
+            transport: Some("ws"),
+            context: Some("from-sipproxy"),
+            ..Default::default()
+        },
+    )
+    .await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_get_pjsip_accounts_cursor_pagination() {
+    /*
+    curl "http://localhost:3000/api/v1/pjsip_realtime/accounts?limit=2&cursor={next_cursor}"
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+    seed_accounts(&app).await;
+
+    // walk every page with limit=2 (default sort: newest first)
+    let mut collected: Vec<String> = Vec::new();
+    let mut query = String::from("limit=2");
+    let mut pages = 0;
+    loop {
+        let (status, page) = get_page(&app, &query).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(page["total"], 5);
+        assert!(page["items"].as_array().unwrap().len() <= 2);
+        collected.extend(usernames(&page));
+        pages += 1;
+        match page["next_cursor"].as_str() {
+            Some(cursor) => query = format!("limit=2&cursor={}", cursor),
+            None => break,
+        }
+    }
+    assert_eq!(pages, 3);
+    assert_eq!(
+        collected,
+        vec![
+            "page_user_e",
+            "page_other_d",
+            "page_user_c",
+            "page_user_b",
+            "page_user_a"
+        ]
+    );
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_get_pjsip_accounts_filters_and_sort() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+    seed_accounts(&app).await;
+
+    let (status, page) = get_page(&app, "transport=tcp&sort=username").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["total"], 2);
+    assert_eq!(usernames(&page), vec!["page_other_d", "page_user_b"]);
+
+    let (status, page) = get_page(&app, "context=users&sort=-username").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(usernames(&page), vec!["page_user_c", "page_other_d"]);
+
+    let (status, page) = get_page(&app, "username_prefix=page_user&sort=username&limit=3").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["total"], 4);
+    assert_eq!(
+        usernames(&page),
+        vec!["page_user_a", "page_user_b", "page_user_c"]
+    );
+    let cursor = page["next_cursor"].as_str().unwrap().to_string();
+    let (status, page) = get_page(
+        &app,
+        &format!(
+            "username_prefix=page_user&sort=username&limit=3&cursor={}",
+            cursor
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(usernames(&page), vec!["page_user_e"]);
+    assert!(page["next_cursor"].is_null());
+
+    // LIKE wildcards in the prefix are matched literally
+    let (status, page) = get_page(&app, "username_prefix=page%25").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["total"], 0);
+
+    // created-at range
+    let (status, page) = get_page(&app, "created_from=2000-01-01T00:00:00Z").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["total"], 5);
+    let (status, page) = get_page(&app, "created_to=2000-01-01T00:00:00Z").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["total"], 0);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_get_pjsip_accounts_invalid_parameters() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    let (status, page) = get_page(&app, "cursor=01HXNOTEXISTACCOUNT0000000").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(page["code"], "invalid_cursor");
+
+    let (status, _) = get_page(&app, "limit=0").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = get_page(
+        &app,
+        "created_from=2030-01-01T00:00:00Z&created_to=2020-01-01T00:00:00Z",
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[serial]
+#[tokio::test]
+async fn test_get_pjsip_accounts_rtp_timeout() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    // UDP account with explicit RTP timeouts, and one with the defaults
+    let payload: Value = json!({
+        "username": "rtp_udp_user",
+        "password": "test_password",
+        "transport": "udp",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "rtp_udp_user",
+        "rtp_timeout": 60,
+        "rtp_timeout_hold": 600,
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/pjsip_realtime/accounts")
+        .header("Content-Type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    create_test_account(
+        &app,
+        "rtp_default_user",
+        TestAccountOptions {
+            transport: Some("udp"),
+            context: Some("from-sipproxy"),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    // stored in ps_endpoints
+    let stored: Option<i32> = sqlx::query_scalar(
+        "SELECT e.rtp_timeout FROM ps_endpoints e JOIN pjsip_realtime_accounts a ON a.id = e.id WHERE a.username = $1",
+    )
+    .bind("rtp_udp_user")
+    .fetch_one(&state.pjsip_db)
+    .await
+    .unwrap();
+    assert_eq!(stored, Some(60));
+
+    // returned by the listing
+    let (status, page) = get_page(&app, "sort=username").await;
+    assert_eq!(status, StatusCode::OK);
+    let items = page["items"].as_array().unwrap();
+    assert_eq!(items[0]["username"], "rtp_default_user");
+    assert_eq!(items[0]["rtp_timeout"], 30);
+    assert_eq!(items[0]["rtp_timeout_hold"], 300);
+    assert_eq!(items[1]["username"], "rtp_udp_user");
+    assert_eq!(items[1]["rtp_timeout"], 60);
+    assert_eq!(items[1]["rtp_timeout_hold"], 600);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
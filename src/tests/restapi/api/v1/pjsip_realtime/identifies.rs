@@ -0,0 +1,297 @@
+use crate::AppState;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    TestAccountOptions, create_test_account, reset_pjsip_realtime_database, setup_test_state,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use sqlx::Row;
+use tower::ServiceExt;
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    payload: Option<Value>,
+) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .body(payload.map_or_else(Body::empty, |p| Body::from(p.to_string())))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn identifies_uri(endpoint_id: &str) -> String {
+    format!(
+        "/api/v1/pjsip_realtime/endpoints/{}/identifies",
+        endpoint_id
+    )
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_list_and_delete_pjsip_endpoint_identifies() {
+    /*
+    curl -X POST http://localhost:3000/api/v1/pjsip_realtime/endpoints/{endpoint_id}/identifies \
+    -H "Content-Type: application/json" \
+    -d '{"match": ["203.0.113.10", "198.51.100.0/24"], "match_header": "X-Carrier-Id: carrier-a"}'
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+    let account_id = create_test_account(&app, "sbc", TestAccountOptions::default()).await;
+
+    let payload = json!({
+        "match": ["203.0.113.10", "198.51.100.0/24"],
+        "match_header": "X-Carrier-Id: carrier-a"
+    });
+    let (status, created) = send(&app, "POST", &identifies_uri(&account_id), Some(payload)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["endpoint"], account_id.as_str());
+    assert_eq!(created["match"], json!(["203.0.113.10", "198.51.100.0/24"]));
+    assert_eq!(created["match_header"], "X-Carrier-Id: carrier-a");
+    let identify_id = created["id"].as_str().unwrap().to_string();
+
+    // match は 1 行の match カラムに "," 区切りで保存します
+    let row =
+        sqlx::query("SELECT endpoint, match, match_header FROM ps_endpoint_id_ips WHERE id = $1")
+            .bind(&identify_id)
+            .fetch_one(&state.pjsip_db)
+            .await
+            .unwrap();
+    assert_eq!(row.get::<String, _>("endpoint"), account_id);
+    assert_eq!(
+        row.get::<String, _>("match"),
+        "203.0.113.10,198.51.100.0/24"
+    );
+
+    // match_header のみ
+    let payload = json!({"match_header": "X-Sbc: edge-1"});
+    let (status, header_only) =
+        send(&app, "POST", &identifies_uri(&account_id), Some(payload)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(header_only["match"], json!([]));
+
+    let (status, identifies) = send(&app, "GET", &identifies_uri(&account_id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(identifies, json!([created, header_only]));
+
+    let identify_uri = format!("{}/{}", identifies_uri(&account_id), identify_id);
+    let (status, _) = send(&app, "DELETE", &identify_uri, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(&app, "DELETE", &identify_uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // アカウントを削除すると残りの識別も削除されます
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        &format!("/api/v1/pjsip_realtime/accounts/{}", account_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM ps_endpoint_id_ips")
+        .fetch_one(&state.pjsip_db)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+
+    let (status, response_json) = send(&app, "GET", &identifies_uri(&account_id), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(response_json["code"], "not_found");
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_pjsip_endpoint_identify_validation() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+    let account_id = create_test_account(&app, "sbc", TestAccountOptions::default()).await;
+
+    let (status, response_json) =
+        send(&app, "POST", &identifies_uri(&account_id), Some(json!({}))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["errors"][0]["field"], "match");
+
+    let payload = json!({
+        "match": [
+            "203.0.113.256",
+            "203.0.113.0/33",
+            "203.0.113.0/255.0.255.0",
+            "2001:db8::/129",
+            "carrier.example.com",
+            "198.51.100.0/24",
+            "198.51.100.77"
+        ],
+        "match_header": "X-Carrier-Id"
+    });
+    let (status, response_json) =
+        send(&app, "POST", &identifies_uri(&account_id), Some(payload)).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let errors: Vec<(&str, &str)> = response_json["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| (e["field"].as_str().unwrap(), e["message"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            ("match[0]", "must be a valid IP address"),
+            ("match[1]", "prefix length must be at most 32"),
+            ("match[2]", "must have a valid prefix length or netmask"),
+            ("match[3]", "prefix length must be at most 128"),
+            ("match[6]", "overlaps match[5]"),
+            ("match", "must be at most 80 characters in total"),
+            ("match_header", "must be in the form \"Header-Name: value\""),
+        ]
+    );
+
+    // 存在しないエンドポイント
+    let payload = json!({"match": ["203.0.113.10"]});
+    let (status, _) = send(&app, "POST", &identifies_uri("unknown"), Some(payload)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_pjsip_endpoint_identify_overlapping_other_endpoint() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+    let first_id = create_test_account(&app, "sbc1", TestAccountOptions::default()).await;
+    let second_id = create_test_account(&app, "sbc2", TestAccountOptions::default()).await;
+
+    let payload = json!({"match": ["203.0.113.0/255.255.255.0", "2001:db8::/32"]});
+    let (status, _) = send(&app, "POST", &identifies_uri(&first_id), Some(payload)).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // 同じエンドポイントであれば重なっても登録できます
+    let payload = json!({"match": ["203.0.113.10"]});
+    let (status, _) = send(&app, "POST", &identifies_uri(&first_id), Some(payload)).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let payload = json!({"match": ["192.0.2.1", "203.0.113.128/25"]});
+    let (status, response_json) =
+        send(&app, "POST", &identifies_uri(&second_id), Some(payload)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(response_json["code"], "match_overlap");
+    assert_eq!(response_json["field"], "match[1]");
+    assert_eq!(response_json["endpoint"], first_id.as_str());
+    assert_eq!(
+        response_json["overlapping_match"],
+        "203.0.113.0/255.255.255.0"
+    );
+
+    let payload = json!({"match": ["2001:db8:1::1"]});
+    let (status, _) = send(&app, "POST", &identifies_uri(&second_id), Some(payload)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // トランクの match も他のエンドポイントと重なってはいけません
+    let trunk = json!({
+        "name": "carrier-a",
+        "transport": "udp",
+        "server_uri": "sip:sip.carrier.example.com",
+        "username": "0312345678",
+        "password": "carrier-secret",
+        "context": "from-carrier",
+        "match": ["203.0.113.1"]
+    });
+    let (status, response_json) =
+        send(&app, "POST", "/api/v1/pjsip_realtime/trunks", Some(trunk)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(response_json["field"], "match[0]");
+
+    let payload = json!({"match": ["192.0.2.1", "198.51.100.0/24"]});
+    let (status, _) = send(&app, "POST", &identifies_uri(&second_id), Some(payload)).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_pjsip_endpoint_identifies_survive_transport_change() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+    let account_id = create_test_account(&app, "sbc", TestAccountOptions::default()).await;
+
+    let payload = json!({"match": ["203.0.113.10"], "match_header": "X-Sbc: edge-1"});
+    let (status, created) = send(&app, "POST", &identifies_uri(&account_id), Some(payload)).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // transport を変更しても識別は残ります
+    let (status, _) = send(
+        &app,
+        "PATCH",
+        &format!(
+            "/api/v1/pjsip_realtime/accounts/{}?allow_transport_change=true",
+            account_id
+        ),
+        Some(json!({"transport": "tcp"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, identifies) = send(&app, "GET", &identifies_uri(&account_id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(identifies, json!([created]));
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_overlapping_pjsip_endpoint_identifies_concurrently() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+    let first_id = create_test_account(&app, "sbc1", TestAccountOptions::default()).await;
+    let second_id = create_test_account(&app, "sbc2", TestAccountOptions::default()).await;
+
+    // 同時に登録しても、重なる match はどちらか一方のみ登録されます
+    let first_uri = identifies_uri(&first_id);
+    let second_uri = identifies_uri(&second_id);
+    let ((first_status, _), (second_status, _)) = tokio::join!(
+        send(
+            &app,
+            "POST",
+            &first_uri,
+            Some(json!({"match": ["203.0.113.0/24"]}))
+        ),
+        send(
+            &app,
+            "POST",
+            &second_uri,
+            Some(json!({"match": ["203.0.113.10"]}))
+        ),
+    );
+    let mut statuses = [first_status, second_status];
+    statuses.sort();
+    assert_eq!(statuses, [StatusCode::CREATED, StatusCode::CONFLICT]);
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM ps_endpoint_id_ips")
+        .fetch_one(&state.pjsip_db)
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
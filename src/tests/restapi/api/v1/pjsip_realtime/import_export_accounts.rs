@@ -0,0 +1,210 @@
+use crate::AppState;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    reset_pjsip_realtime_database, setup_test_state,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+const BOUNDARY: &str = "pjsip-realtime-import-boundary";
+const CSV_HEADER: &str = "id,username,password,transport,context,from_domain,from_user,rtp_timeout,rtp_timeout_hold,auth_type,realm";
+
+async fn import_csv(app: &Router, query: &str, field_name: &str, csv: &str) -> (StatusCode, Value) {
+    let body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{field_name}\"; filename=\"accounts.csv\"\r\nContent-Type: text/csv\r\n\r\n{csv}\r\n--{BOUNDARY}--\r\n"
+    );
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/api/v1/pjsip_realtime/accounts/import{}", query))
+        .header(
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", BOUNDARY),
+        )
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+async fn count_accounts(pool: &PgPool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM pjsip_realtime_accounts")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+fn csv_row(id: &str, username: &str, transport: &str) -> String {
+    format!(
+        "{id},{username},Secret_pass1,{transport},from-sipproxy,sip.example.com,{username},60,120,,"
+    )
+}
+
+#[serial]
+#[tokio::test]
+async fn test_import_and_export_pjsip_realtime_accounts_csv() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let csv = [
+        CSV_HEADER.to_string(),
+        csv_row("", "csv_user_1", "udp"),
+        csv_row("01JZ0K6Q4W8Y3N2V5T7R9P1M3C", "csv_user_2", "tls"),
+    ]
+    .join("\n");
+    let (status, response_json) = import_csv(&app, "", "file", &csv).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(response_json["dry_run"], false);
+    assert_eq!(response_json["total"], 2);
+    assert_eq!(response_json["valid"], 2);
+    assert_eq!(response_json["invalid"], 0);
+    let created = response_json["created"].as_array().unwrap();
+    assert_eq!(created.len(), 2);
+    assert_eq!(created[0]["row"], 2);
+    assert_eq!(created[0]["username"], "csv_user_1");
+    assert_eq!(created[1]["row"], 3);
+    assert_eq!(created[1]["id"], "01JZ0K6Q4W8Y3N2V5T7R9P1M3C");
+    assert_eq!(count_accounts(&state.pjsip_db).await, 2);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/v1/pjsip_realtime/accounts/export?format=csv")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/csv; charset=utf-8"
+    );
+    assert_eq!(
+        response.headers()[header::CONTENT_DISPOSITION],
+        "attachment; filename=\"pjsip_accounts.csv\""
+    );
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let exported = String::from_utf8(body.to_vec()).unwrap();
+    let lines: Vec<&str> = exported.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], CSV_HEADER);
+    // パスワードは出力されません
+    let expected = format!(
+        "{},csv_user_1,,udp,from-sipproxy,sip.example.com,csv_user_1,60,120,userpass,",
+        created[0]["id"].as_str().unwrap()
+    );
+    assert!(lines.iter().any(|line| line.starts_with(&expected)));
+    let expected = "01JZ0K6Q4W8Y3N2V5T7R9P1M3C,csv_user_2,,tls,from-sipproxy,";
+    assert!(lines.iter().any(|line| line.starts_with(expected)));
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_import_pjsip_realtime_accounts_csv_dry_run() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    // 登録済みのアカウント
+    let csv = [CSV_HEADER.to_string(), csv_row("", "csv_existing", "udp")].join("\n");
+    let (status, _) = import_csv(&app, "", "file", &csv).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // 列の順序は任意で、省略可能な列は省略できます
+    let csv = [
+        "username,password,transport,context,from_domain,from_user,rtp_timeout",
+        "csv_user_1,Secret_pass1,udp,from-sipproxy,sip.example.com,csv_user_1,",
+        "csv_user_2,short,udp,from-sipproxy,sip.example.com,csv_user_2,",
+        "csv_user_3,Secret_pass1,sctp,from-sipproxy,sip.example.com,csv_user_3,abc",
+        "csv_user_1,Secret_pass1,tcp,from-sipproxy,sip.example.com,csv_user_1,",
+        "csv_existing,Secret_pass1,tcp,from-sipproxy,sip.example.com,csv_existing,",
+    ]
+    .join("\n");
+    let (status, response_json) = import_csv(&app, "?dry_run=true", "file", &csv).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["dry_run"], true);
+    assert_eq!(response_json["total"], 5);
+    assert_eq!(response_json["valid"], 1);
+    assert_eq!(response_json["invalid"], 4);
+    assert!(response_json.get("created").is_none());
+    let errors = response_json["errors"].as_array().unwrap();
+    let has_error = |row: usize, field: &str| {
+        errors
+            .iter()
+            .any(|e| e["row"] == row && e["field"] == field)
+    };
+    assert!(has_error(3, "password"));
+    assert!(has_error(4, "transport"));
+    assert!(has_error(4, "rtp_timeout"));
+    assert!(has_error(5, "username"));
+    assert!(has_error(6, "username"));
+    let duplicate = errors.iter().find(|e| e["row"] == 5).unwrap();
+    assert_eq!(duplicate["message"], "duplicates the username in row 2");
+    // dry_run では登録しません
+    assert_eq!(count_accounts(&state.pjsip_db).await, 1);
+
+    // dry_run でない場合はエラーをまとめて 422 で返し、1 件も登録しません
+    let (status, response_json) = import_csv(&app, "", "file", &csv).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["code"], "invalid_fields");
+    assert_eq!(
+        response_json["errors"].as_array().unwrap().len(),
+        errors.len()
+    );
+    assert_eq!(count_accounts(&state.pjsip_db).await, 1);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_import_pjsip_realtime_accounts_csv_invalid_file() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    // 必須列の欠落
+    let csv = "username,password\ncsv_user_1,Secret_pass1";
+    let (status, response_json) = import_csv(&app, "", "file", csv).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(response_json["code"], "validation_failed");
+
+    // 未知の列
+    let csv = format!("{},extra\n", CSV_HEADER);
+    let (status, response_json) = import_csv(&app, "", "file", &csv).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(response_json["code"], "validation_failed");
+
+    // ヘッダーのみ
+    let (status, _) = import_csv(&app, "", "file", CSV_HEADER).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // file フィールドがない
+    let csv = [CSV_HEADER.to_string(), csv_row("", "csv_user_1", "udp")].join("\n");
+    let (status, response_json) = import_csv(&app, "", "upload", &csv).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(response_json["code"], "invalid_request_body");
+
+    // multipart でないリクエスト
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/pjsip_realtime/accounts/import")
+        .header("Content-Type", "application/json")
+        .body(Body::from(json!({}).to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    assert_eq!(count_accounts(&state.pjsip_db).await, 0);
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
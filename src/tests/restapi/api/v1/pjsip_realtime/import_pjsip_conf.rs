@@ -0,0 +1,429 @@
+use crate::AppState;
+use crate::cli::parse_import_pjsip_conf_args;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    reset_pjsip_realtime_database, setup_test_state,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::Value;
+use serial_test::serial;
+use sqlx::{PgPool, Row};
+use tower::ServiceExt;
+
+const BOUNDARY: &str = "pjsip-conf-import-boundary";
+
+async fn import_conf(app: &Router, query: &str, conf: &str) -> (StatusCode, Value) {
+    let body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"pjsip.conf\"\r\nContent-Type: text/plain\r\n\r\n{conf}\r\n--{BOUNDARY}--\r\n"
+    );
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/api/v1/pjsip_realtime/accounts/import/pjsip_conf{}",
+            query
+        ))
+        .header(
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", BOUNDARY),
+        )
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+async fn count_accounts(pool: &PgPool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM pjsip_realtime_accounts")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+const PJSIP_CONF: &str = r#"
+[transport-udp]
+type=transport
+protocol=udp
+bind=0.0.0.0
+
+[transport-tls]
+type=transport
+protocol=tls
+bind=0.0.0.0:5061
+
+;-- 共通設定
+    (テンプレート) --;
+[endpoint-base](!)
+type=endpoint
+context=from-phones
+disallow=all
+allow=ulaw
+allow=alaw ; allow は連結されます
+dtmf_mode=rfc4733
+callerid=Office <100>
+
+[aor-base](!)
+type=aor
+max_contacts=2
+qualify_frequency=30
+qualify_timeout=3.0
+
+[1001](endpoint-base)
+transport=transport-udp
+auth=1001-auth
+aors=1001
+from_domain=pbx.example.com
+
+[1001-auth]
+type=auth
+auth_type=userpass
+username=1001
+password=Secret_pass1
+
+[1001](aor-base)
+
+[1002](endpoint-base)
+transport=transport-tls
+auth=1002
+aors=1002
+context=from-tls
+
+[1002]
+type=auth
+username=1002
+password=Secret_pass2
+
+[1002](aor-base)
+max_contacts=1
+contact=sip:1002@192.0.2.10
+
+[unused-auth]
+type=auth
+username=unused
+password=Secret_pass3
+
+[office-gw]
+type=registration
+server_uri=sip:provider.example.com
+"#;
+
+#[serial]
+#[tokio::test]
+async fn test_import_pjsip_conf() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let (status, response_json) =
+        import_conf(&app, "?from_domain=sip.example.com", PJSIP_CONF).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(response_json["dry_run"], false);
+    assert_eq!(response_json["endpoints"], 2);
+    assert_eq!(response_json["valid"], 2);
+    assert_eq!(response_json["invalid"], 0);
+    assert_eq!(response_json["errors"].as_array().unwrap().len(), 0);
+    let created = response_json["created"].as_array().unwrap();
+    assert_eq!(created.len(), 2);
+    assert_eq!(created[0]["id"], "1001");
+    assert_eq!(created[0]["username"], "1001");
+    assert_eq!(created[0]["transport"], "udp");
+    assert_eq!(created[1]["id"], "1002");
+    assert_eq!(created[1]["transport"], "tls");
+
+    // 取り込めないオプションとセクションの報告
+    let unsupported = response_json["unsupported_options"].as_array().unwrap();
+    assert!(unsupported.iter().any(|o| o["section"] == "1001"
+        && o["option"] == "callerid"
+        && o["message"] == "not supported"));
+    assert!(
+        unsupported
+            .iter()
+            .any(|o| o["section"] == "1002" && o["option"] == "contact")
+    );
+    let skipped: Vec<&str> = response_json["skipped_sections"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["section"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        skipped,
+        ["transport-udp", "transport-tls", "unused-auth", "office-gw"]
+    );
+
+    // テンプレートの値とセクションの値が ps_* に反映されます
+    let endpoint = sqlx::query(
+        "SELECT transport::text AS transport, context, disallow, allow, dtmf_mode::text AS dtmf_mode, from_domain, auth, aors FROM ps_endpoints WHERE id = '1001'",
+    )
+    .fetch_one(&state.pjsip_db)
+    .await
+    .unwrap();
+    assert_eq!(endpoint.get::<String, _>("transport"), "udp");
+    assert_eq!(endpoint.get::<String, _>("context"), "from-phones");
+    assert_eq!(endpoint.get::<String, _>("disallow"), "all");
+    assert_eq!(endpoint.get::<String, _>("allow"), "ulaw,alaw");
+    assert_eq!(endpoint.get::<String, _>("dtmf_mode"), "rfc4733");
+    assert_eq!(endpoint.get::<String, _>("from_domain"), "pbx.example.com");
+    assert_eq!(endpoint.get::<String, _>("auth"), "1001");
+    assert_eq!(endpoint.get::<String, _>("aors"), "1001");
+
+    let aor = sqlx::query(
+        "SELECT max_contacts, qualify_frequency, qualify_timeout::float8 AS qualify_timeout FROM ps_aors WHERE id = '1001'",
+    )
+    .fetch_one(&state.pjsip_db)
+    .await
+    .unwrap();
+    assert_eq!(aor.get::<i32, _>("max_contacts"), 2);
+    assert_eq!(aor.get::<i32, _>("qualify_frequency"), 30);
+    assert_eq!(aor.get::<f64, _>("qualify_timeout"), 3.0);
+
+    let auth_username: String =
+        sqlx::query_scalar("SELECT username FROM ps_auths WHERE id = '1001'")
+            .fetch_one(&state.pjsip_db)
+            .await
+            .unwrap();
+    assert_eq!(auth_username, "1001");
+
+    let endpoint = sqlx::query(
+        "SELECT transport::text AS transport, context, from_domain FROM ps_endpoints WHERE id = '1002'",
+    )
+    .fetch_one(&state.pjsip_db)
+    .await
+    .unwrap();
+    assert_eq!(endpoint.get::<String, _>("transport"), "transport-tls");
+    assert_eq!(endpoint.get::<String, _>("context"), "from-tls");
+    assert_eq!(endpoint.get::<String, _>("from_domain"), "sip.example.com");
+    let max_contacts: i32 =
+        sqlx::query_scalar("SELECT max_contacts FROM ps_aors WHERE id = '1002'")
+            .fetch_one(&state.pjsip_db)
+            .await
+            .unwrap();
+    assert_eq!(max_contacts, 1);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_import_pjsip_conf_dry_run_reports_errors() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let (status, _) = import_conf(&app, "?from_domain=sip.example.com", PJSIP_CONF).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let conf = r#"
+[2001](missing-template)
+type=endpoint
+auth=2001
+from_domain=sip.example.com
+
+[2001]
+type=auth
+username=2001
+password=
+
+[2002]
+type=endpoint
+transport=transport-missing
+auth=2002
+
+[2002]
+type=auth
+username=2002
+password=Secret_pass1
+
+[2003]
+type=endpoint
+from_domain=sip.example.com
+
+[1001]
+type=endpoint
+auth=1001
+from_domain=sip.example.com
+
+[1001]
+type=auth
+username=1001-new
+password=Secret_pass1
+"#;
+    let (status, response_json) = import_conf(&app, "?dry_run=true", conf).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["dry_run"], true);
+    assert_eq!(response_json["endpoints"], 4);
+    assert_eq!(response_json["valid"], 0);
+    assert_eq!(response_json["invalid"], 4);
+    assert!(response_json.get("created").is_none());
+    let errors = response_json["errors"].as_array().unwrap();
+    let has_error = |section: &str, message: &str| {
+        errors
+            .iter()
+            .any(|e| e["section"] == section && e["message"].as_str().unwrap().contains(message))
+    };
+    assert!(has_error("2001", "template not found: missing-template"));
+    assert!(has_error("2001", "must not be empty"));
+    assert!(has_error("2002", "transport section not found"));
+    assert!(has_error("2003", "auth is not set"));
+    assert!(has_error("1001", "an account with this id already exists"));
+    assert_eq!(count_accounts(&state.pjsip_db).await, 2);
+
+    // dry_run でない場合はエラーをまとめて 422 で返し、1 件も登録しません
+    let (status, response_json) = import_conf(&app, "", conf).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["code"], "invalid_fields");
+    assert_eq!(
+        response_json["errors"].as_array().unwrap().len(),
+        errors.len()
+    );
+    assert_eq!(count_accounts(&state.pjsip_db).await, 2);
+
+    // エンドポイントのないファイル
+    let (status, response_json) = import_conf(&app, "", "[global]\ntype=global\n").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(response_json["code"], "validation_failed");
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_import_pjsip_conf_existing_credentials() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let conf = r#"
+[3001]
+type=endpoint
+auth=3001
+from_domain=sip.example.com
+
+[3001]
+type=auth
+username=3001
+password=3001
+
+[3002]
+type=endpoint
+auth=3002
+from_domain=sip.example.com
+
+[3002]
+type=auth
+username=3002
+realm=pbx.example.com
+md5_cred=0123456789ABCDEF0123456789ABCDEF
+
+[3003]
+type=endpoint
+auth=3003
+from_domain=sip.example.com
+
+[3003]
+type=auth
+auth_type=userpass
+username=3003
+md5_cred=0123456789abcdef0123456789abcdef
+
+[3004]
+type=endpoint
+auth=3004
+from_domain=sip.example.com
+
+[3004]
+type=auth
+username=3004
+md5_cred=not-a-md5-cred
+"#;
+    let (status, response_json) = import_conf(&app, "?dry_run=true", conf).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["valid"], 2);
+    assert_eq!(response_json["invalid"], 2);
+    let errors = response_json["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0]["section"], "3003");
+    assert_eq!(errors[0]["message"], "password is not set");
+    assert_eq!(errors[1]["section"], "3004");
+    assert_eq!(errors[1]["message"], "password is not set");
+    assert_eq!(errors[2]["section"], "3004");
+    assert_eq!(
+        errors[2]["message"],
+        "md5_cred must be 32 hexadecimal characters"
+    );
+
+    // 稼働中の端末のパスワードは強度の規則を満たさなくても取り込みます。
+    // md5_cred のみの auth は md5 の認証情報として取り込みます。
+    let conf = conf.split("[3003]").next().unwrap();
+    let (status, response_json) = import_conf(&app, "", conf).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(response_json["created"].as_array().unwrap().len(), 2);
+
+    let auth = sqlx::query(
+        "SELECT auth_type::text AS auth_type, password, md5_cred, realm FROM ps_auths WHERE id = '3001'",
+    )
+    .fetch_one(&state.pjsip_db)
+    .await
+    .unwrap();
+    assert_eq!(auth.get::<String, _>("auth_type"), "userpass");
+    assert_eq!(
+        auth.get::<Option<String>, _>("password").as_deref(),
+        Some("3001")
+    );
+
+    let auth = sqlx::query(
+        "SELECT auth_type::text AS auth_type, password, md5_cred, realm FROM ps_auths WHERE id = '3002'",
+    )
+    .fetch_one(&state.pjsip_db)
+    .await
+    .unwrap();
+    assert_eq!(auth.get::<String, _>("auth_type"), "md5");
+    assert_eq!(auth.get::<Option<String>, _>("password"), None);
+    assert_eq!(
+        auth.get::<Option<String>, _>("md5_cred").as_deref(),
+        Some("0123456789abcdef0123456789abcdef")
+    );
+    assert_eq!(
+        auth.get::<Option<String>, _>("realm").as_deref(),
+        Some("pbx.example.com")
+    );
+    let password: Option<String> =
+        sqlx::query_scalar("SELECT password FROM pjsip_realtime_accounts WHERE id = '3002'")
+            .fetch_one(&state.pjsip_db)
+            .await
+            .unwrap();
+    assert_eq!(password, None);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[test]
+fn test_import_pjsip_conf_cli_args() {
+    let args = |values: &[&str]| -> Vec<String> { values.iter().map(|v| v.to_string()).collect() };
+
+    let (path, params) = parse_import_pjsip_conf_args(&args(&[
+        "/etc/asterisk/pjsip.conf",
+        "--dry-run",
+        "--from-domain",
+        "sip.example.com",
+    ]))
+    .unwrap();
+    assert_eq!(path, "/etc/asterisk/pjsip.conf");
+    assert!(params.dry_run);
+    assert_eq!(params.from_domain.as_deref(), Some("sip.example.com"));
+
+    let (_, params) = parse_import_pjsip_conf_args(&args(&["pjsip.conf"])).unwrap();
+    assert!(!params.dry_run);
+    assert!(params.from_domain.is_none());
+
+    assert!(parse_import_pjsip_conf_args(&args(&[])).is_err());
+    assert!(parse_import_pjsip_conf_args(&args(&["pjsip.conf", "--from-domain"])).is_err());
+    assert!(parse_import_pjsip_conf_args(&args(&["pjsip.conf", "--force"])).is_err());
+    assert!(parse_import_pjsip_conf_args(&args(&["a.conf", "b.conf"])).is_err());
+}
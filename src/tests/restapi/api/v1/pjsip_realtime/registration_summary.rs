@@ -0,0 +1,217 @@
+use crate::AppState;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    TestAccountOptions, create_test_account, insert_ps_contact, reset_pjsip_realtime_database,
+    setup_test_state,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use tower::ServiceExt;
+
+const REGISTRATIONS_URI: &str = "/api/v1/pjsip_realtime/accounts/registrations";
+
+async fn get_json(app: &Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method("GET")
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn account_ids(summary: &Value) -> Vec<&str> {
+    summary["accounts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|account| account["id"].as_str().unwrap())
+        .collect()
+}
+
+#[serial]
+#[tokio::test]
+async fn test_get_pjsip_realtime_registration_summary() {
+    /*
+    curl -X GET "http://localhost:3000/api/v1/pjsip_realtime/accounts/registrations?status=unregistered"
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let registered_id = create_test_account(
+        &app,
+        "phone_1",
+        TestAccountOptions {
+            transport: Some("udp"),
+            context: Some("office"),
+            ..Default::default()
+        },
+    )
+    .await;
+    let expiring_id = create_test_account(
+        &app,
+        "phone_2",
+        TestAccountOptions {
+            transport: Some("udp"),
+            context: Some("office"),
+            ..Default::default()
+        },
+    )
+    .await;
+    let expired_id = create_test_account(
+        &app,
+        "phone_3",
+        TestAccountOptions {
+            transport: Some("udp"),
+            context: Some("office"),
+            ..Default::default()
+        },
+    )
+    .await;
+    let no_contacts_id = create_test_account(
+        &app,
+        "phone_4",
+        TestAccountOptions {
+            transport: Some("tcp"),
+            context: Some("lobby"),
+            ..Default::default()
+        },
+    )
+    .await;
+    let pool = &state.pjsip_db;
+    insert_ps_contact(pool, &registered_id, "sip:phone_1@192.168.1.1", "UA", 3600).await;
+    insert_ps_contact(pool, &registered_id, "sip:phone_1@192.168.1.2", "UA", 120).await;
+    insert_ps_contact(pool, &expiring_id, "sip:phone_2@192.168.1.3", "UA", 120).await;
+    insert_ps_contact(pool, &expired_id, "sip:phone_3@192.168.1.4", "UA", -60).await;
+
+    let (status, summary) = get_json(&app, REGISTRATIONS_URI).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(summary["expiring_within"], 300);
+    assert_eq!(summary["total"], 4);
+    assert_eq!(summary["registered"], 2);
+    assert_eq!(summary["unregistered"], 2);
+    assert_eq!(summary["no_contacts"], 1);
+    assert_eq!(summary["expiring_soon"], 1);
+    assert!(summary.get("accounts").is_none());
+    assert_eq!(
+        summary["groups"],
+        json!([
+            {
+                "transport": "tcp",
+                "context": "lobby",
+                "total": 1,
+                "registered": 0,
+                "unregistered": 1,
+                "no_contacts": 1,
+                "expiring_soon": 0
+            },
+            {
+                "transport": "udp",
+                "context": "office",
+                "total": 3,
+                "registered": 2,
+                "unregistered": 1,
+                "no_contacts": 0,
+                "expiring_soon": 1
+            }
+        ])
+    );
+
+    let (status, summary) =
+        get_json(&app, &format!("{}?status=unregistered", REGISTRATIONS_URI)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        account_ids(&summary),
+        [expired_id.as_str(), no_contacts_id.as_str()]
+    );
+    let no_contacts = &summary["accounts"][1];
+    assert_eq!(no_contacts["username"], "phone_4");
+    assert_eq!(no_contacts["transport"], "tcp");
+    assert_eq!(no_contacts["no_contacts"], true);
+    assert_eq!(no_contacts["contacts"], 0);
+    assert_eq!(no_contacts["expiration_time"], Value::Null);
+
+    let (_, summary) = get_json(&app, &format!("{}?status=no_contacts", REGISTRATIONS_URI)).await;
+    assert_eq!(account_ids(&summary), [no_contacts_id.as_str()]);
+
+    // accounts は cursor でページングします
+    let (_, summary) = get_json(
+        &app,
+        &format!("{}?status=unregistered&limit=1", REGISTRATIONS_URI),
+    )
+    .await;
+    assert_eq!(summary["unregistered"], 2);
+    assert_eq!(account_ids(&summary), [expired_id.as_str()]);
+    assert_eq!(summary["next_cursor"], expired_id.as_str());
+    let (_, summary) = get_json(
+        &app,
+        &format!(
+            "{}?status=unregistered&limit=1&cursor={}",
+            REGISTRATIONS_URI, expired_id
+        ),
+    )
+    .await;
+    assert_eq!(account_ids(&summary), [no_contacts_id.as_str()]);
+    assert!(summary.get("next_cursor").is_none());
+
+    let (_, summary) = get_json(&app, &format!("{}?status=registered", REGISTRATIONS_URI)).await;
+    assert_eq!(
+        account_ids(&summary),
+        [registered_id.as_str(), expiring_id.as_str()]
+    );
+    // 最も遅い有効期限を返します
+    assert_eq!(summary["accounts"][0]["contacts"], 2);
+    assert!(summary["accounts"][0]["expires_in"].as_i64().unwrap() > 3500);
+
+    let (_, summary) = get_json(&app, &format!("{}?status=expiring_soon", REGISTRATIONS_URI)).await;
+    assert_eq!(account_ids(&summary), [expiring_id.as_str()]);
+    let (_, summary) = get_json(
+        &app,
+        &format!(
+            "{}?status=expiring_soon&expiring_within=60",
+            REGISTRATIONS_URI
+        ),
+    )
+    .await;
+    assert_eq!(summary["expiring_soon"], 0);
+    assert_eq!(account_ids(&summary), Vec::<&str>::new());
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_get_pjsip_realtime_registration_summary_invalid_query() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    for query in [
+        "expiring_within=-1",
+        "expiring_within=86401",
+        "status=offline",
+        "status=registered&limit=0",
+        "status=registered&limit=501",
+    ] {
+        let (status, response_json) =
+            get_json(&app, &format!("{}?{}", REGISTRATIONS_URI, query)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", query);
+        assert_eq!(response_json["code"], "invalid_query");
+    }
+
+    let (status, response_json) = get_json(
+        &app,
+        &format!("{}?status=registered&cursor=unknown", REGISTRATIONS_URI),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(response_json["code"], "invalid_cursor");
+}
@@ -0,0 +1,255 @@
+use crate::AppState;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    reset_pjsip_realtime_database, setup_test_state,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use sqlx::Row;
+use tower::ServiceExt;
+
+const TRUNKS_URI: &str = "/api/v1/pjsip_realtime/trunks";
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    payload: Option<Value>,
+) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .body(payload.map_or_else(Body::empty, |p| Body::from(p.to_string())))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn trunk_payload(name: &str) -> Value {
+    json!({
+        "name": name,
+        "transport": "udp",
+        "server_uri": "sip:sip.carrier.example.com",
+        "username": "0312345678",
+        "password": "carrier-secret",
+        "context": "from-carrier",
+        "from_domain": "sip.carrier.example.com",
+        "match": ["203.0.113.10", "198.51.100.0/24"],
+    })
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_and_get_pjsip_realtime_trunk() {
+    /*
+    curl -X POST http://localhost:3000/api/v1/pjsip_realtime/trunks \
+    -H "Content-Type: application/json" \
+    -d '{"name": "carrier-a", "transport": "udp", "server_uri": "sip:sip.carrier.example.com", ...}'
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let (status, created) = send(&app, "POST", TRUNKS_URI, Some(trunk_payload("carrier-a"))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let trunk_id = created["id"].as_str().unwrap().to_string();
+    assert_eq!(created["name"], "carrier-a");
+    assert_eq!(created["transport"], "udp");
+    assert_eq!(
+        created["client_uri"],
+        "sip:0312345678@sip.carrier.example.com"
+    );
+    assert_eq!(created["username"], "0312345678");
+    assert_eq!(created["expiration"], 3600);
+    assert_eq!(created["match"], json!(["203.0.113.10", "198.51.100.0/24"]));
+    // パスワードは返却しません
+    assert!(created.get("password").is_none());
+
+    // ps_registrations は outbound_auth と line / endpoint でトランクの行を参照します
+    let row = sqlx::query(
+        "SELECT outbound_auth, endpoint, line::text AS line, transport FROM ps_registrations WHERE id = $1",
+    )
+    .bind(&trunk_id)
+    .fetch_one(&state.pjsip_db)
+    .await
+    .unwrap();
+    assert_eq!(row.get::<String, _>("outbound_auth"), trunk_id);
+    assert_eq!(row.get::<String, _>("endpoint"), trunk_id);
+    assert_eq!(row.get::<String, _>("line"), "yes");
+    assert_eq!(row.get::<String, _>("transport"), "udp");
+    let row = sqlx::query("SELECT outbound_auth, aors, context FROM ps_endpoints WHERE id = $1")
+        .bind(&trunk_id)
+        .fetch_one(&state.pjsip_db)
+        .await
+        .unwrap();
+    assert_eq!(row.get::<String, _>("outbound_auth"), trunk_id);
+    assert_eq!(row.get::<String, _>("context"), "from-carrier");
+    let contact: String = sqlx::query_scalar("SELECT contact FROM ps_aors WHERE id = $1")
+        .bind(&trunk_id)
+        .fetch_one(&state.pjsip_db)
+        .await
+        .unwrap();
+    assert_eq!(contact, "sip:sip.carrier.example.com");
+    let password: String = sqlx::query_scalar("SELECT password FROM ps_auths WHERE id = $1")
+        .bind(&trunk_id)
+        .fetch_one(&state.pjsip_db)
+        .await
+        .unwrap();
+    assert_eq!(password, "carrier-secret");
+
+    let (status, trunk) = send(&app, "GET", &format!("{}/{}", TRUNKS_URI, trunk_id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(trunk, created);
+
+    let (status, trunks) = send(&app, "GET", TRUNKS_URI, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(trunks.as_array().unwrap().len(), 1);
+
+    // トランクはアカウントとしては扱いません
+    let (status, _) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/pjsip_realtime/accounts/{}", trunk_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_replace_and_delete_pjsip_realtime_trunk() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let (status, created) = send(&app, "POST", TRUNKS_URI, Some(trunk_payload("carrier-a"))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let trunk_uri = format!("{}/{}", TRUNKS_URI, created["id"].as_str().unwrap());
+    // identifies API で追加した match_header の識別は PUT で置き換えません
+    let (status, header_only) = send(
+        &app,
+        "POST",
+        &format!(
+            "/api/v1/pjsip_realtime/endpoints/{}/identifies",
+            created["id"].as_str().unwrap()
+        ),
+        Some(json!({"match_header": "X-Carrier-Id: carrier-a"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let mut payload = trunk_payload("carrier-a");
+    payload["transport"] = json!("tls");
+    payload["server_uri"] = json!("sips:sip.carrier.example.com:5061");
+    payload["client_uri"] = json!("sips:0312345678@carrier.example.com");
+    payload["expiration"] = json!(600);
+    payload["match"] = json!(["203.0.113.20"]);
+    let (status, replaced) = send(&app, "PUT", &trunk_uri, Some(payload)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(replaced["id"], created["id"]);
+    assert_eq!(replaced["transport"], "tls");
+    assert_eq!(
+        replaced["client_uri"],
+        "sips:0312345678@carrier.example.com"
+    );
+    assert_eq!(replaced["expiration"], 600);
+    assert_eq!(replaced["match"], json!(["203.0.113.20"]));
+    assert_eq!(replaced["created_at"], created["created_at"]);
+    let identifies: Vec<(Option<String>, Option<String>)> = sqlx::query_as(
+        "SELECT match, match_header FROM ps_endpoint_id_ips WHERE endpoint = $1 ORDER BY id",
+    )
+    .bind(created["id"].as_str().unwrap())
+    .fetch_all(&state.pjsip_db)
+    .await
+    .unwrap();
+    assert_eq!(
+        identifies,
+        [
+            (None, header_only["match_header"].as_str().map(String::from)),
+            (Some(String::from("203.0.113.20")), None),
+        ]
+    );
+
+    let (status, _) = send(&app, "DELETE", &trunk_uri, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    for table in [
+        "ps_registrations",
+        "ps_auths",
+        "ps_aors",
+        "ps_endpoints",
+        "ps_endpoint_id_ips",
+    ] {
+        let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+            .fetch_one(&state.pjsip_db)
+            .await
+            .unwrap();
+        assert_eq!(count, 0, "{} should be empty", table);
+    }
+    let (status, _) = send(&app, "DELETE", &trunk_uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(&app, "PUT", &trunk_uri, Some(trunk_payload("carrier-a"))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_pjsip_realtime_trunk_validation_and_duplicate_name() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let mut payload = trunk_payload("carrier-a");
+    payload["transport"] = json!("wss");
+    payload["server_uri"] = json!("http://sip.carrier.example.com");
+    payload["outbound_proxy"] = json!("sip:proxy.example.com:70000");
+    payload["expiration"] = json!(0);
+    payload["match"] = json!(["203.0.113.10", "203.0.113.10"]);
+    let (status, response_json) = send(&app, "POST", TRUNKS_URI, Some(payload)).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["code"], "invalid_fields");
+    let fields: Vec<&str> = response_json["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            "transport",
+            "server_uri",
+            "outbound_proxy",
+            "expiration",
+            "match[1]"
+        ]
+    );
+
+    let (status, _) = send(&app, "POST", TRUNKS_URI, Some(trunk_payload("carrier-a"))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let mut payload = trunk_payload("carrier-a");
+    payload["match"] = json!(["192.0.2.10"]);
+    let (status, response_json) = send(&app, "POST", TRUNKS_URI, Some(payload)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(response_json["field"], "name");
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM ps_registrations")
+        .fetch_one(&state.pjsip_db)
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
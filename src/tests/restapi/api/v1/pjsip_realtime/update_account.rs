@@ -0,0 +1,382 @@
+use crate::AppState;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    TestAccountOptions, create_test_account, reset_pjsip_realtime_database, setup_test_state,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use tower::ServiceExt;
+
+async fn send_update(
+    app: &Router,
+    method: &str,
+    uri: String,
+    payload: Value,
+) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[serial]
+#[tokio::test]
+async fn test_replace_pjsip_realtime_account() {
+    /*
+    curl -X PUT -H "Content-Type: application/json" \
+      -d '{"username" : "test_user" , ... , "from_user": "Test default_user" }' \
+      http://localhost:3000/api/v1/pjsip_realtime/accounts/{account_id}
+    */
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let account_id =
+        create_test_account(&app, "put_test_user", TestAccountOptions::default()).await;
+
+    let payload: Value = json!({
+        "username": "put_test_user",
+        "password": "new_password",
+        "transport": "udp",
+        "context": "users",
+        "from_domain": "example.com",
+        "from_user": "put_from_user",
+        "rtp_timeout": 60,
+        "rtp_timeout_hold": 600,
+    });
+    let (status, response_json) = send_update(
+        &app,
+        "PUT",
+        format!("/api/v1/pjsip_realtime/accounts/{}", account_id),
+        payload.clone(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["id"], account_id.as_str());
+    assert!(response_json.get("password").is_none());
+    assert_eq!(response_json["context"], payload["context"]);
+    assert_eq!(response_json["from_domain"], payload["from_domain"]);
+    assert_eq!(response_json["from_user"], payload["from_user"]);
+    assert_eq!(response_json["rtp_timeout"], 60);
+    assert_eq!(response_json["rtp_timeout_hold"], 600);
+    assert_eq!(response_json["endpoint"]["context"], payload["context"]);
+    assert_eq!(
+        response_json["endpoint"]["from_domain"],
+        payload["from_domain"]
+    );
+
+    let auth_password: String = sqlx::query_scalar("SELECT password FROM ps_auths WHERE id = $1")
+        .bind(&account_id)
+        .fetch_one(&state.pjsip_db)
+        .await
+        .unwrap();
+    assert_eq!(auth_password, "new_password");
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_patch_pjsip_realtime_account() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let account_id =
+        create_test_account(&app, "patch_test_user", TestAccountOptions::default()).await;
+
+    let (status, response_json) = send_update(
+        &app,
+        "PATCH",
+        format!("/api/v1/pjsip_realtime/accounts/{}", account_id),
+        json!({ "context": "patched-context" }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["context"], "patched-context");
+    assert_eq!(response_json["endpoint"]["context"], "patched-context");
+    // untouched fields keep their values
+    assert_eq!(response_json["username"], "patch_test_user");
+    assert!(response_json.get("password").is_none());
+    assert_eq!(response_json["transport"], "udp");
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_patch_pjsip_realtime_account_transport_change() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let account_id =
+        create_test_account(&app, "transport_test_user", TestAccountOptions::default()).await;
+    let uri = format!("/api/v1/pjsip_realtime/accounts/{}", account_id);
+
+    // transport change without explicit request is rejected
+    let (status, response_json) =
+        send_update(&app, "PATCH", uri.clone(), json!({ "transport": "ws" })).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(response_json["code"], "transport_change_not_allowed");
+
+    // transport change with explicit request updates the transport columns in place
+    let (status, response_json) = send_update(
+        &app,
+        "PATCH",
+        format!("{}?allow_transport_change=true", uri),
+        json!({ "transport": "ws" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["id"], account_id.as_str());
+    assert_eq!(response_json["transport"], "ws");
+    assert_eq!(response_json["endpoint"]["transport"], "ws");
+    assert!(!response_json["endpoint"]["webrtc"].is_null());
+    // codecs and AOR settings are kept
+    assert_eq!(response_json["endpoint"]["allow"], "ulaw,opus");
+    assert_eq!(response_json["aor"]["max_contacts"], 5);
+    assert_eq!(response_json["aor"]["default_expiration"], 60);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_patch_pjsip_realtime_account_duplicate_username() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    create_test_account(&app, "duplicate_user_1", TestAccountOptions::default()).await;
+    let second = create_test_account(&app, "duplicate_user_2", TestAccountOptions::default()).await;
+
+    let (status, response_json) = send_update(
+        &app,
+        "PATCH",
+        format!("/api/v1/pjsip_realtime/accounts/{}", second),
+        json!({ "username": "duplicate_user_1" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(response_json["code"], "duplicate");
+    assert_eq!(response_json["field"], "username");
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_update_pjsip_realtime_account_not_found() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+
+    let (status, response_json) = send_update(
+        &app,
+        "PATCH",
+        String::from("/api/v1/pjsip_realtime/accounts/01HXNOTEXISTACCOUNT0000000"),
+        json!({ "context": "users" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(response_json["code"], "not_found");
+}
+
+#[serial]
+#[tokio::test]
+async fn test_update_pjsip_realtime_account_tls_options() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let account_id =
+        create_test_account(&app, "tls_update_user", TestAccountOptions::default()).await;
+    let uri = format!("/api/v1/pjsip_realtime/accounts/{}", account_id);
+
+    // transport change to tls uses the requested TLS options
+    let payload: Value = json!({
+        "username": "tls_update_user",
+        "password": "test_password",
+        "transport": "tls",
+        "context": "from-sipproxy",
+        "from_domain": "default_domain",
+        "from_user": "tls_update_user",
+        "tls": { "transport_name": "transport-tls" },
+    });
+    let (status, response_json) = send_update(
+        &app,
+        "PUT",
+        format!("{}?allow_transport_change=true", uri),
+        payload,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["endpoint"]["transport"], "transport-tls");
+    assert_eq!(response_json["endpoint"]["media_encryption"], "sdes");
+    assert!(response_json["endpoint"]["media_encryption_optimistic"].is_null());
+
+    // TLS options can be changed within the same transport
+    let (status, response_json) = send_update(
+        &app,
+        "PATCH",
+        uri.clone(),
+        json!({ "tls": { "transport_name": "transport-tls-2", "media_encryption_optimistic": "yes" } }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["endpoint"]["transport"], "transport-tls-2");
+    assert_eq!(
+        response_json["endpoint"]["media_encryption_optimistic"],
+        "yes"
+    );
+
+    // only the TLS fields in the request are changed
+    let (status, response_json) = send_update(
+        &app,
+        "PATCH",
+        uri.clone(),
+        json!({ "tls": { "media_encryption": "dtls" } }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["endpoint"]["transport"], "transport-tls-2");
+    assert_eq!(response_json["endpoint"]["media_encryption"], "dtls");
+    assert_eq!(
+        response_json["endpoint"]["media_encryption_optimistic"],
+        "yes"
+    );
+
+    // other updates keep the TLS options
+    let (status, response_json) =
+        send_update(&app, "PATCH", uri.clone(), json!({ "context": "users" })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["endpoint"]["transport"], "transport-tls-2");
+    assert_eq!(
+        response_json["endpoint"]["media_encryption_optimistic"],
+        "yes"
+    );
+
+    let (status, response_json) = send_update(
+        &app,
+        "PATCH",
+        uri,
+        json!({ "tls": { "transport_name": " " }, "realm": " " }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["code"], "invalid_fields");
+    let fields: Vec<&str> = response_json["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(fields, vec!["realm", "tls.transport_name"]);
+
+    // TLS options are only allowed for tls accounts
+    let udp_account_id =
+        create_test_account(&app, "tls_update_udp_user", TestAccountOptions::default()).await;
+    let (status, response_json) = send_update(
+        &app,
+        "PATCH",
+        format!("/api/v1/pjsip_realtime/accounts/{}", udp_account_id),
+        json!({ "tls": { "media_encryption": "sdes" } }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["errors"][0]["field"], "tls");
+    assert_eq!(
+        response_json["errors"][0]["message"],
+        "only allowed for transport tls"
+    );
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_update_pjsip_realtime_account_dtls_options() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let account_id = create_test_account(
+        &app,
+        "dtls_update_user",
+        TestAccountOptions {
+            transport: Some("ws"),
+            ..Default::default()
+        },
+    )
+    .await;
+    let uri = format!("/api/v1/pjsip_realtime/accounts/{}", account_id);
+
+    // DTLS options can be changed within the same transport
+    let (status, response_json) = send_update(
+        &app,
+        "PATCH",
+        uri.clone(),
+        json!({ "dtls": { "dtls_verify": "no", "dtls_setup": "passive" } }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["endpoint"]["dtls_verify"], "no");
+    assert_eq!(response_json["endpoint"]["dtls_setup"], "passive");
+    assert_eq!(response_json["endpoint"]["dtls_fingerprint"], "SHA-256");
+
+    // transport change to wss changes only the requested DTLS options
+    let (status, response_json) = send_update(
+        &app,
+        "PATCH",
+        format!("{}?allow_transport_change=true", uri),
+        json!({ "transport": "wss", "dtls": { "dtls_fingerprint": "SHA-1" } }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response_json["endpoint"]["transport"], "wss");
+    assert_eq!(response_json["endpoint"]["dtls_fingerprint"], "SHA-1");
+    assert_eq!(response_json["endpoint"]["dtls_verify"], "no");
+    assert_eq!(response_json["endpoint"]["dtls_setup"], "passive");
+
+    let (status, response_json) = send_update(
+        &app,
+        "PATCH",
+        uri.clone(),
+        json!({ "dtls": { "dtls_verify": "sometimes" } }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["code"], "invalid_fields");
+    assert_eq!(response_json["errors"][0]["field"], "dtls.dtls_verify");
+
+    // DTLS options are only allowed for ws / wss accounts
+    let (status, response_json) = send_update(
+        &app,
+        "PATCH",
+        format!("{}?allow_transport_change=true", uri),
+        json!({ "transport": "tcp", "dtls": { "bundle": "no" } }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["errors"][0]["field"], "dtls");
+    assert_eq!(
+        response_json["errors"][0]["message"],
+        "only allowed for transport ws or wss"
+    );
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
@@ -0,0 +1,242 @@
+use crate::AppState;
+use crate::tests::restapi::api::v1::pjsip_realtime::account_helper::{
+    reset_pjsip_realtime_database, setup_test_state,
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt; // for `collect`
+use serde_json::{Value, json};
+use serial_test::serial;
+use tower::ServiceExt;
+
+async fn send(app: &Router, method: &str, uri: &str, payload: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+fn valid_payload() -> Value {
+    json!({
+        "username": "valid_user",
+        "password": "test_password",
+        "transport": "udp",
+        "context": "from-sipproxy",
+        "from_domain": "sip.example.com",
+        "from_user": "valid_user",
+    })
+}
+
+fn error_fields(response_json: &Value) -> Vec<String> {
+    response_json["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["field"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_pjsip_realtime_account_reports_all_invalid_fields() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let payload = json!({
+        "id": "not-a-ulid",
+        "username": "bad user",
+        "password": "short",
+        "transport": "udp",
+        "context": "",
+        "from_domain": "-invalid-.example.com",
+        "from_user": "x".repeat(51),
+    });
+    let (status, response_json) = send(
+        &app,
+        "POST",
+        "/api/v1/pjsip_realtime/accounts_with_id",
+        payload,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response_json["code"], "invalid_fields");
+    assert_eq!(response_json["status"], 422);
+    assert_eq!(
+        error_fields(&response_json),
+        vec![
+            "id",
+            "username",
+            "password",
+            "context",
+            "from_domain",
+            "from_user"
+        ]
+    );
+    for error in response_json["errors"].as_array().unwrap() {
+        assert!(error["message"].as_str().is_some_and(|m| !m.is_empty()));
+    }
+
+    // 何も作成されていないこと
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pjsip_realtime_accounts")
+        .fetch_one(&state.pjsip_db)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_pjsip_realtime_account_invalid_values() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let cases = [
+        ("username", json!("user@example.com")),
+        ("username", json!("user%2")),
+        ("username", json!("u".repeat(51))),
+        ("password", json!("onlyletters")),
+        ("password", json!("valid_user")),
+        ("password", json!("p".repeat(255) + "_")),
+        ("context", json!("from sipproxy")),
+        ("context", json!("c".repeat(101))),
+        ("from_domain", json!("192.168.0.256")),
+        ("from_domain", json!("example..com")),
+        ("from_domain", json!("[192.168.0.1]")),
+        ("from_domain", json!("sip.example.com:5060")),
+        ("from_user", json!("")),
+    ];
+    for (field, value) in cases {
+        let mut payload = valid_payload();
+        payload[field] = value.clone();
+        for transport in ["udp", "tcp", "tls", "ws"] {
+            payload["transport"] = json!(transport);
+            let (status, response_json) = send(
+                &app,
+                "POST",
+                "/api/v1/pjsip_realtime/accounts",
+                payload.clone(),
+            )
+            .await;
+            assert_eq!(
+                status,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "{}: {} ({})",
+                field,
+                value,
+                transport
+            );
+            assert_eq!(error_fields(&response_json), vec![field]);
+        }
+    }
+}
+
+#[serial]
+#[tokio::test]
+async fn test_create_pjsip_realtime_account_valid_values() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let cases = [
+        ("username", json!("user%40tenant")),
+        ("username", json!("+81-3-1234-5678")),
+        ("from_domain", json!("192.168.0.1")),
+        ("from_domain", json!("2001:db8::1")),
+        ("from_domain", json!("[2001:db8::1]")),
+        ("from_domain", json!("_sip._udp.example.com")),
+        ("from_domain", json!("localhost")),
+    ];
+    for (i, (field, value)) in cases.into_iter().enumerate() {
+        let mut payload = valid_payload();
+        payload["username"] = json!(format!("valid_user_{}", i));
+        payload[field] = value.clone();
+        let (status, response_json) =
+            send(&app, "POST", "/api/v1/pjsip_realtime/accounts", payload).await;
+        assert_eq!(
+            status,
+            StatusCode::CREATED,
+            "{}: {} {}",
+            field,
+            value,
+            response_json
+        );
+    }
+
+    // UUID の ID
+    let mut payload = valid_payload();
+    payload["id"] = json!("6f1c2b4e-8a3d-4e5f-9b7a-1c2d3e4f5a6b");
+    let (status, response_json) = send(
+        &app,
+        "POST",
+        "/api/v1/pjsip_realtime/accounts_with_id",
+        payload,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", response_json);
+    assert_eq!(response_json["id"], "6f1c2b4e-8a3d-4e5f-9b7a-1c2d3e4f5a6b");
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
+
+#[serial]
+#[tokio::test]
+async fn test_update_pjsip_realtime_account_invalid_values() {
+    let state: AppState = setup_test_state().await;
+    let app: Router = crate::restapi::routes::root::create_router(state.clone());
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+
+    let (status, created) = send(
+        &app,
+        "POST",
+        "/api/v1/pjsip_realtime/accounts",
+        valid_payload(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let uri = format!(
+        "/api/v1/pjsip_realtime/accounts/{}",
+        created["id"].as_str().unwrap()
+    );
+
+    let (status, response_json) = send(
+        &app,
+        "PATCH",
+        &uri,
+        json!({ "context": " ", "from_domain": "not a domain" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error_fields(&response_json), vec!["context", "from_domain"]);
+
+    let mut payload = valid_payload();
+    payload["password"] = json!("weak");
+    let (status, response_json) = send(&app, "PUT", &uri, payload).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error_fields(&response_json), vec!["password"]);
+
+    // the password is compared with the current username when only the password changes
+    let username = created["username"].as_str().unwrap();
+    let (status, response_json) = send(
+        &app,
+        "PATCH",
+        &uri,
+        json!({ "password": username.to_uppercase() }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error_fields(&response_json), vec!["password"]);
+
+    reset_pjsip_realtime_database(&state.pjsip_db).await;
+}
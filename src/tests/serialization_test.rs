@@ -10,13 +10,15 @@ mod tests {
         let account = PjsipRealtimeAccountWithId {
             id: "test_id".to_string(),
             username: "test_user".to_string(),
-            password: "test_pass".to_string(),
+            password: Some("test_pass".to_string()),
             transport: TransportType::Ws,
             context: "from-sipproxy".to_string(),
             from_domain: "test.com".to_string(),
             from_user: "test_user".to_string(),
             rtp_timeout: Some(RtpTimeout::ThreeHundred),
             rtp_timeout_hold: Some(RtpTimeout::SixHundred),
+            tls: None,
+            dtls: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };